@@ -0,0 +1,182 @@
+// 管理后台页面逻辑，接口统一返回 { code, msg, data }，失败时 data 为错误信息
+const TOKEN_KEY = 'admin_token';
+
+async function api(method, url, body) {
+  const headers = { 'Content-Type': 'application/json' };
+  const token = sessionStorage.getItem(TOKEN_KEY);
+  if (token) headers['Authorization'] = token;
+
+  const res = await fetch(url, { method, headers, body: body === undefined ? undefined : JSON.stringify(body) });
+  const json = await res.json().catch(() => ({ code: res.status, data: res.statusText }));
+  if (!res.ok || json.code !== 200) {
+    const error = new Error(typeof json.data === 'string' ? json.data : '请求失败');
+    error.status = res.status;
+    throw error;
+  }
+  return json.data;
+}
+
+function toast(icon, title) {
+  Swal.fire({ toast: true, position: 'top-end', timer: 2000, showConfirmButton: false, icon, title });
+}
+
+function adminApp() {
+  return {
+    token: sessionStorage.getItem(TOKEN_KEY),
+    loading: false,
+    tab: 'users',
+    tabs: [
+      { key: 'users', label: '用户管理' },
+      { key: 'employees', label: '员工查询' },
+      { key: 'redis', label: 'Redis' },
+    ],
+
+    loginForm: { userId: '', password: '', challengeId: null, challengeAnswer: '' },
+    challenge: null,
+    mfaTicket: null,
+    mfaCode: '',
+
+    users: { curPage: 1, pageSize: 20, total: 0, records: [], filter: { userId: '', userName: '', locked: '' } },
+    employee: { empNo: '', mobile: '', result: null },
+    employeeFields: [
+      ['工号', 'empNo'], ['姓名', 'empName'], ['部门', 'deptName'], ['职务', 'dutyName'],
+      ['岗位', 'postName'], ['职称', 'jobTitle'], ['手机号', 'mobile'], ['入职日期', 'entryDate'],
+      ['离职日期', 'resignDate'], ['在职', 'activeLabel'],
+    ],
+    redis: { pattern: '', cursor: null, keys: [], selected: null },
+
+    init() {
+      if (this.token) this.loadUsers();
+    },
+
+    async request(method, url, body) {
+      try {
+        return await api(method, url, body);
+      } catch (e) {
+        if (e.status === 401 && this.token) {
+          this.logout();
+          toast('warning', '登录已过期，请重新登录');
+        } else {
+          toast('error', e.message);
+        }
+        throw e;
+      }
+    },
+
+    async loadChallenge() {
+      this.challenge = await api('GET', '/rust/auth/challenge?kind=captcha');
+      this.loginForm.challengeId = this.challenge.challengeId;
+      this.loginForm.challengeAnswer = '';
+    },
+
+    async login() {
+      this.loading = true;
+      try {
+        const data = this.mfaTicket
+          ? await api('POST', '/rust/auth/mfa/verify', { ticket: this.mfaTicket, code: this.mfaCode })
+          : await api('POST', '/rust/auth/login', this.loginForm);
+        if (data.mfaTicket) {
+          this.mfaTicket = data.mfaTicket;
+          return;
+        }
+        this.token = data.Authorization[0];
+        sessionStorage.setItem(TOKEN_KEY, this.token);
+        this.mfaTicket = null;
+        this.mfaCode = '';
+        this.challenge = null;
+        this.loginForm = { userId: '', password: '', challengeId: null, challengeAnswer: '' };
+        this.loadUsers();
+      } catch (e) {
+        // 428 表示需要人机验证，其余错误后也刷新验证码
+        if (e.status === 428 || this.challenge) await this.loadChallenge().catch(() => {});
+        if (e.status === 401 && this.mfaTicket && e.message.includes('重新登录')) this.mfaTicket = null;
+        if (e.status !== 428) toast('error', e.message);
+      } finally {
+        this.loading = false;
+      }
+    },
+
+    logout() {
+      sessionStorage.removeItem(TOKEN_KEY);
+      this.token = null;
+    },
+
+    switchTab(key) {
+      this.tab = key;
+      if (key === 'redis' && this.redis.cursor === null) this.scanKeys(true);
+    },
+
+    userPages() {
+      return Math.max(1, Math.ceil(this.users.total / this.users.pageSize));
+    },
+
+    async loadUsers() {
+      const filter = this.users.filter;
+      const data = await this.request('POST', '/rust/user/page', {
+        curPage: this.users.curPage,
+        pageSize: this.users.pageSize,
+        userId: filter.userId || null,
+        userName: filter.userName || null,
+        locked: filter.locked === '' ? null : Number(filter.locked),
+      });
+      this.users.total = data.total;
+      this.users.records = data.records;
+    },
+
+    async toggleLock(user) {
+      const action = user.locked === 1 ? 'unlock' : 'lock';
+      const { isConfirmed } = await Swal.fire({
+        icon: 'question',
+        title: `确认${action === 'lock' ? '锁定' : '解锁'}用户 ${user.userId}？`,
+        showCancelButton: true,
+        confirmButtonText: '确认',
+        cancelButtonText: '取消',
+      });
+      if (!isConfirmed) return;
+      await this.request('POST', `/rust/user/${encodeURIComponent(user.userId)}/${action}`);
+      toast('success', '操作成功');
+      this.loadUsers();
+    },
+
+    async lookupEmployee() {
+      this.employee.result = null;
+      const data = await this.request('POST', '/rust/employee/lookup', {
+        empNo: this.employee.empNo || null,
+        mobile: this.employee.mobile || null,
+      });
+      this.employee.result = { ...data, activeLabel: data.active ? '是' : '否' };
+    },
+
+    async scanKeys(reset) {
+      if (reset) {
+        this.redis.cursor = 0;
+        this.redis.keys = [];
+        this.redis.selected = null;
+      }
+      const params = new URLSearchParams({ pattern: this.redis.pattern || '*', cursor: this.redis.cursor, count: 200 });
+      const data = await this.request('GET', `/redis/keys?${params}`);
+      this.redis.cursor = data.cursor;
+      this.redis.keys.push(...data.keys);
+    },
+
+    async getKey(key) {
+      const data = await this.request('GET', `/redis/get/${encodeURIComponent(key)}`);
+      this.redis.selected = data;
+    },
+
+    async deleteKey(key) {
+      const { isConfirmed } = await Swal.fire({
+        icon: 'warning',
+        title: `确认删除 ${key}？`,
+        showCancelButton: true,
+        confirmButtonText: '删除',
+        cancelButtonText: '取消',
+      });
+      if (!isConfirmed) return;
+      await this.request('DELETE', `/redis/delete/${encodeURIComponent(key)}`);
+      this.redis.keys = this.redis.keys.filter((k) => k !== key);
+      if (this.redis.selected?.key === key) this.redis.selected = null;
+      toast('success', '删除成功');
+    },
+  };
+}
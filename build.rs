@@ -0,0 +1,80 @@
+//! 构建时为 `assets/` 下的文本资源预先生成 gzip 与 brotli 压缩版本，
+//! 输出到 `$OUT_DIR/compressed_assets/`，运行时按 `Accept-Encoding` 直接返回。
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const ASSETS_DIR: &str = "assets";
+/// 小于该字节数的文件压缩收益不明显
+const MIN_SIZE: u64 = 1024;
+const COMPRESSIBLE_EXTENSIONS: &[&str] = &["html", "css", "js", "mjs", "json", "svg", "txt", "xml", "ico", "map"];
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed={}", ASSETS_DIR);
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set")).join("compressed_assets");
+    // 目录需始终存在，供 rust-embed 引用
+    fs::create_dir_all(&out_dir)?;
+    compress_dir(Path::new(ASSETS_DIR), Path::new(ASSETS_DIR), &out_dir)
+}
+
+fn compress_dir(root: &Path, dir: &Path, out_dir: &Path) -> io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            compress_dir(root, &path, out_dir)?;
+            continue;
+        }
+        let compressible = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| COMPRESSIBLE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        if !compressible || fs::metadata(&path)?.len() < MIN_SIZE {
+            continue;
+        }
+
+        let content = fs::read(&path)?;
+        let target = out_dir.join(path.strip_prefix(root).expect("asset path under root"));
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_if_smaller(&target, "gz", &content, gzip(&content)?)?;
+        write_if_smaller(&target, "br", &content, brotli(&content)?)?;
+    }
+    Ok(())
+}
+
+/// 压缩后体积更小时才写入 `<文件名>.<后缀>`
+fn write_if_smaller(target: &Path, suffix: &str, original: &[u8], compressed: Vec<u8>) -> io::Result<()> {
+    let mut file_name = target.file_name().expect("asset file name").to_os_string();
+    file_name.push(".");
+    file_name.push(suffix);
+    let path = target.with_file_name(file_name);
+    if compressed.len() < original.len() {
+        fs::write(path, compressed)
+    } else {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn gzip(content: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(content)?;
+    encoder.finish()
+}
+
+fn brotli(content: &[u8]) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    {
+        let mut encoder = brotli::CompressorWriter::new(&mut output, 4096, 11, 22);
+        encoder.write_all(content)?;
+    }
+    Ok(output)
+}
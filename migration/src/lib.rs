@@ -1,12 +1,36 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20251120_000001_create_sys_outbox_event;
+mod m20251121_000001_create_sys_audit_log;
+mod m20251122_000001_create_sys_api_key;
+mod m20251123_000001_create_sys_role;
+mod m20251123_000002_add_sys_user_auth_source;
+mod m20251124_000001_add_sys_user_mfa;
+mod m20251125_000001_add_encrypted_columns;
+mod m20251126_000001_create_sys_file;
+mod m20251127_000001_create_sys_user_identity;
+mod m20251128_000001_sys_file_owner_unique;
+mod m20251129_000001_seed_metrics_role;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20251120_000001_create_sys_outbox_event::Migration),
+            Box::new(m20251121_000001_create_sys_audit_log::Migration),
+            Box::new(m20251122_000001_create_sys_api_key::Migration),
+            Box::new(m20251123_000001_create_sys_role::Migration),
+            Box::new(m20251123_000002_add_sys_user_auth_source::Migration),
+            Box::new(m20251124_000001_add_sys_user_mfa::Migration),
+            Box::new(m20251125_000001_add_encrypted_columns::Migration),
+            Box::new(m20251126_000001_create_sys_file::Migration),
+            Box::new(m20251127_000001_create_sys_user_identity::Migration),
+            Box::new(m20251128_000001_sys_file_owner_unique::Migration),
+            Box::new(m20251129_000001_seed_metrics_role::Migration),
+        ]
     }
 }
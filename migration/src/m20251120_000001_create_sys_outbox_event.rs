@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysOutboxEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysOutboxEvent::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SysOutboxEvent::AggregateType).string().not_null())
+                    .col(ColumnDef::new(SysOutboxEvent::AggregateId).string().not_null())
+                    .col(ColumnDef::new(SysOutboxEvent::EventType).string().not_null())
+                    .col(ColumnDef::new(SysOutboxEvent::Topic).string().not_null())
+                    .col(ColumnDef::new(SysOutboxEvent::Payload).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(SysOutboxEvent::Status)
+                            .small_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(SysOutboxEvent::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(SysOutboxEvent::LastError).text())
+                    .col(
+                        ColumnDef::new(SysOutboxEvent::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SysOutboxEvent::NextAttemptAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(SysOutboxEvent::PublishedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_outbox_event_status_id")
+                    .table(SysOutboxEvent::Table)
+                    .col(SysOutboxEvent::Status)
+                    .col(SysOutboxEvent::Id)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysOutboxEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysOutboxEvent {
+    Table,
+    Id,
+    AggregateType,
+    AggregateId,
+    EventType,
+    Topic,
+    Payload,
+    Status,
+    Attempts,
+    LastError,
+    CreatedAt,
+    NextAttemptAt,
+    PublishedAt,
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysAuditLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysAuditLog::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SysAuditLog::Actor).string())
+                    .col(ColumnDef::new(SysAuditLog::RequestId).string())
+                    .col(ColumnDef::new(SysAuditLog::Method).string_len(16).not_null())
+                    .col(ColumnDef::new(SysAuditLog::Route).string().not_null())
+                    .col(ColumnDef::new(SysAuditLog::ClientIp).string())
+                    .col(ColumnDef::new(SysAuditLog::TargetType).string())
+                    .col(ColumnDef::new(SysAuditLog::TargetId).string())
+                    .col(ColumnDef::new(SysAuditLog::Changes).json_binary())
+                    .col(ColumnDef::new(SysAuditLog::Outcome).string_len(16).not_null())
+                    .col(ColumnDef::new(SysAuditLog::StatusCode).integer().not_null())
+                    .col(ColumnDef::new(SysAuditLog::LatencyMs).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(SysAuditLog::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_audit_log_actor_created_at")
+                    .table(SysAuditLog::Table)
+                    .col(SysAuditLog::Actor)
+                    .col(SysAuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_audit_log_target")
+                    .table(SysAuditLog::Table)
+                    .col(SysAuditLog::TargetType)
+                    .col(SysAuditLog::TargetId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysAuditLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysAuditLog {
+    Table,
+    Id,
+    Actor,
+    RequestId,
+    Method,
+    Route,
+    ClientIp,
+    TargetType,
+    TargetId,
+    Changes,
+    Outcome,
+    StatusCode,
+    LatencyMs,
+    CreatedAt,
+}
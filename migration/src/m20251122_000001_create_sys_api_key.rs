@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysApiKey::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysApiKey::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysApiKey::KeyPrefix)
+                            .string_len(32)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(SysApiKey::KeyHash).string_len(64).not_null())
+                    .col(ColumnDef::new(SysApiKey::Name).string().not_null())
+                    .col(ColumnDef::new(SysApiKey::Owner).string().not_null())
+                    .col(ColumnDef::new(SysApiKey::Scopes).string().not_null().default(""))
+                    .col(ColumnDef::new(SysApiKey::ExpiresAt).timestamp())
+                    .col(
+                        ColumnDef::new(SysApiKey::Revoked)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(SysApiKey::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(SysApiKey::LastUsedAt).timestamp())
+                    .col(ColumnDef::new(SysApiKey::RotatedFrom).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_api_key_owner")
+                    .table(SysApiKey::Table)
+                    .col(SysApiKey::Owner)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysApiKey::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysApiKey {
+    Table,
+    Id,
+    KeyPrefix,
+    KeyHash,
+    Name,
+    Owner,
+    Scopes,
+    ExpiresAt,
+    Revoked,
+    CreatedAt,
+    LastUsedAt,
+    RotatedFrom,
+}
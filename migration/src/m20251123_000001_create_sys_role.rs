@@ -0,0 +1,112 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysRole::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysRole::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysRole::RoleCode)
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(SysRole::RoleName).string().not_null())
+                    .col(ColumnDef::new(SysRole::Remark).string())
+                    .col(
+                        ColumnDef::new(SysRole::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysUserRole::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(SysUserRole::UserId).string().not_null())
+                    .col(ColumnDef::new(SysUserRole::RoleCode).string_len(64).not_null())
+                    .col(
+                        ColumnDef::new(SysUserRole::Source)
+                            .string_len(16)
+                            .not_null()
+                            .default("manual"),
+                    )
+                    .col(
+                        ColumnDef::new(SysUserRole::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(SysUserRole::UserId)
+                            .col(SysUserRole::RoleCode),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_user_role_role_code")
+                            .from(SysUserRole::Table, SysUserRole::RoleCode)
+                            .to(SysRole::Table, SysRole::RoleCode)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 内置角色，角色编码与授权范围同名
+        let seed = Query::insert()
+            .into_table(SysRole::Table)
+            .columns([SysRole::RoleCode, SysRole::RoleName, SysRole::Remark])
+            .values_panic(["admin".into(), "管理员".into(), "拥有全部授权范围".into()])
+            .values_panic(["audit:read".into(), "审计员".into(), "查询审计日志".into()])
+            .on_conflict(OnConflict::column(SysRole::RoleCode).do_nothing().to_owned())
+            .to_owned();
+        manager.exec_stmt(seed).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysUserRole::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(SysRole::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysRole {
+    Table,
+    Id,
+    RoleCode,
+    RoleName,
+    Remark,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum SysUserRole {
+    Table,
+    UserId,
+    RoleCode,
+    Source,
+    CreatedAt,
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysUser::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SysUser::MfaEnabled)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column_if_not_exists(ColumnDef::new(SysUser::MfaSecret).string_len(64))
+                    .add_column_if_not_exists(ColumnDef::new(SysUser::MfaRecoveryCodes).text())
+                    .add_column_if_not_exists(ColumnDef::new(SysUser::MfaLastStep).big_integer())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysUser::Table)
+                    .drop_column(SysUser::MfaEnabled)
+                    .drop_column(SysUser::MfaSecret)
+                    .drop_column(SysUser::MfaRecoveryCodes)
+                    .drop_column(SysUser::MfaLastStep)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysUser {
+    Table,
+    MfaEnabled,
+    MfaSecret,
+    MfaRecoveryCodes,
+    MfaLastStep,
+}
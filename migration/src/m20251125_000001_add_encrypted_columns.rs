@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+/// 加密字段改为 text 以容纳密文，并为需要等值查询的字段增加盲索引列。
+/// 存量数据的加密由 `base_web encrypt-existing` 完成。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysUser::Table)
+                    .modify_column(ColumnDef::new(SysUser::EmailPassword).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RsEmployee01::Table)
+                    .modify_column(ColumnDef::new(RsEmployee01::Idcardno).text())
+                    .modify_column(ColumnDef::new(RsEmployee01::Idcardsn).text())
+                    .modify_column(ColumnDef::new(RsEmployee01::Mobileno).text())
+                    .add_column_if_not_exists(ColumnDef::new(RsEmployee01::IdcardnoBidx).string_len(64))
+                    .add_column_if_not_exists(ColumnDef::new(RsEmployee01::MobilenoBidx).string_len(64))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rs_employee01_idcardno_bidx")
+                    .table(RsEmployee01::Table)
+                    .col(RsEmployee01::IdcardnoBidx)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rs_employee01_mobileno_bidx")
+                    .table(RsEmployee01::Table)
+                    .col(RsEmployee01::MobilenoBidx)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    /// 字段恢复为 varchar，已加密的数据需先解密，否则回滚后读到的是密文
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_rs_employee01_idcardno_bidx")
+                    .table(RsEmployee01::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_rs_employee01_mobileno_bidx")
+                    .table(RsEmployee01::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RsEmployee01::Table)
+                    .drop_column(RsEmployee01::IdcardnoBidx)
+                    .drop_column(RsEmployee01::MobilenoBidx)
+                    .modify_column(ColumnDef::new(RsEmployee01::Idcardno).string())
+                    .modify_column(ColumnDef::new(RsEmployee01::Idcardsn).string())
+                    .modify_column(ColumnDef::new(RsEmployee01::Mobileno).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysUser::Table)
+                    .modify_column(ColumnDef::new(SysUser::EmailPassword).string())
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysUser {
+    Table,
+    EmailPassword,
+}
+
+#[derive(Iden)]
+enum RsEmployee01 {
+    #[iden = "rs_employee01"]
+    Table,
+    Idcardno,
+    Idcardsn,
+    Mobileno,
+    IdcardnoBidx,
+    MobilenoBidx,
+}
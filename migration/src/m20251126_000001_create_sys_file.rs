@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysFile::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysFile::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysFile::ContentHash)
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(SysFile::StorageKey).string().not_null())
+                    .col(ColumnDef::new(SysFile::FileName).string())
+                    .col(ColumnDef::new(SysFile::ContentType).string_len(128).not_null())
+                    .col(ColumnDef::new(SysFile::Size).big_integer().not_null())
+                    .col(ColumnDef::new(SysFile::Width).integer())
+                    .col(ColumnDef::new(SysFile::Height).integer())
+                    .col(ColumnDef::new(SysFile::ThumbnailSizes).string_len(64))
+                    .col(ColumnDef::new(SysFile::CreatedBy).string())
+                    .col(
+                        ColumnDef::new(SysFile::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysFile::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysFile {
+    Table,
+    Id,
+    ContentHash,
+    StorageKey,
+    FileName,
+    ContentType,
+    Size,
+    Width,
+    Height,
+    ThumbnailSizes,
+    CreatedBy,
+    CreatedAt,
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysUserIdentity::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysUserIdentity::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SysUserIdentity::Issuer).string().not_null())
+                    .col(ColumnDef::new(SysUserIdentity::Subject).string().not_null())
+                    .col(ColumnDef::new(SysUserIdentity::UserId).string().not_null())
+                    .col(
+                        ColumnDef::new(SysUserIdentity::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("uk_sys_user_identity_issuer_subject")
+                            .col(SysUserIdentity::Issuer)
+                            .col(SysUserIdentity::Subject)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_user_identity_user_id")
+                    .table(SysUserIdentity::Table)
+                    .col(SysUserIdentity::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysUserIdentity::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SysUserIdentity {
+    Table,
+    Id,
+    Issuer,
+    Subject,
+    UserId,
+    CreatedAt,
+}
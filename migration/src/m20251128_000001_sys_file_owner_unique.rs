@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+/// 文件记录按上传用户去重：相同内容由不同用户上传时各自保存一条记录，对象仍只保存一份
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE sys_file DROP CONSTRAINT IF EXISTS sys_file_content_hash_key")
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("uk_sys_file_created_by_content_hash")
+                    .table(SysFile::Table)
+                    .col(SysFile::CreatedBy)
+                    .col(SysFile::ContentHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    /// 回滚前需先清理不同用户上传的重复内容，否则无法恢复内容摘要的唯一约束
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("uk_sys_file_created_by_content_hash")
+                    .table(SysFile::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE sys_file ADD CONSTRAINT sys_file_content_hash_key UNIQUE (content_hash)")
+            .await
+            .map(|_| ())
+    }
+}
+
+#[derive(Iden)]
+enum SysFile {
+    Table,
+    ContentHash,
+    CreatedBy,
+}
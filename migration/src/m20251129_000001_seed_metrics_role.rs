@@ -0,0 +1,33 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let seed = Query::insert()
+            .into_table(SysRole::Table)
+            .columns([SysRole::RoleCode, SysRole::RoleName, SysRole::Remark])
+            .values_panic(["metrics:read".into(), "监控".into(), "拉取运行指标".into()])
+            .on_conflict(OnConflict::column(SysRole::RoleCode).do_nothing().to_owned())
+            .to_owned();
+        manager.exec_stmt(seed).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let delete = Query::delete()
+            .from_table(SysRole::Table)
+            .and_where(Expr::col(SysRole::RoleCode).eq("metrics:read"))
+            .to_owned();
+        manager.exec_stmt(delete).await
+    }
+}
+
+#[derive(Iden)]
+enum SysRole {
+    Table,
+    RoleCode,
+    RoleName,
+    Remark,
+}
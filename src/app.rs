@@ -0,0 +1,46 @@
+//! 应用状态与服务装配
+
+use std::sync::{Arc, OnceLock};
+
+use anyhow::Result;
+use deadpool_redis::Pool;
+use salvo::catcher::Catcher;
+use salvo::prelude::*;
+
+use crate::cache::redis_manager;
+use crate::config::{self, ServerConfig};
+use crate::{db, hoops, routers};
+
+static APP_STATE: OnceLock<Arc<AppState>> = OnceLock::new();
+
+/// 处理器共享的应用状态
+pub struct AppState {
+    pub config: &'static ServerConfig,
+    pub redis: Pool,
+}
+
+impl AppState {
+    /// 连接数据库与 Redis，需在 [`config::init`] 之后调用
+    pub async fn bootstrap() -> Result<Self> {
+        let config = config::get();
+        db::postgres::init(&config.db).await?;
+        let redis = redis_manager::init_redis_pool_with(&config.redis)?;
+        Ok(Self { config, redis })
+    }
+}
+
+pub fn set_app_state(state: AppState) -> Arc<AppState> {
+    APP_STATE.get_or_init(|| Arc::new(state)).clone()
+}
+
+/// 跨域处理挂在服务上而不是路由上，未匹配路由的预检请求（OPTIONS）也能得到响应；
+/// 链路追踪与请求指标同样挂在服务上，未匹配路由的请求以固定的路由标签记录
+pub fn build_service(state: Arc<AppState>) -> Service {
+    let router = routers::root().hoop(hoops::StateInjector::new(state));
+    Service::new(router)
+        .hoop(hoops::cors_hoop())
+        .hoop(RequestId::new())// 添加链路ID，由 salvo 自动记录
+        .hoop(hoops::trace_hoop)// 创建请求 span，继承上游 traceparent
+        .hoop(hoops::metrics::metrics_hoop)// 记录请求指标
+        .catcher(Catcher::default().hoop(hoops::error_404))
+}
@@ -0,0 +1,166 @@
+//! 静态资源
+//!
+//! `assets/` 目录在编译时打包进二进制，部署时只需一个可执行文件；开启
+//! `assets.serve_from_disk` 后改为从磁盘读取，便于开发时调试页面。
+//! 文本资源的 gzip 与 brotli 版本由 `build.rs` 在构建时生成，按 `Accept-Encoding` 选择。
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use rust_embed::RustEmbed;
+
+use crate::config;
+use crate::utils;
+
+pub const ENCODING_BR: &str = "br";
+pub const ENCODING_GZIP: &str = "gzip";
+
+#[derive(RustEmbed)]
+#[folder = "assets/"]
+struct EmbeddedAssets;
+
+/// 构建时生成的压缩版本，文件名为 `<原文件名>.br` 与 `<原文件名>.gz`
+#[derive(RustEmbed)]
+#[folder = "$OUT_DIR/compressed_assets/"]
+struct CompressedAssets;
+
+/// 读取到的静态资源
+pub struct Asset {
+    pub data: Cow<'static, [u8]>,
+    pub content_type: String,
+    /// 内容编码，`None` 表示未压缩
+    pub content_encoding: Option<&'static str>,
+    /// 内容摘要，带引号，可直接作为 ETag 响应头；压缩版本带编码后缀
+    pub etag: String,
+}
+
+/// 按相对路径读取资源，`accept_encoding` 为请求的 `Accept-Encoding`，
+/// 路径不合法或不存在时返回 `None`
+pub async fn load(path: &str, accept_encoding: Option<&str>) -> Option<Asset> {
+    if !is_safe_path(path) {
+        return None;
+    }
+    let config = &config::get().assets;
+    let content_type = mime_guess::from_path(path).first_or_octet_stream().to_string();
+    if config.serve_from_disk {
+        let data = tokio::fs::read(Path::new(&config.dir).join(path)).await.ok()?;
+        let etag = format!("\"{}\"", &utils::sha256_hex(&data)[..32]);
+        return Some(Asset {
+            data: Cow::Owned(data),
+            content_type,
+            content_encoding: None,
+            etag,
+        });
+    }
+
+    let file = EmbeddedAssets::get(path)?;
+    let hash = file
+        .metadata
+        .sha256_hash()
+        .iter()
+        .take(16)
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    for encoding in preferred_encodings(accept_encoding) {
+        let suffix = if encoding == ENCODING_BR { "br" } else { "gz" };
+        if let Some(compressed) = CompressedAssets::get(&format!("{}.{}", path, suffix)) {
+            return Some(Asset {
+                data: compressed.data,
+                content_type,
+                content_encoding: Some(encoding),
+                etag: format!("\"{}-{}\"", hash, suffix),
+            });
+        }
+    }
+    Some(Asset {
+        data: file.data,
+        content_type,
+        content_encoding: None,
+        etag: format!("\"{}\"", hash),
+    })
+}
+
+/// 按客户端支持的编码返回候选列表，同等权重时优先 brotli
+fn preferred_encodings(accept_encoding: Option<&str>) -> Vec<&'static str> {
+    let Some(accept_encoding) = accept_encoding else {
+        return Vec::new();
+    };
+    let mut candidates: Vec<(&'static str, f32)> = Vec::new();
+    for item in accept_encoding.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let quality = parts
+            .find_map(|p| p.trim().strip_prefix("q="))
+            .and_then(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        let encoding = match coding.as_str() {
+            "br" => ENCODING_BR,
+            "gzip" | "x-gzip" => ENCODING_GZIP,
+            _ => continue,
+        };
+        if quality > 0.0 {
+            candidates.push((encoding, quality));
+        }
+    }
+    // 稳定排序，先按 br、gzip 排列再按权重排序
+    candidates.sort_by_key(|(encoding, _)| *encoding != ENCODING_BR);
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    candidates.into_iter().map(|(encoding, _)| encoding).collect()
+}
+
+/// 只允许相对路径，不能包含 `..` 等特殊路径段
+fn is_safe_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.contains('\\')
+        && path
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..")
+}
+
+/// 文件名中带有内容指纹（如 `app.3f2a9c1b.js`）的资源内容不会变化，可长期缓存
+pub fn is_fingerprinted(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let mut segments = file_name.split('.').skip(1).collect::<Vec<_>>();
+    segments.pop();
+    segments
+        .iter()
+        .any(|s| s.len() >= 8 && s.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_safe_path() {
+        assert!(is_safe_path("admin/index.html"));
+        assert!(is_safe_path("js/alpinejs.js"));
+        assert!(!is_safe_path("../Cargo.toml"));
+        assert!(!is_safe_path("admin/../../config.toml"));
+        assert!(!is_safe_path("/etc/passwd"));
+    }
+
+    #[test]
+    fn test_admin_console_is_embedded() {
+        assert!(EmbeddedAssets::get("admin/index.html").is_some());
+        assert!(EmbeddedAssets::get("js/alpinejs.js").is_some());
+        assert!(CompressedAssets::get("js/alpinejs.js.br").is_some());
+        assert!(CompressedAssets::get("js/alpinejs.js.gz").is_some());
+    }
+
+    #[test]
+    fn test_preferred_encodings() {
+        assert_eq!(preferred_encodings(Some("gzip, deflate, br")), vec![ENCODING_BR, ENCODING_GZIP]);
+        assert_eq!(preferred_encodings(Some("br;q=0.5, gzip")), vec![ENCODING_GZIP, ENCODING_BR]);
+        assert_eq!(preferred_encodings(Some("br;q=0, identity")), Vec::<&str>::new());
+        assert!(preferred_encodings(None).is_empty());
+    }
+
+    #[test]
+    fn test_is_fingerprinted() {
+        assert!(is_fingerprinted("js/app.3f2a9c1b.js"));
+        assert!(!is_fingerprinted("js/alpinejs.js"));
+        assert!(!is_fingerprinted("deadbeefcafe.js"));
+    }
+}
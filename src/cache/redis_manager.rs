@@ -5,8 +5,10 @@
 use anyhow::{anyhow, Result};
 use deadpool_redis::{Config, Pool, Runtime};
 use tokio::sync::OnceCell;
+use tokio::time::Instant;
 
 use crate::config::{get, RedisConfig};
+use crate::metrics;
 
 // Redis连接池静态实例
 static REDIS_POOL: OnceCell<Pool> = OnceCell::const_new();
@@ -44,8 +46,13 @@ pub fn pool() -> &'static Pool {
         .expect("Redis pool should be initialized before use")
 }
 
+/// 获取全局 Redis 连接池，未初始化时返回 `None`
+pub fn try_pool() -> Option<&'static Pool> {
+    REDIS_POOL.get()
+}
+
 fn build_redis_url(config: &RedisConfig) -> String {
-    if let Some(password) = &config.password {
+    if let Some(password) = config.password.as_deref().filter(|password| !password.is_empty()) {
         format!(
             "redis://:{}@{}:{}/{}",
             password, config.host, config.port, config.database
@@ -57,9 +64,16 @@ fn build_redis_url(config: &RedisConfig) -> String {
 
 /// 获取Redis连接
 pub async fn get_redis_connection() -> Result<deadpool_redis::Connection> {
-    let conn = pool()
+    get_connection_from(pool()).await
+}
+
+/// 从指定连接池获取连接，并记录等待时间
+pub async fn get_connection_from(pool: &Pool) -> Result<deadpool_redis::Connection> {
+    let start = Instant::now();
+    let conn = pool
         .get()
         .await
-        .map_err(|e| anyhow!("Redis pool not initialized: {}", e))?;
-    Ok(conn)
+        .map_err(|e| anyhow!("Redis pool not initialized: {}", e));
+    metrics::observe_redis_pool_wait(start.elapsed());
+    conn
 }
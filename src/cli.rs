@@ -0,0 +1,143 @@
+//! 命令行子命令
+//!
+//! 不带参数时启动服务，其余子命令执行完即退出：
+//!
+//! - `check-config`（或 `--check-config`）：校验配置并输出脱敏后的生效配置，有错误时列出全部错误
+//! - `encrypt-existing [--batch-size N] [--dry-run]`：加密存量敏感数据，并将旧密文轮换到当前主密钥
+//! - `grant-role <user_id> <role_code>`：为用户分配角色，用于初始化第一个管理员
+
+use anyhow::{anyhow, Result};
+
+use crate::config;
+use crate::db;
+use crate::services::encryption_service::EncryptionService;
+use crate::services::permission::user_service::UserService;
+use crate::utils::crypto_util;
+
+const DEFAULT_BATCH_SIZE: u64 = 500;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Serve,
+    CheckConfig,
+    EncryptExisting { batch_size: u64, dry_run: bool },
+    GrantRole { user_id: String, role_code: String },
+}
+
+/// 解析命令行参数（不含程序名）
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command> {
+    let mut args = args.into_iter();
+    let Some(command) = args.next() else {
+        return Ok(Command::Serve);
+    };
+    match command.as_str() {
+        "serve" => Ok(Command::Serve),
+        "check-config" | "--check-config" => Ok(Command::CheckConfig),
+        "encrypt-existing" => {
+            let mut batch_size = DEFAULT_BATCH_SIZE;
+            let mut dry_run = false;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--dry-run" => dry_run = true,
+                    "--batch-size" => {
+                        batch_size = args
+                            .next()
+                            .and_then(|v| v.parse().ok())
+                            .filter(|v| *v > 0)
+                            .ok_or_else(|| anyhow!("--batch-size 需要正整数"))?;
+                    }
+                    other => return Err(anyhow!("未知参数: {}", other)),
+                }
+            }
+            Ok(Command::EncryptExisting { batch_size, dry_run })
+        }
+        "grant-role" => {
+            let (Some(user_id), Some(role_code), None) = (args.next(), args.next(), args.next()) else {
+                return Err(anyhow!("用法: grant-role <user_id> <role_code>"));
+            };
+            Ok(Command::GrantRole { user_id, role_code })
+        }
+        other => Err(anyhow!(
+            "未知命令: {}，可用命令: serve, check-config, encrypt-existing, grant-role",
+            other
+        )),
+    }
+}
+
+/// 执行子命令
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Serve => Ok(()),
+        Command::CheckConfig => check_config(),
+        Command::EncryptExisting { batch_size, dry_run } => encrypt_existing(batch_size, dry_run).await,
+        Command::GrantRole { user_id, role_code } => grant_role(&user_id, &role_code).await,
+    }
+}
+
+fn check_config() -> Result<()> {
+    let effective = config::check()?;
+    println!("{}", serde_json::to_string_pretty(&effective)?);
+    println!("配置校验通过");
+    Ok(())
+}
+
+async fn encrypt_existing(batch_size: u64, dry_run: bool) -> Result<()> {
+    config::init();
+    let config = config::get();
+    if config.crypto.is_none() {
+        return Err(anyhow!("未配置 [crypto]，无法加密"));
+    }
+    crypto_util::init(config.crypto.as_ref())?;
+    db::postgres::init(&config.db).await?;
+
+    let reports = EncryptionService::encrypt_existing(batch_size, dry_run, db::postgres::pool())
+        .await
+        .map_err(|e| anyhow!("encrypt existing rows failed: {}", e))?;
+    for report in reports {
+        println!(
+            "{}{}: scanned {}, {} {}",
+            if dry_run { "[dry-run] " } else { "" },
+            report.table,
+            report.scanned,
+            if dry_run { "to update" } else { "updated" },
+            report.updated
+        );
+    }
+    Ok(())
+}
+
+async fn grant_role(user_id: &str, role_code: &str) -> Result<()> {
+    config::init();
+    db::postgres::init(&config::get().db).await?;
+    UserService::grant_role(user_id, role_code, db::postgres::pool())
+        .await
+        .map_err(|e| anyhow!("grant role failed: {}", e))?;
+    println!("granted {} to {}", role_code, user_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse(args(&[])).unwrap(), Command::Serve);
+        assert_eq!(
+            parse(args(&["encrypt-existing", "--batch-size", "100", "--dry-run"])).unwrap(),
+            Command::EncryptExisting { batch_size: 100, dry_run: true }
+        );
+        assert!(parse(args(&["encrypt-existing", "--batch-size", "0"])).is_err());
+        assert_eq!(parse(args(&["--check-config"])).unwrap(), Command::CheckConfig);
+        assert_eq!(
+            parse(args(&["grant-role", "u1", "admin"])).unwrap(),
+            Command::GrantRole { user_id: "u1".to_string(), role_code: "admin".to_string() }
+        );
+        assert!(parse(args(&["grant-role", "u1"])).is_err());
+        assert!(parse(args(&["unknown"])).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use crate::AppError;
 
 pub type AppResult<T> = Result<T, AppError>;
 pub type JsonResult<T> = Result<Json<ApiResponse<T>>, AppError>;
+#[allow(dead_code)]
 pub type EmptyResult = Result<Json<Empty>, AppError>;
 
 #[derive(Serialize, ToSchema, Clone, Debug)]
@@ -59,3 +60,13 @@ pub fn empty_ok() -> JsonResult<Empty> {
         data: Empty {},
     }))
 }
+
+/// 分页查询结果
+#[derive(Serialize, ToSchema, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PageData<T> {
+    pub total: u64,
+    pub cur_page: u64,
+    pub page_size: u64,
+    pub records: Vec<T>,
+}
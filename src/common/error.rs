@@ -24,6 +24,8 @@ pub enum AppError {
     Seaorm(#[from] sea_orm::DbErr),
     #[error("validation error:`{0}`")]
     Validation(#[from] validator::ValidationErrors),
+    #[error("rate limited, retry after {0}s")]
+    RateLimited(u64),
 }
 
 impl AppError {
@@ -47,6 +49,13 @@ impl Writer for AppError {
                 (StatusCode::INTERNAL_SERVER_ERROR, msg.clone())
             }
             Self::Validation(_) => (StatusCode::BAD_REQUEST, "Validation error".to_string()),
+            Self::RateLimited(retry_after) => {
+                let _ = res.add_header("Retry-After", retry_after.to_string(), true);
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    format!("请求过于频繁，请{}秒后重试", retry_after),
+                )
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error".to_string()),
         };
         
@@ -84,6 +93,26 @@ impl EndpointOutRegister for AppError {
         );
         operation.responses.insert(
             StatusCode::UNAUTHORIZED.as_str(),
+            error_response.clone(),
+        );
+        operation.responses.insert(
+            StatusCode::FORBIDDEN.as_str(),
+            error_response.clone(),
+        );
+        operation.responses.insert(
+            StatusCode::PAYLOAD_TOO_LARGE.as_str(),
+            error_response.clone(),
+        );
+        operation.responses.insert(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE.as_str(),
+            error_response.clone(),
+        );
+        operation.responses.insert(
+            StatusCode::PRECONDITION_REQUIRED.as_str(),
+            error_response.clone(),
+        );
+        operation.responses.insert(
+            StatusCode::TOO_MANY_REQUESTS.as_str(),
             error_response,
         );
     }
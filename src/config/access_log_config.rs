@@ -0,0 +1,111 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+/// 访问日志配置，按路由开启，记录请求与响应的头和正文
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AccessLogConfig {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub routes: Vec<AccessLogRoute>,
+    /// 正文最多记录的字节数，超出部分截断
+    #[serde(default = "default_max_body")]
+    pub max_body: usize,
+    /// 需要脱敏的 JSON 字段与请求头，不区分大小写
+    #[serde(default = "default_redact")]
+    pub redact: Vec<String>,
+}
+
+/// 开启访问日志的路由
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AccessLogRoute {
+    /// 路由路径，以 `*` 结尾时按前缀匹配
+    pub path: String,
+    /// 请求方法，未配置时匹配所有方法
+    pub method: Option<String>,
+    /// 采样比例，0.0 ~ 1.0
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64,
+}
+
+fn default_max_body() -> usize {
+    4096
+}
+fn default_redact() -> Vec<String> {
+    [
+        "password",
+        "emailPassword",
+        "Authorization",
+        "token",
+        "apiKey",
+        "secret",
+        "otpauthUri",
+        "recoveryCodes",
+        "mfaTicket",
+        "Cookie",
+        "Set-Cookie",
+        "X-Api-Key",
+    ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+fn default_sample_rate() -> f64 {
+    1.0
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            routes: Vec::new(),
+            max_body: default_max_body(),
+            redact: default_redact(),
+        }
+    }
+}
+
+impl AccessLogConfig {
+    pub fn validate(&self) -> Result<()> {
+        for route in &self.routes {
+            if !(0.0..=1.0).contains(&route.sample_rate) {
+                return Err(anyhow!("access_log 路由 {} 的 sample_rate 必须在 0.0 ~ 1.0 之间", route.path));
+            }
+        }
+        Ok(())
+    }
+
+    /// 查找匹配请求的路由，按配置顺序优先
+    pub fn match_route(&self, method: &str, path: &str) -> Option<&AccessLogRoute> {
+        self.routes.iter().find(|route| route.matches(method, path))
+    }
+
+    /// 忽略大小写、`_` 与 `-` 比较字段名，`email_password` 与 `emailPassword` 视为同一字段
+    pub fn is_redacted(&self, name: &str) -> bool {
+        let name = normalize(name);
+        self.redact.iter().any(|field| normalize(field) == name)
+    }
+}
+
+impl AccessLogRoute {
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        if let Some(m) = &self.method
+            && !m.eq_ignore_ascii_case(method)
+        {
+            return false;
+        }
+        match self.path.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => self.path == path,
+        }
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use super::{default_false, default_true};
+
+/// 内嵌静态资源与管理后台配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AssetsConfig {
+    /// 是否开放管理后台页面 `/admin`
+    #[serde(default = "default_true")]
+    pub admin_enabled: bool,
+    /// 从磁盘读取资源而不是使用编译进二进制的版本，便于开发时修改页面后直接刷新
+    #[serde(default = "default_false")]
+    pub serve_from_disk: bool,
+    /// serve_from_disk 开启时的资源目录
+    #[serde(default = "default_dir")]
+    pub dir: String,
+    /// 非 HTML 资源的浏览器缓存时间（秒），HTML 每次通过 ETag 校验
+    #[serde(default = "default_max_age")]
+    pub max_age: u64,
+}
+
+fn default_dir() -> String {
+    "assets".into()
+}
+fn default_max_age() -> u64 {
+    3600
+}
+
+impl Default for AssetsConfig {
+    fn default() -> Self {
+        Self {
+            admin_enabled: true,
+            serve_from_disk: false,
+            dir: default_dir(),
+            max_age: default_max_age(),
+        }
+    }
+}
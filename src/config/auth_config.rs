@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+pub const AUTH_SOURCE_LOCAL: &str = "local";
+pub const AUTH_SOURCE_LDAP: &str = "ldap";
+
+/// 登录认证配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AuthConfig {
+    /// 用户未单独指定认证来源时使用的默认来源: local | ldap
+    #[serde(default = "default_source")]
+    pub default_source: String,
+    pub ldap: Option<LdapConfig>,
+    /// 两步验证中显示的签发方名称
+    #[serde(default = "default_mfa_issuer")]
+    pub mfa_issuer: String,
+    /// 密码校验通过后等待输入验证码的有效期（秒）
+    #[serde(default = "default_mfa_ticket_ttl")]
+    pub mfa_ticket_ttl: usize,
+    /// 允许连续输错验证码的次数，按用户累计，达到后锁定两步验证
+    #[serde(default = "default_mfa_max_attempts")]
+    pub mfa_max_attempts: u64,
+    /// 两步验证锁定时长（秒），期间重新输入密码也无法获取新票据
+    #[serde(default = "default_mfa_lockout_ttl")]
+    pub mfa_lockout_ttl: usize,
+}
+
+/// LDAP / Active Directory 配置，需启用 ldap 特性
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct LdapConfig {
+    /// 服务地址，如 `ldaps://ad.example.com:636`
+    pub url: String,
+    #[serde(default = "default_false")]
+    pub starttls: bool,
+    /// 用于查找用户的服务账号，未配置时匿名查找
+    pub bind_dn: Option<String>,
+    pub bind_password: Option<String>,
+    /// 用户查找的根节点
+    pub base_dn: String,
+    /// 用户查找条件，`{username}` 会被替换为转义后的工号
+    #[serde(default = "default_user_filter")]
+    pub user_filter: String,
+    /// 保存用户所属组的属性
+    #[serde(default = "default_group_attribute")]
+    pub group_attribute: String,
+    /// 组 DN 到角色编码的映射，组 DN 不区分大小写
+    #[serde(default)]
+    pub group_roles: HashMap<String, String>,
+    /// 连接超时（秒）
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_source() -> String {
+    AUTH_SOURCE_LOCAL.into()
+}
+fn default_mfa_issuer() -> String {
+    "base_web".into()
+}
+fn default_mfa_ticket_ttl() -> usize {
+    300
+}
+fn default_mfa_max_attempts() -> u64 {
+    5
+}
+fn default_mfa_lockout_ttl() -> usize {
+    900
+}
+fn default_user_filter() -> String {
+    "(sAMAccountName={username})".into()
+}
+fn default_group_attribute() -> String {
+    "memberOf".into()
+}
+fn default_timeout() -> u64 {
+    5
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            default_source: default_source(),
+            ldap: None,
+            mfa_issuer: default_mfa_issuer(),
+            mfa_ticket_ttl: default_mfa_ticket_ttl(),
+            mfa_max_attempts: default_mfa_max_attempts(),
+            mfa_lockout_ttl: default_mfa_lockout_ttl(),
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn validate(&self) -> Result<()> {
+        match self.default_source.as_str() {
+            AUTH_SOURCE_LOCAL => {}
+            AUTH_SOURCE_LDAP if self.ldap.is_none() => {
+                return Err(anyhow!("auth.default_source 为 ldap 时必须配置 [auth.ldap]"));
+            }
+            AUTH_SOURCE_LDAP => {}
+            other => return Err(anyhow!("auth.default_source 不支持: {}", other)),
+        }
+        if let Some(ldap) = &self.ldap {
+            ldap.validate()?;
+        }
+        // 签发方会拼入 otpauth 地址的 label，不能包含冒号
+        if self.mfa_issuer.trim().is_empty() || self.mfa_issuer.contains(':') {
+            return Err(anyhow!("auth.mfa_issuer 不能为空且不能包含冒号"));
+        }
+        if self.mfa_ticket_ttl == 0 || self.mfa_max_attempts == 0 || self.mfa_lockout_ttl == 0 {
+            return Err(anyhow!("auth.mfa_ticket_ttl/auth.mfa_max_attempts/auth.mfa_lockout_ttl 必须大于 0"));
+        }
+        Ok(())
+    }
+}
+
+impl LdapConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.url.trim().is_empty() {
+            return Err(anyhow!("auth.ldap.url 不能为空"));
+        }
+        if self.base_dn.trim().is_empty() {
+            return Err(anyhow!("auth.ldap.base_dn 不能为空"));
+        }
+        if !self.user_filter.contains("{username}") {
+            return Err(anyhow!("auth.ldap.user_filter 必须包含 {{username}}"));
+        }
+        if self.bind_dn.is_some() != self.bind_password.is_some() {
+            return Err(anyhow!("auth.ldap.bind_dn 与 bind_password 需同时配置"));
+        }
+        Ok(())
+    }
+}
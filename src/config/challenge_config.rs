@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{default_false, default_true};
+
+pub const CHALLENGE_CAPTCHA: &str = "captcha";
+pub const CHALLENGE_POW: &str = "pow";
+
+/// 人机验证配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ChallengeConfig {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    /// 默认下发的验证类型: captcha | pow
+    #[serde(default = "default_kind")]
+    pub kind: String,
+    /// 验证答案在 Redis 中的保存时间（秒）
+    #[serde(default = "default_ttl")]
+    pub ttl: usize,
+    /// 连续登录失败达到该次数后要求验证，0 表示每次登录都要求
+    #[serde(default = "default_login_after_failures")]
+    pub login_after_failures: u64,
+    /// 登录失败计数的统计时长（秒）
+    #[serde(default = "default_failure_window")]
+    pub failure_window: usize,
+    /// 注册时是否总是要求验证
+    #[serde(default = "default_true")]
+    pub registration: bool,
+    /// 工作量证明要求的摘要前导零位数
+    #[serde(default = "default_pow_difficulty")]
+    pub pow_difficulty: u32,
+}
+
+fn default_kind() -> String {
+    CHALLENGE_CAPTCHA.into()
+}
+fn default_ttl() -> usize {
+    120
+}
+fn default_login_after_failures() -> u64 {
+    3
+}
+fn default_failure_window() -> usize {
+    900
+}
+fn default_pow_difficulty() -> u32 {
+    18
+}
+
+impl Default for ChallengeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: default_kind(),
+            ttl: default_ttl(),
+            login_after_failures: default_login_after_failures(),
+            failure_window: default_failure_window(),
+            registration: true,
+            pow_difficulty: default_pow_difficulty(),
+        }
+    }
+}
+
+impl ChallengeConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.kind != CHALLENGE_CAPTCHA && self.kind != CHALLENGE_POW {
+            return Err(anyhow!("challenge.kind 不支持: {}", self.kind));
+        }
+        if self.ttl == 0 || self.failure_window == 0 {
+            return Err(anyhow!("challenge.ttl/challenge.failure_window 必须大于 0"));
+        }
+        if self.pow_difficulty == 0 || self.pow_difficulty > 32 {
+            return Err(anyhow!("challenge.pow_difficulty 必须在 1-32 之间"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::{anyhow, Result};
+use salvo::http::{HeaderName, HeaderValue, Method};
+use serde::{Deserialize, Serialize};
+
+pub const CORS_ANY: &str = "*";
+
+/// 跨域配置，修改后重新加载配置即生效
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CorsConfig {
+    /// 允许的来源，`*` 表示任意来源
+    #[serde(default = "default_any")]
+    pub allow_origins: Vec<String>,
+    #[serde(default = "default_any")]
+    pub allow_methods: Vec<String>,
+    #[serde(default = "default_any")]
+    pub allow_headers: Vec<String>,
+    /// 预检请求的缓存时间（秒）
+    pub max_age: Option<u64>,
+}
+
+fn default_any() -> Vec<String> {
+    vec![CORS_ANY.into()]
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allow_origins: default_any(),
+            allow_methods: default_any(),
+            allow_headers: default_any(),
+            max_age: None,
+        }
+    }
+}
+
+impl CorsConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.allow_origins.is_empty() {
+            return Err(anyhow!("cors.allow_origins 不能为空"));
+        }
+        for origin in self.allow_origins.iter().filter(|o| *o != CORS_ANY) {
+            HeaderValue::from_str(origin).map_err(|_| anyhow!("cors.allow_origins 无效: {}", origin))?;
+        }
+        for method in self.allow_methods.iter().filter(|m| *m != CORS_ANY) {
+            Method::from_bytes(method.as_bytes()).map_err(|_| anyhow!("cors.allow_methods 无效: {}", method))?;
+        }
+        for header in self.allow_headers.iter().filter(|h| *h != CORS_ANY) {
+            HeaderName::from_bytes(header.as_bytes()).map_err(|_| anyhow!("cors.allow_headers 无效: {}", header))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use figment::providers::{Format, Toml};
+use figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// `key` 字段对应的密钥id
+pub const DEFAULT_KEY_ID: &str = "default";
+
+/// 敏感字段加密配置
+///
+/// 主密钥可以直接写在 `keys` 中，也可以放在单独的密钥文件（`key_file`，TOML 格式，
+/// 每行 `密钥id = "Base64密钥"`）。新数据使用 `active_key_id` 加密，旧密钥保留用于解密。
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CryptoConfig {
+    /// 单密钥写法，等同于 `keys.default`
+    pub key: Option<String>,
+    /// 密钥id -> AES-256-GCM 主密钥（Base64 编码的 32 字节）
+    #[serde(default)]
+    pub keys: HashMap<String, String>,
+    /// 密钥文件，与 `keys` 合并，同名时以文件为准
+    pub key_file: Option<String>,
+    /// 加密新数据使用的密钥id
+    #[serde(default = "default_active_key_id")]
+    pub active_key_id: String,
+    /// 盲索引 HMAC 密钥（Base64），不随主密钥轮换；带盲索引的字段写入时需要，配置 `[crypto]` 时必填
+    pub blind_index_key: Option<String>,
+}
+
+fn default_active_key_id() -> String {
+    DEFAULT_KEY_ID.into()
+}
+
+impl CryptoConfig {
+    /// 合并配置与密钥文件中的主密钥
+    pub fn load_keys(&self) -> Result<HashMap<String, Vec<u8>>> {
+        let mut encoded = self.keys.clone();
+        if let Some(key) = &self.key {
+            encoded.entry(DEFAULT_KEY_ID.to_string()).or_insert_with(|| key.clone());
+        }
+        if let Some(key_file) = &self.key_file {
+            let from_file: HashMap<String, String> = Figment::from(Toml::file(key_file))
+                .extract()
+                .map_err(|e| anyhow!("crypto.key_file 读取失败: {}", e))?;
+            encoded.extend(from_file);
+        }
+
+        let mut keys = HashMap::with_capacity(encoded.len());
+        for (key_id, key) in encoded {
+            if key_id.is_empty() || key_id.contains(':') {
+                return Err(anyhow!("crypto 密钥id不能为空且不能包含冒号: {}", key_id));
+            }
+            keys.insert(key_id.clone(), decode_key(&format!("crypto 密钥 {}", key_id), &key)?);
+        }
+        Ok(keys)
+    }
+
+    pub fn blind_index_key(&self) -> Result<Option<Vec<u8>>> {
+        self.blind_index_key
+            .as_deref()
+            .map(|key| decode_key("crypto.blind_index_key", key))
+            .transpose()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let keys = self.load_keys()?;
+        if !keys.contains_key(&self.active_key_id) {
+            return Err(anyhow!("crypto.active_key_id 对应的密钥不存在: {}", self.active_key_id));
+        }
+        if self.blind_index_key()?.is_none() {
+            return Err(anyhow!("crypto.blind_index_key 不能为空"));
+        }
+        Ok(())
+    }
+}
+
+fn decode_key(name: &str, key: &str) -> Result<Vec<u8>> {
+    let key = BASE64_STANDARD
+        .decode(key.trim())
+        .map_err(|_| anyhow!("{} 不是合法的 Base64", name))?;
+    if key.len() != 32 {
+        return Err(anyhow!("{} 必须为 32 字节", name));
+    }
+    Ok(key)
+}
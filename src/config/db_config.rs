@@ -1,14 +1,17 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
-use super::default_false;
+use super::{default_false, report};
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct DbConfig {
-    /// Settings for the primary database. This is usually writeable, but will be read-only in
-    /// some configurations.
-    /// An optional follower database. Always read-only.
+    /// 主库，写操作与未配置只读副本时的读操作都使用主库
     #[serde(alias = "database_url", default)]
     pub url: String,
+    /// 只读副本，只读查询在健康的副本间轮询，全部不可用时回退到主库
+    #[serde(default)]
+    pub replicas: Vec<String>,
 
     #[serde(default = "default_min_connections")]
     pub min_connections: u32,
@@ -22,6 +25,36 @@ pub struct DbConfig {
 
     #[serde(default = "default_false")]
     pub sqlx_logging: bool,
+
+    /// 启动时连接主库失败的重试次数
+    #[serde(default = "default_connect_retries")]
+    pub connect_retries: u32,
+    /// 首次重试的等待时间（毫秒），之后每次翻倍，最长 30 秒
+    #[serde(default = "default_retry_backoff")]
+    pub retry_backoff: u64,
+    /// 只读副本健康检查间隔（秒），检查失败的副本暂停使用，恢复后重新加入
+    #[serde(default = "default_replica_check_interval")]
+    pub replica_check_interval: u64,
+}
+
+impl fmt::Debug for DbConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DbConfig")
+            .field("url", &report::masked_url(&self.url))
+            .field(
+                "replicas",
+                &self.replicas.iter().map(|url| report::masked_url(url)).collect::<Vec<_>>(),
+            )
+            .field("min_connections", &self.min_connections)
+            .field("max_connections", &self.max_connections)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("sqlx_logging", &self.sqlx_logging)
+            .field("connect_retries", &self.connect_retries)
+            .field("retry_backoff", &self.retry_backoff)
+            .field("replica_check_interval", &self.replica_check_interval)
+            .finish()
+    }
 }
 
 fn default_min_connections() -> u32 {
@@ -36,3 +69,12 @@ fn default_idle_timeout() -> u32 {
 fn default_connect_timeout() -> u32 {
     8
 }
+fn default_connect_retries() -> u32 {
+    5
+}
+fn default_retry_backoff() -> u64 {
+    500
+}
+fn default_replica_check_interval() -> u64 {
+    10
+}
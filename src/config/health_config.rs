@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+pub const CHECK_POSTGRES: &str = "postgres";
+pub const CHECK_REDIS: &str = "redis";
+pub const CHECK_BROKER: &str = "broker";
+
+/// 健康检查配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct HealthConfig {
+    /// 关键依赖，任一检查失败时就绪检查返回 503；其余依赖失败只标记为 degraded
+    #[serde(default = "default_critical")]
+    pub critical: Vec<String>,
+    /// 单个依赖检查的默认超时（毫秒）
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+    /// 按依赖覆盖超时（毫秒），如 `{ broker = 5000 }`
+    #[serde(default)]
+    pub timeouts: HashMap<String, u64>,
+    /// 收到停止信号后就绪检查先返回失败，等待该时长（秒）再停止服务，
+    /// 便于负载均衡摘除实例
+    #[serde(default = "default_drain_delay")]
+    pub drain_delay: u64,
+}
+
+fn default_critical() -> Vec<String> {
+    vec![CHECK_POSTGRES.into(), CHECK_REDIS.into()]
+}
+fn default_timeout() -> u64 {
+    2000
+}
+fn default_drain_delay() -> u64 {
+    5
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            critical: default_critical(),
+            timeout: default_timeout(),
+            timeouts: HashMap::new(),
+            drain_delay: default_drain_delay(),
+        }
+    }
+}
+
+impl HealthConfig {
+    pub fn is_critical(&self, check: &str) -> bool {
+        self.critical.iter().any(|c| c == check)
+    }
+
+    /// 依赖检查的超时（毫秒）
+    pub fn timeout_of(&self, check: &str) -> u64 {
+        self.timeouts.get(check).copied().unwrap_or(self.timeout)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let known = [CHECK_POSTGRES, CHECK_REDIS, CHECK_BROKER];
+        if let Some(unknown) = self
+            .critical
+            .iter()
+            .chain(self.timeouts.keys())
+            .find(|c| !known.contains(&c.as_str()))
+        {
+            return Err(anyhow!("health 不支持的依赖: {}", unknown));
+        }
+        if self.timeout == 0 || self.timeouts.values().any(|t| *t == 0) {
+            return Err(anyhow!("health.timeout 必须大于 0"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+pub const DRIVER_KAFKA: &str = "kafka";
+pub const DRIVER_MEMORY: &str = "memory";
+
+/// 消息中间件配置，未配置 `[kafka]` 时使用内存实现。
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct KafkaConfig {
+    /// 驱动类型: kafka | memory
+    #[serde(default = "default_driver")]
+    pub driver: String,
+    #[serde(default)]
+    pub bootstrap_servers: String,
+    #[serde(default = "default_group_id")]
+    pub group_id: String,
+    #[serde(default = "default_false")]
+    pub enable_auto_commit: bool,
+    #[serde(default = "default_auto_offset_reset")]
+    pub auto_offset_reset: String,
+    #[serde(default = "default_ack_mode")]
+    pub ack_mode: String,
+    #[serde(default = "default_security_protocol")]
+    pub security_protocol: String,
+    /// 发送超时时间（毫秒）
+    #[serde(default = "default_send_timeout")]
+    pub send_timeout: u64,
+    /// 消费处理失败后的重试次数，超过后投递到死信主题
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// 死信主题后缀，死信主题名为 `{topic}{suffix}`
+    #[serde(default = "default_dead_letter_suffix")]
+    pub dead_letter_suffix: String,
+}
+
+fn default_driver() -> String {
+    DRIVER_KAFKA.into()
+}
+fn default_group_id() -> String {
+    "base_web_group".into()
+}
+fn default_auto_offset_reset() -> String {
+    "latest".into()
+}
+fn default_ack_mode() -> String {
+    "all".into()
+}
+fn default_security_protocol() -> String {
+    "plaintext".into()
+}
+fn default_send_timeout() -> u64 {
+    5000
+}
+fn default_max_retries() -> u32 {
+    3
+}
+fn default_dead_letter_suffix() -> String {
+    ".dlq".into()
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            driver: DRIVER_MEMORY.into(),
+            bootstrap_servers: String::new(),
+            group_id: default_group_id(),
+            enable_auto_commit: false,
+            auto_offset_reset: default_auto_offset_reset(),
+            ack_mode: default_ack_mode(),
+            security_protocol: default_security_protocol(),
+            send_timeout: default_send_timeout(),
+            max_retries: default_max_retries(),
+            dead_letter_suffix: default_dead_letter_suffix(),
+        }
+    }
+}
+
+impl KafkaConfig {
+    pub fn validate(&self) -> Result<()> {
+        match self.driver.as_str() {
+            DRIVER_KAFKA => {
+                if self.bootstrap_servers.trim().is_empty() {
+                    return Err(anyhow!("kafka.bootstrap_servers 不能为空"));
+                }
+            }
+            DRIVER_MEMORY => {}
+            other => return Err(anyhow!("kafka.driver 不支持: {}", other)),
+        }
+        if self.group_id.trim().is_empty() {
+            return Err(anyhow!("kafka.group_id 不能为空"));
+        }
+        if self.dead_letter_suffix.is_empty() {
+            return Err(anyhow!("kafka.dead_letter_suffix 不能为空"));
+        }
+        Ok(())
+    }
+
+    /// 死信主题名称
+    pub fn dead_letter_topic(&self, topic: &str) -> String {
+        format!("{}{}", topic, self.dead_letter_suffix)
+    }
+}
@@ -1,10 +1,21 @@
 // https://github.com/clia/tracing-config/blob/main/src/lib.rs
-use serde::Deserialize;
-use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::fmt;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
 
-use tracing_appender::rolling;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::Subscriber;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::fmt::{self, writer::BoxMakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
 
+use crate::services::log_service::LogService;
+use crate::telemetry;
+use crate::utils::rolling_file::{Rolling, RollingFile, RollingOptions};
 use crate::utils::timer_util::LocalTimeWithMillis;
 
 use super::default_true;
@@ -13,8 +24,12 @@ const FORMAT_PRETTY: &str = "pretty";
 const FORMAT_COMPACT: &str = "compact";
 const FORMAT_JSON: &str = "json";
 const FORMAT_FULL: &str = "full";
+const FORMATS: [&str; 4] = [FORMAT_PRETTY, FORMAT_COMPACT, FORMAT_JSON, FORMAT_FULL];
+
+pub const SINK_CONSOLE: &str = "console";
+pub const SINK_FILE: &str = "file";
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct LogConfig {
     #[serde(default = "default_filter_level")]
     pub filter_level: String,
@@ -40,7 +55,43 @@ pub struct LogConfig {
     pub with_thread_names: bool,
     #[serde(default = "default_true")]
     pub with_source_location: bool,
+    /// 输出目标，可同时输出到控制台与多个文件，各自使用不同格式；
+    /// 为空时按 `stdout`、`directory`、`file_name`、`rolling`、`format` 输出到单一目标
+    #[serde(default)]
+    pub sinks: Vec<LogSink>,
+}
+
+/// 日志输出目标
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct LogSink {
+    /// console | file
+    pub kind: String,
+    /// 为空时使用 `log.format`
+    pub format: Option<String>,
+    #[serde(default = "default_directory")]
+    pub directory: String,
+    #[serde(default = "default_file_name")]
+    pub file_name: String,
+    /// 按时间切分：minutely | hourly | daily | never
+    #[serde(default = "default_rolling")]
+    pub rolling: String,
+    /// 单个文件的最大字节数，超过后切分
+    pub max_size: Option<u64>,
+    /// 保留的历史文件个数
+    #[serde(default = "default_max_files")]
+    pub max_files: Option<usize>,
+    /// 历史文件保留天数
+    pub max_age: Option<u64>,
+    /// 切分后 gzip 压缩历史文件
+    #[serde(default)]
+    pub compress: bool,
 }
+
+/// 持有各文件输出的后台写入线程，释放时写完缓冲的日志
+pub struct LogGuard {
+    _guards: Vec<WorkerGuard>,
+}
+
 fn default_filter_level() -> String {
     "info".into()
 }
@@ -56,6 +107,9 @@ fn default_rolling() -> String {
 fn default_format() -> String {
     FORMAT_FULL.into()
 }
+fn default_max_files() -> Option<usize> {
+    Some(30)
+}
 
 impl Default for LogConfig {
     fn default() -> Self {
@@ -72,6 +126,7 @@ impl Default for LogConfig {
             with_thread_ids: true,
             with_thread_names: true,
             with_source_location: true,
+            sinks: Vec::new(),
         }
     }
 }
@@ -118,7 +173,7 @@ impl LogConfig {
     /// Will panic on other values.
     pub fn rolling(mut self, rolling: impl Into<String>) -> Self {
         let rolling = rolling.into();
-        if !["minutely", "hourly", "daily", "never"].contains(&&*rolling) {
+        if rolling.parse::<Rolling>().is_err() {
             panic!("Unknown rolling")
         }
         self.rolling = rolling;
@@ -130,11 +185,7 @@ impl LogConfig {
     /// Will panic on other values.
     pub fn format(mut self, format: impl Into<String>) -> Self {
         let format = format.into();
-        if format != FORMAT_PRETTY
-            && format != FORMAT_COMPACT
-            && format != FORMAT_JSON
-            && format != FORMAT_FULL
-        {
+        if !FORMATS.contains(&format.as_str()) {
             panic!("Unknown format")
         }
         self.format = format;
@@ -171,98 +222,132 @@ impl LogConfig {
         self
     }
 
+    pub fn validate(&self) -> Result<()> {
+        if !FORMATS.contains(&self.format.as_str()) {
+            return Err(anyhow!("log.format 不支持: {}，可选 pretty | compact | json | full", self.format));
+        }
+        self.rolling.parse::<Rolling>().map_err(|e| anyhow!("log.rolling {}", e))?;
+        let mut files = HashSet::new();
+        for sink in &self.sinks {
+            sink.validate()?;
+            if sink.kind == SINK_FILE && !files.insert(Path::new(&sink.directory).join(&sink.file_name)) {
+                return Err(anyhow!("log.sinks 存在重复的日志文件: {}/{}", sink.directory, sink.file_name));
+            }
+        }
+        Ok(())
+    }
+
+    /// 生效的输出目标，未配置 `sinks` 时由单一目标的配置生成
+    fn effective_sinks(&self) -> Vec<LogSink> {
+        if !self.sinks.is_empty() {
+            return self.sinks.clone();
+        }
+        vec![LogSink {
+            kind: if self.stdout { SINK_CONSOLE } else { SINK_FILE }.into(),
+            format: Some(self.format.clone()),
+            directory: self.directory.clone(),
+            file_name: self.file_name.clone(),
+            rolling: self.rolling.clone(),
+            max_size: None,
+            max_files: default_max_files(),
+            max_age: None,
+            compress: false,
+        }]
+    }
+
+    fn fmt_layer<S>(&self, sink: &LogSink, writer: BoxMakeWriter) -> Box<dyn Layer<S> + Send + Sync>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let layer = fmt::layer()
+            // 文件中不输出颜色控制符
+            .with_ansi(self.with_ansi && sink.kind == SINK_CONSOLE)
+            .with_timer(LocalTimeWithMillis)
+            .with_level(self.with_level)
+            .with_target(self.with_target)
+            .with_thread_ids(self.with_thread_ids)
+            .with_thread_names(self.with_thread_names)
+            .with_file(self.with_source_location)
+            .with_line_number(self.with_source_location)
+            .with_writer(writer);
+        match sink.format.as_deref().unwrap_or(&self.format) {
+            FORMAT_PRETTY => layer.pretty().boxed(),
+            FORMAT_COMPACT => layer.compact().boxed(),
+            FORMAT_JSON => layer.json().boxed(),
+            _ => layer.boxed(),
+        }
+    }
+
     /// Init tracing log.
     ///
     /// Caller should hold the guard.
-    pub fn guard(&self) -> WorkerGuard {
-        // Tracing appender init.
-        let file_appender = match &*self.rolling {
-            "minutely" => rolling::minutely(&self.directory, &self.file_name),
-            "hourly" => rolling::hourly(&self.directory, &self.file_name),
-            "daily" => rolling::daily(&self.directory, &self.file_name),
-            "never" => rolling::never(&self.directory, &self.file_name),
-            _ => rolling::never(&self.directory, &self.file_name),
-        };
-        let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
-
-        // 创建时间格式化器实例
-        let time_format = LocalTimeWithMillis;
+    pub fn guard(&self) -> Result<LogGuard> {
+        self.validate()?;
 
-        // Tracing subscriber init.
-        let subscriber = tracing_subscriber::fmt()
-            .with_env_filter(
-                tracing_subscriber::EnvFilter::try_from_default_env()
-                    .unwrap_or(tracing_subscriber::EnvFilter::new(&self.filter_level)),
-            )
-            .with_ansi(self.with_ansi)
-            .with_timer(time_format);
-
-        if self.format == FORMAT_PRETTY {
-            let subscriber = subscriber.event_format(
-                fmt::format()
-                    .pretty()
-                    .with_timer(LocalTimeWithMillis)
-                    .with_level(self.with_level)
-                    .with_target(self.with_target)
-                    .with_thread_ids(self.with_thread_ids)
-                    .with_thread_names(self.with_thread_names)
-                    .with_source_location(self.with_source_location),
-            );
-            if self.stdout {
-                subscriber.with_writer(std::io::stdout).init();
-            } else {
-                subscriber.with_writer(file_writer).init();
-            };
-        } else if self.format == FORMAT_COMPACT {
-            let subscriber = subscriber.event_format(
-                fmt::format()
-                    .compact()
-                    .with_timer(LocalTimeWithMillis)
-                    .with_level(self.with_level)
-                    .with_target(self.with_target)
-                    .with_thread_ids(self.with_thread_ids)
-                    .with_thread_names(self.with_thread_names)
-                    .with_source_location(self.with_source_location),
-            );
-            if self.stdout {
-                subscriber.with_writer(std::io::stdout).init();
+        let mut guards = Vec::new();
+        let mut layers = Vec::new();
+        for sink in self.effective_sinks() {
+            let writer = if sink.kind == SINK_CONSOLE {
+                BoxMakeWriter::new(std::io::stdout)
             } else {
-                subscriber.with_writer(file_writer).init();
-            };
-        } else if self.format == FORMAT_JSON {
-            let subscriber = subscriber.event_format(
-                fmt::format()
-                    .json()
-                    .with_timer(LocalTimeWithMillis)
-                    .with_level(self.with_level)
-                    .with_target(self.with_target)
-                    .with_thread_ids(self.with_thread_ids)
-                    .with_thread_names(self.with_thread_names)
-                    .with_source_location(self.with_source_location),
-            );
-            if self.stdout {
-                subscriber.json().with_writer(std::io::stdout).init();
-            } else {
-                subscriber.json().with_writer(file_writer).init();
-            };
-        } else if self.format == FORMAT_FULL {
-            let subscriber = subscriber.event_format(
-                fmt::format()
-                    .with_timer(LocalTimeWithMillis)
-                    .with_level(self.with_level)
-                    .with_target(self.with_target)
-                    .with_thread_ids(self.with_thread_ids)
-                    .with_thread_names(self.with_thread_names)
-                    .with_source_location(self.with_source_location),
-            );
-            if self.stdout {
-                subscriber.with_writer(std::io::stdout).init();
-            } else {
-                subscriber.with_writer(file_writer).init();
+                let (file_writer, guard) = tracing_appender::non_blocking(RollingFile::new(sink.rolling_options()?)?);
+                guards.push(guard);
+                BoxMakeWriter::new(file_writer)
             };
+            layers.push(self.fmt_layer(&sink, writer));
         }
 
+        // 开启链路追踪时同时导出 span，配置未加载时不导出
+        let otel_layer = super::CONFIG.get().and_then(|config| {
+            telemetry::layer(&config.trace).unwrap_or_else(|e| {
+                eprintln!("trace exporter init failed, tracing export disabled: {e}");
+                None
+            })
+        });
+
+        // Tracing subscriber init.
+        tracing_subscriber::registry()
+            .with(LogService::reload_layer(self))
+            .with(otel_layer)
+            .with(layers)
+            .init();
+
         // Caller should hold this handler.
-        guard
+        Ok(LogGuard { _guards: guards })
     }
-}
\ No newline at end of file
+}
+
+impl LogSink {
+    pub fn validate(&self) -> Result<()> {
+        if self.kind != SINK_CONSOLE && self.kind != SINK_FILE {
+            return Err(anyhow!("log.sinks.kind 不支持: {}，可选 console | file", self.kind));
+        }
+        if let Some(format) = &self.format
+            && !FORMATS.contains(&format.as_str())
+        {
+            return Err(anyhow!("log.sinks.format 不支持: {}，可选 pretty | compact | json | full", format));
+        }
+        if self.kind == SINK_FILE {
+            self.rolling.parse::<Rolling>().map_err(|e| anyhow!("log.sinks.rolling {}", e))?;
+            if self.file_name.trim().is_empty() {
+                return Err(anyhow!("log.sinks.file_name 不能为空"));
+            }
+            if self.max_size == Some(0) || self.max_files == Some(0) || self.max_age == Some(0) {
+                return Err(anyhow!("log.sinks 的 max_size/max_files/max_age 必须大于 0"));
+            }
+        }
+        Ok(())
+    }
+
+    fn rolling_options(&self) -> Result<RollingOptions> {
+        Ok(RollingOptions {
+            directory: self.directory.clone().into(),
+            file_name: self.file_name.clone(),
+            rolling: self.rolling.parse()?,
+            max_size: self.max_size,
+            max_files: self.max_files,
+            max_age: self.max_age.map(|days| Duration::from_secs(days * 24 * 60 * 60)),
+            compress: self.compress,
+        })
+    }
+}
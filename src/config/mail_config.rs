@@ -0,0 +1,119 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+pub const MAIL_TRANSPORT_SMTP: &str = "smtp";
+pub const MAIL_TRANSPORT_FILE: &str = "file";
+
+pub const SMTP_TLS_WRAPPER: &str = "wrapper";
+pub const SMTP_TLS_STARTTLS: &str = "starttls";
+pub const SMTP_TLS_NONE: &str = "none";
+
+/// 邮件发送配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct MailConfig {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    /// 发送方式: smtp | file，file 将邮件写入本地目录，便于本地测试
+    #[serde(default = "default_transport")]
+    pub transport: String,
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// 加密方式: wrapper | starttls | none
+    #[serde(default = "default_tls")]
+    pub tls: String,
+    /// 系统发件账号
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// 系统发件人，如 `基础平台 <noreply@example.com>`
+    #[serde(default)]
+    pub from: String,
+    /// file 方式的输出目录
+    #[serde(default = "default_file_dir")]
+    pub file_dir: String,
+    /// 发送队列容量，写满后新邮件会被拒绝
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+    /// 单封邮件的最大发送次数
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// 首次重试间隔（秒），之后按指数退避
+    #[serde(default = "default_retry_backoff")]
+    pub retry_backoff: u64,
+    /// SMTP 超时（秒）
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_transport() -> String {
+    MAIL_TRANSPORT_SMTP.into()
+}
+fn default_port() -> u16 {
+    465
+}
+fn default_tls() -> String {
+    SMTP_TLS_WRAPPER.into()
+}
+fn default_file_dir() -> String {
+    "logs/mail".into()
+}
+fn default_queue_capacity() -> usize {
+    1024
+}
+fn default_max_attempts() -> u32 {
+    5
+}
+fn default_retry_backoff() -> u64 {
+    5
+}
+fn default_timeout() -> u64 {
+    10
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            transport: default_transport(),
+            host: String::new(),
+            port: default_port(),
+            tls: default_tls(),
+            username: None,
+            password: None,
+            from: String::new(),
+            file_dir: default_file_dir(),
+            queue_capacity: default_queue_capacity(),
+            max_attempts: default_max_attempts(),
+            retry_backoff: default_retry_backoff(),
+            timeout: default_timeout(),
+        }
+    }
+}
+
+impl MailConfig {
+    pub fn validate(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        match self.transport.as_str() {
+            MAIL_TRANSPORT_SMTP if self.host.trim().is_empty() => {
+                return Err(anyhow!("mail.host 不能为空"));
+            }
+            MAIL_TRANSPORT_SMTP | MAIL_TRANSPORT_FILE => {}
+            other => return Err(anyhow!("mail.transport 不支持: {}", other)),
+        }
+        if ![SMTP_TLS_WRAPPER, SMTP_TLS_STARTTLS, SMTP_TLS_NONE].contains(&self.tls.as_str()) {
+            return Err(anyhow!("mail.tls 不支持: {}", self.tls));
+        }
+        if self.from.trim().is_empty() {
+            return Err(anyhow!("mail.from 不能为空"));
+        }
+        if self.queue_capacity == 0 || self.max_attempts == 0 {
+            return Err(anyhow!("mail.queue_capacity/mail.max_attempts 必须大于 0"));
+        }
+        Ok(())
+    }
+}
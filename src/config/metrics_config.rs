@@ -0,0 +1,39 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_true;
+
+/// Prometheus 指标配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct MetricsConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 单独监听 `/metrics` 的地址，如 `0.0.0.0:9090`，不校验身份；为空时挂在主服务上并要求 `metrics:read` 授权范围
+    #[serde(default)]
+    pub listen_addr: Option<String>,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            listen_addr: None,
+        }
+    }
+}
+
+impl MetricsConfig {
+    /// 挂在主服务上时返回 true
+    pub fn on_main_server(&self) -> bool {
+        self.enabled && self.listen_addr.is_none()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(addr) = &self.listen_addr
+            && addr.trim().is_empty()
+        {
+            return Err(anyhow!("metrics.listen_addr 不能为空字符串"));
+        }
+        Ok(())
+    }
+}
@@ -1,52 +1,156 @@
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-use anyhow::{anyhow, Result};
-use figment::providers::{Env, Format, Toml};
+use figment::providers::{Env, Format, Serialized, Toml};
 use figment::Figment;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+mod access_log_config;
+pub use access_log_config::AccessLogConfig;
+mod assets_config;
+pub use assets_config::AssetsConfig;
+mod auth_config;
+pub use auth_config::{AuthConfig, AUTH_SOURCE_LDAP, AUTH_SOURCE_LOCAL};
+#[cfg(feature = "ldap")]
+pub use auth_config::LdapConfig;
 mod log_config;
 pub use log_config::LogConfig;
+mod challenge_config;
+pub use challenge_config::{ChallengeConfig, CHALLENGE_CAPTCHA, CHALLENGE_POW};
+mod cors_config;
+pub use cors_config::{CorsConfig, CORS_ANY};
+mod crypto_config;
+pub use crypto_config::{CryptoConfig, DEFAULT_KEY_ID};
 mod db_config;
 pub use db_config::DbConfig;
+mod health_config;
+pub use health_config::{HealthConfig, CHECK_BROKER, CHECK_POSTGRES, CHECK_REDIS};
+mod kafka_config;
+pub use kafka_config::{KafkaConfig, DRIVER_KAFKA};
+mod mail_config;
+pub use mail_config::{
+    MailConfig, MAIL_TRANSPORT_FILE, SMTP_TLS_NONE, SMTP_TLS_STARTTLS,
+};
+mod metrics_config;
+pub use metrics_config::MetricsConfig;
+mod oidc_config;
+pub use oidc_config::OidcConfig;
+mod outbox_config;
+pub use outbox_config::{OutboxConfig, TARGET_REDIS};
+mod storage_config;
+pub use storage_config::{StorageConfig, STORAGE_S3};
+#[cfg(feature = "s3")]
+pub use storage_config::S3Config;
+mod trace_config;
+pub use trace_config::TraceConfig;
+mod rate_limit_config;
+pub use rate_limit_config::{
+    RateLimitConfig, RateLimitRule, BACKEND_MEMORY, KEY_BY_API_KEY, KEY_BY_USER,
+};
 
+mod reload;
+pub use reload::{current, on_reload, spawn_watcher};
+mod report;
+pub use report::{masked_url, ConfigError};
+mod secret;
+mod profile;
+
+/// JWT 签名密钥的最小长度
+const MIN_JWT_SECRET_LEN: usize = 32;
+/// `config.toml` 中的示例密钥，部署时未替换会被拒绝
+const PLACEHOLDER_JWT_SECRET: &str = "change-me-to-a-random-string-of-32-chars";
+
+/// 启动时的配置，重新加载后不变；可重新加载的配置项通过 [`current`] 读取
 pub static CONFIG: OnceLock<ServerConfig> = OnceLock::new();
 
+/// 读取并校验配置，有任何问题时输出全部问题后退出
 pub fn init() {
     CONFIG.get_or_init(|| {
-        let raw_config = Figment::new()
-            .merge(Toml::file(
-                Env::var("APP_CONFIG").as_deref().unwrap_or("config.toml"),
-            ))
-            .merge(Env::prefixed("APP_").global());
-
-        let mut config = match raw_config.extract::<ServerConfig>() {
-            Ok(s) => s,
+        let (config, raw) = match try_load() {
+            Ok(loaded) => loaded,
             Err(e) => {
-                eprintln!(
-                    "It looks like your config is invalid. The following error occurred: {e}"
-                );
+                eprintln!("It looks like your config is invalid. {e}");
                 std::process::exit(1);
             }
         };
-        if config.db.url.is_empty() {
-            config.db.url = std::env::var("DATABASE_URL").unwrap_or_default();
-        }
-        if config.db.url.is_empty() {
-            eprintln!("DATABASE_URL is not set");
-            std::process::exit(1);
-        }
+        reload::set_raw(raw);
         config
     });
 }
 
+fn try_load() -> Result<(ServerConfig, serde_json::Value), ConfigError> {
+    let (config, raw) = load()?;
+    config.validate()?;
+    Ok((config, raw))
+}
+
+/// 以 `test` 环境读取配置，测试使用 `config.test.toml`，不依赖实际的数据库与 Redis 地址
+#[cfg(test)]
+pub fn init_test() {
+    profile::select(profile::PROFILE_TEST);
+    init();
+}
+
+/// 校验配置并返回脱敏后的生效配置，供 `--check-config` 使用
+pub fn check() -> Result<serde_json::Value, ConfigError> {
+    let (config, _) = try_load()?;
+    Ok(report::masked(&config))
+}
+
 pub fn get() -> &'static ServerConfig {
     CONFIG.get().expect("config should be set")
 }
 
-#[derive(Deserialize, Clone, Debug)]
+fn config_path() -> String {
+    Env::var("APP_CONFIG").unwrap_or_else(|| "config.toml".into())
+}
+
+/// 基础配置文件与当前环境的配置文件
+fn config_files() -> Vec<PathBuf> {
+    let base = PathBuf::from(config_path());
+    let profile_file = profile::file_path(&base, profile::active());
+    vec![base, profile_file]
+}
+
+/// 读取 `.env`、各层配置（见 [`profile`]）并替换密钥引用，同时返回原始值
+fn load() -> Result<(ServerConfig, serde_json::Value), ConfigError> {
+    let mut errors = ConfigError::default();
+    // 已存在的环境变量不会被 `.env` 覆盖
+    if let Err(e) = dotenvy::dotenv()
+        && !e.not_found()
+    {
+        errors.push("dotenv", format!(".env 读取失败: {}", e));
+    }
+
+    let profile = profile::active();
+    errors.check("profile", profile::validate(profile));
+    if !errors.issues.is_empty() {
+        return Err(errors);
+    }
+
+    let mut value: figment::value::Value = config_files()
+        .into_iter()
+        .fold(profile::defaults(profile), |figment, file| figment.merge(Toml::file(file)))
+        .merge(Env::prefixed("APP_").global())
+        .extract()?;
+    secret::resolve(&mut value, &mut errors);
+    errors.into_result()?;
+
+    let raw_config = Figment::from(Serialized::defaults(value));
+    let mut config = raw_config.extract::<ServerConfig>()?;
+    if config.db.url.is_empty() {
+        config.db.url = std::env::var("DATABASE_URL").unwrap_or_default();
+    }
+    config.profile = profile.to_string();
+    Ok((config, raw_config.extract()?))
+}
+
+#[derive(Deserialize, Serialize, Clone)]
 pub struct ServerConfig {
+    /// 当前环境，由 `APP_PROFILE` 决定
+    #[serde(skip_deserializing)]
+    pub profile: String,
     #[serde(default = "default_listen_addr")]
     pub listen_addr: String,
 
@@ -55,15 +159,42 @@ pub struct ServerConfig {
     pub jwt: JwtConfig,
     pub redis: RedisConfig,
     pub tls: Option<TlsConfig>,
+    pub kafka: Option<KafkaConfig>,
+    #[serde(default)]
+    pub outbox: OutboxConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    pub oidc: Option<OidcConfig>,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub challenge: ChallengeConfig,
+    #[serde(default)]
+    pub mail: MailConfig,
+    pub crypto: Option<CryptoConfig>,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub assets: AssetsConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub trace: TraceConfig,
+    #[serde(default)]
+    pub access_log: AccessLogConfig,
+    #[serde(default)]
+    pub cors: CorsConfig,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct JwtConfig {
     pub secret: String,
     pub expiry: i64,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct RedisConfig {
     pub host: String,
     pub password: Option<String>,
@@ -76,7 +207,44 @@ pub struct RedisConfig {
     pub min_idle: u32,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+/// 输出与 `--check-config` 相同的脱敏配置
+impl fmt::Debug for ServerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let masked = report::masked(self);
+        if f.alternate() {
+            write!(f, "{:#}", masked)
+        } else {
+            write!(f, "{}", masked)
+        }
+    }
+}
+
+impl fmt::Debug for JwtConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JwtConfig")
+            .field("secret", &report::MASKED)
+            .field("expiry", &self.expiry)
+            .finish()
+    }
+}
+
+impl fmt::Debug for RedisConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisConfig")
+            .field("host", &self.host)
+            .field("password", &self.password.as_ref().map(|_| report::MASKED))
+            .field("port", &self.port)
+            .field("timeout", &self.timeout)
+            .field("database", &self.database)
+            .field("max_active", &self.max_active)
+            .field("max_wait", &self.max_wait)
+            .field("max_idle", &self.max_idle)
+            .field("min_idle", &self.min_idle)
+            .finish()
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct TlsConfig {
     pub cert: String,
     pub key: String,
@@ -97,61 +265,112 @@ fn default_listen_addr() -> String {
 }
 
 impl ServerConfig {
-    pub fn validate(&self) -> Result<()> {
+    /// 校验全部配置段，返回所有问题而不是遇到第一个就停止
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = ConfigError::default();
         if self.listen_addr.trim().is_empty() {
-            return Err(anyhow!("listen_addr 不能为空"));
+            errors.push("listen_addr", "listen_addr 不能为空");
         }
-        self.db.validate()?;
-        self.jwt.validate()?;
-        self.redis.validate()?;
+        self.db.validate(&mut errors);
+        errors.check("log", self.log.validate());
+        self.jwt.validate(&mut errors);
+        self.redis.validate(&mut errors);
         if let Some(tls) = &self.tls {
-            tls.validate()?;
+            tls.validate(&mut errors);
+        }
+        if let Some(kafka) = &self.kafka {
+            errors.check("kafka", kafka.validate());
+        }
+        errors.check("outbox", self.outbox.validate());
+        errors.check("rate_limit", self.rate_limit.validate());
+        if let Some(oidc) = &self.oidc {
+            errors.check("oidc", oidc.validate());
+        }
+        errors.check("auth", self.auth.validate());
+        errors.check("challenge", self.challenge.validate());
+        errors.check("mail", self.mail.validate());
+        if let Some(crypto) = &self.crypto {
+            errors.check("crypto", crypto.validate());
         }
-        Ok(())
+        errors.check("storage", self.storage.validate());
+        errors.check("health", self.health.validate());
+        errors.check("metrics", self.metrics.validate());
+        errors.check("trace", self.trace.validate());
+        errors.check("access_log", self.access_log.validate());
+        errors.check("cors", self.cors.validate());
+        if self.metrics.listen_addr.as_deref() == Some(self.listen_addr.as_str()) {
+            errors.push("metrics", "metrics.listen_addr 不能与 listen_addr 相同");
+        }
+        errors.into_result()
     }
 }
 
 impl DbConfig {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, errors: &mut ConfigError) {
         if self.url.trim().is_empty() {
-            return Err(anyhow!("数据库配置 url 不能为空"));
+            errors.push("db", "db.url 不能为空，也可以通过环境变量 DATABASE_URL 设置");
+        }
+        if self.max_connections == 0 {
+            errors.push("db", "db.max_connections 必须大于 0");
+        }
+        if self.min_connections > self.max_connections {
+            errors.push(
+                "db",
+                format!(
+                    "db.min_connections ({}) 不能大于 db.max_connections ({})",
+                    self.min_connections, self.max_connections
+                ),
+            );
+        }
+        if self.replicas.iter().any(|url| url.trim().is_empty()) {
+            errors.push("db", "db.replicas 不能包含空地址");
+        }
+        if self.replicas.contains(&self.url) {
+            errors.push("db", "db.replicas 不能包含主库地址");
+        }
+        if self.replica_check_interval == 0 {
+            errors.push("db", "db.replica_check_interval 必须大于 0");
         }
-        Ok(())
     }
 }
 
 impl JwtConfig {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, errors: &mut ConfigError) {
         if self.secret.trim().is_empty() {
-            return Err(anyhow!("jwt.secret 不能为空"));
+            errors.push("jwt", "jwt.secret 不能为空");
+        } else if self.secret.len() < MIN_JWT_SECRET_LEN {
+            errors.push("jwt", format!("jwt.secret 强度不足，长度不能少于 {} 个字符", MIN_JWT_SECRET_LEN));
+        } else if self.secret.trim() == PLACEHOLDER_JWT_SECRET {
+            errors.push("jwt", "jwt.secret 仍为示例值，请替换为随机字符串");
+        }
+        if self.expiry <= 0 {
+            errors.push("jwt", format!("jwt.expiry 必须大于 0，当前为 {}", self.expiry));
         }
-        Ok(())
     }
 }
 
 impl RedisConfig {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, errors: &mut ConfigError) {
         if self.host.trim().is_empty() {
-            return Err(anyhow!("redis.host 不能为空"));
+            errors.push("redis", "redis.host 不能为空");
         }
         if self.port == 0 {
-            return Err(anyhow!("redis.port 不能为 0"));
+            errors.push("redis", "redis.port 不能为 0");
+        }
+        if self.max_active == 0 {
+            errors.push("redis", "redis.max_active 必须大于 0");
         }
-        Ok(())
     }
 }
 
 impl TlsConfig {
-    pub fn validate(&self) -> Result<()> {
-        if self.cert.trim().is_empty() || self.key.trim().is_empty() {
-            return Err(anyhow!("tls.cert/tls.key 不能为空"));
-        }
-        if !Path::new(&self.cert).exists() {
-            return Err(anyhow!("tls.cert 文件不存在: {}", self.cert));
-        }
-        if !Path::new(&self.key).exists() {
-            return Err(anyhow!("tls.key 文件不存在: {}", self.key));
+    pub fn validate(&self, errors: &mut ConfigError) {
+        for (field, path) in [("tls.cert", &self.cert), ("tls.key", &self.key)] {
+            if path.trim().is_empty() {
+                errors.push("tls", format!("{} 不能为空", field));
+            } else if let Err(e) = std::fs::File::open(Path::new(path)) {
+                errors.push("tls", format!("{} 文件无法读取: {} ({})", field, path, e));
+            }
         }
-        Ok(())
     }
 }
@@ -0,0 +1,69 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+/// OpenID Connect 单点登录配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OidcConfig {
+    /// 身份提供方地址，发现文档为 `{issuer}/.well-known/openid-configuration`
+    pub issuer: String,
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// 回调地址，需与身份提供方中登记的一致
+    pub redirect_uri: String,
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+    /// 作为工号使用的 ID Token 声明
+    #[serde(default = "default_employee_claim")]
+    pub employee_claim: String,
+    /// 外部身份未绑定且没有已验证邮箱匹配的用户时，是否按该声明自动开通
+    #[serde(default = "default_false")]
+    pub auto_provision: bool,
+    /// 允许的 ID Token 签名算法，JWKS 中的密钥声明了算法时以密钥为准
+    #[serde(default = "default_algorithms")]
+    pub algorithms: Vec<String>,
+    /// state/nonce 在 Redis 中的保存时间（秒）
+    #[serde(default = "default_state_ttl")]
+    pub state_ttl: usize,
+}
+
+fn default_scopes() -> Vec<String> {
+    vec!["openid".into(), "profile".into(), "email".into()]
+}
+fn default_employee_claim() -> String {
+    "preferred_username".into()
+}
+fn default_algorithms() -> Vec<String> {
+    vec!["RS256".into()]
+}
+fn default_state_ttl() -> usize {
+    300
+}
+
+impl OidcConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.issuer.trim().is_empty() {
+            return Err(anyhow!("oidc.issuer 不能为空"));
+        }
+        if self.client_id.trim().is_empty() {
+            return Err(anyhow!("oidc.client_id 不能为空"));
+        }
+        if self.redirect_uri.trim().is_empty() {
+            return Err(anyhow!("oidc.redirect_uri 不能为空"));
+        }
+        if !self.scopes.iter().any(|s| s == "openid") {
+            return Err(anyhow!("oidc.scopes 必须包含 openid"));
+        }
+        if self.algorithms.is_empty() {
+            return Err(anyhow!("oidc.algorithms 不能为空"));
+        }
+        for algorithm in &self.algorithms {
+            algorithm
+                .parse::<jsonwebtoken::Algorithm>()
+                .map_err(|_| anyhow!("oidc.algorithms 不支持: {}", algorithm))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_true;
+
+pub const TARGET_BROKER: &str = "broker";
+pub const TARGET_REDIS: &str = "redis";
+
+/// 事务发件箱中继配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OutboxConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 发布目标: broker | redis
+    #[serde(default = "default_target")]
+    pub target: String,
+    /// 发布到 Redis 时的 stream 前缀，stream 名为 `{prefix}{topic}`
+    #[serde(default = "default_redis_stream_prefix")]
+    pub redis_stream_prefix: String,
+    /// 轮询间隔（毫秒）
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval: u64,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: u64,
+    /// 超过该次数后事件标记为死信，需人工重试
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: i32,
+    /// 待发布超过该时长（秒）的事件视为卡住
+    #[serde(default = "default_stuck_after")]
+    pub stuck_after: u64,
+}
+
+fn default_target() -> String {
+    TARGET_BROKER.into()
+}
+fn default_redis_stream_prefix() -> String {
+    "outbox:".into()
+}
+fn default_poll_interval() -> u64 {
+    1000
+}
+fn default_batch_size() -> u64 {
+    100
+}
+fn default_max_attempts() -> i32 {
+    10
+}
+fn default_stuck_after() -> u64 {
+    300
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target: default_target(),
+            redis_stream_prefix: default_redis_stream_prefix(),
+            poll_interval: default_poll_interval(),
+            batch_size: default_batch_size(),
+            max_attempts: default_max_attempts(),
+            stuck_after: default_stuck_after(),
+        }
+    }
+}
+
+impl OutboxConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.target != TARGET_BROKER && self.target != TARGET_REDIS {
+            return Err(anyhow!("outbox.target 不支持: {}", self.target));
+        }
+        if self.batch_size == 0 {
+            return Err(anyhow!("outbox.batch_size 不能为 0"));
+        }
+        if self.max_attempts <= 0 {
+            return Err(anyhow!("outbox.max_attempts 必须大于 0"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! 配置环境
+//!
+//! `APP_PROFILE` 选择环境，未设置时调试构建为 `dev`、发布构建为 `prod`。配置按以下顺序合并，
+//! 后者覆盖前者：
+//!
+//! 1. 环境默认值，见 [`defaults`]
+//! 2. `config.toml`（或 `APP_CONFIG` 指定的文件）
+//! 3. 同目录下的 `config.{profile}.toml`，不存在时跳过
+//! 4. `APP_` 开头的环境变量
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use figment::providers::{Env, Serialized};
+use figment::Figment;
+
+pub const PROFILE_DEV: &str = "dev";
+#[cfg(test)]
+pub const PROFILE_TEST: &str = "test";
+pub const PROFILE_PROD: &str = "prod";
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// 当前环境，首次调用后不再变化
+pub fn active() -> &'static str {
+    PROFILE.get_or_init(|| {
+        Env::var("APP_PROFILE")
+            .map(|profile| profile.trim().to_ascii_lowercase())
+            .filter(|profile| !profile.is_empty())
+            .unwrap_or_else(|| {
+                if cfg!(debug_assertions) { PROFILE_DEV } else { PROFILE_PROD }.to_string()
+            })
+    })
+}
+
+/// 在读取配置前指定环境，已确定环境时不生效
+#[cfg(test)]
+pub(super) fn select(profile: &str) {
+    let _ = PROFILE.set(profile.to_string());
+}
+
+/// 环境名用于拼接文件名，只允许字母、数字、`-` 与 `_`
+pub(super) fn validate(profile: &str) -> Result<()> {
+    if !profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow!("APP_PROFILE 只能包含字母、数字、- 与 _: {}", profile));
+    }
+    Ok(())
+}
+
+/// 各环境的默认值，配置文件中显式配置的值优先
+pub(super) fn defaults(profile: &str) -> Figment {
+    let figment = Figment::new();
+    match profile {
+        PROFILE_DEV => figment
+            .merge(Serialized::default("db.sqlx_logging", true))
+            .merge(Serialized::default("log.format", "pretty")),
+        PROFILE_PROD => figment
+            .merge(Serialized::default("db.sqlx_logging", false))
+            .merge(Serialized::default("log.format", "json"))
+            .merge(Serialized::default("log.with_ansi", false)),
+        _ => figment,
+    }
+}
+
+/// `config.toml` 对应的 `config.{profile}.toml`
+pub(super) fn file_path(base: &Path, profile: &str) -> PathBuf {
+    let stem = base.file_stem().and_then(|stem| stem.to_str()).unwrap_or("config");
+    let file_name = match base.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) => format!("{}.{}.{}", stem, profile, extension),
+        None => format!("{}.{}", stem, profile),
+    };
+    base.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_path() {
+        assert_eq!(file_path(Path::new("config.toml"), "prod"), PathBuf::from("config.prod.toml"));
+        assert_eq!(
+            file_path(Path::new("/etc/app/server.toml"), "test"),
+            PathBuf::from("/etc/app/server.test.toml")
+        );
+        assert!(validate("../prod").is_err());
+        assert!(validate("staging-2").is_ok());
+    }
+
+    #[test]
+    fn test_profile_defaults() {
+        let format = |profile| defaults(profile).extract_inner::<String>("log.format").ok();
+        assert_eq!(format(PROFILE_PROD).as_deref(), Some("json"));
+        assert_eq!(format(PROFILE_DEV).as_deref(), Some("pretty"));
+        assert_eq!(format("staging"), None);
+    }
+}
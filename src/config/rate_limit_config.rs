@@ -0,0 +1,157 @@
+use std::net::IpAddr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+pub const BACKEND_REDIS: &str = "redis";
+pub const BACKEND_MEMORY: &str = "memory";
+
+pub const KEY_BY_IP: &str = "ip";
+pub const KEY_BY_USER: &str = "user";
+pub const KEY_BY_API_KEY: &str = "api_key";
+
+/// 限流配置，采用滑动窗口计数
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RateLimitConfig {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    /// 计数存储: redis | memory，redis 不可用时自动降级为进程内计数
+    #[serde(default = "default_backend")]
+    pub backend: String,
+    #[serde(default = "default_key_prefix")]
+    pub key_prefix: String,
+    /// 未匹配到任何规则时使用的配额，未配置则不限流
+    pub default: Option<RateLimitRule>,
+    #[serde(default)]
+    pub rules: Vec<RateLimitRule>,
+    /// 可信反向代理的地址或网段（如 `10.0.0.0/8`），只有来自这些地址的请求才读取 `X-Forwarded-For`
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+}
+
+/// 单条限流规则
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RateLimitRule {
+    /// 路由路径，以 `*` 结尾时按前缀匹配
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// 请求方法，未配置时匹配所有方法
+    pub method: Option<String>,
+    /// 计数维度: ip | user | api_key，取不到对应标识时按 ip 计数
+    #[serde(default = "default_key_by")]
+    pub key_by: String,
+    /// 窗口内允许的请求数
+    pub limit: u64,
+    /// 窗口长度（秒）
+    pub window: u64,
+}
+
+fn default_backend() -> String {
+    BACKEND_REDIS.into()
+}
+fn default_key_prefix() -> String {
+    "rate_limit:".into()
+}
+fn default_path() -> String {
+    "*".into()
+}
+fn default_key_by() -> String {
+    KEY_BY_IP.into()
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_backend(),
+            key_prefix: default_key_prefix(),
+            default: None,
+            rules: Vec::new(),
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.backend != BACKEND_REDIS && self.backend != BACKEND_MEMORY {
+            return Err(anyhow!("rate_limit.backend 不支持: {}", self.backend));
+        }
+        for rule in self.rules.iter().chain(self.default.iter()) {
+            rule.validate()?;
+        }
+        for proxy in &self.trusted_proxies {
+            parse_network(proxy)
+                .ok_or_else(|| anyhow!("rate_limit.trusted_proxies 格式错误: {}", proxy))?;
+        }
+        Ok(())
+    }
+
+    /// 地址是否属于可信代理
+    pub fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies
+            .iter()
+            .filter_map(|proxy| parse_network(proxy))
+            .any(|(network, prefix)| in_network(ip, network, prefix))
+    }
+
+    /// 查找匹配请求的规则，按配置顺序优先
+    pub fn match_rule(&self, method: &str, path: &str) -> Option<&RateLimitRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(method, path))
+            .or(self.default.as_ref())
+    }
+}
+
+impl RateLimitRule {
+    pub fn validate(&self) -> Result<()> {
+        if ![KEY_BY_IP, KEY_BY_USER, KEY_BY_API_KEY].contains(&self.key_by.as_str()) {
+            return Err(anyhow!("rate_limit.key_by 不支持: {}", self.key_by));
+        }
+        if self.limit == 0 || self.window == 0 {
+            return Err(anyhow!("rate_limit 规则 {} 的 limit/window 必须大于 0", self.path));
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        if let Some(m) = &self.method
+            && !m.eq_ignore_ascii_case(method)
+        {
+            return false;
+        }
+        match self.path.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => self.path == path,
+        }
+    }
+}
+
+/// 解析 `地址` 或 `地址/前缀长度`
+fn parse_network(text: &str) -> Option<(IpAddr, u32)> {
+    let (addr, prefix) = match text.trim().split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix.parse::<u32>().ok()?)),
+        None => (text.trim(), None),
+    };
+    let addr: IpAddr = addr.parse().ok()?;
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max);
+    (prefix <= max).then_some((addr, prefix))
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u32) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,199 @@
+//! 配置重新加载
+//!
+//! 监听 `APP_CONFIG` 与当前环境配置文件的变化（及 SIGHUP），重新读取并校验配置后替换 [`current`] 返回的快照，
+//! 再依次通知 [`on_reload`] 注册的订阅者。只有日志级别、限流、跨域与访问日志支持重新加载，
+//! 其余配置（如 `listen_addr`、数据库、Redis）变化时拒绝本次加载，需重启生效。
+//! [`super::get`] 始终返回启动时的配置。
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use arc_swap::ArcSwap;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use super::ServerConfig;
+
+/// 支持重新加载的配置项
+const RELOADABLE: [&str; 4] = ["log.filter_level", "rate_limit", "cors", "access_log"];
+
+type Listener = Box<dyn Fn(&ServerConfig) + Send + Sync>;
+
+static CURRENT: OnceLock<ArcSwap<ServerConfig>> = OnceLock::new();
+/// 当前配置的原始值，用于比较不可重新加载的配置项是否变化
+static RAW: Mutex<Option<Value>> = Mutex::new(None);
+static LISTENERS: LazyLock<Mutex<Vec<Listener>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+pub(super) fn set_raw(raw: Value) {
+    *RAW.lock().unwrap() = Some(raw);
+}
+
+/// 最新的配置快照
+pub fn current() -> Arc<ServerConfig> {
+    snapshot().load_full()
+}
+
+fn snapshot() -> &'static ArcSwap<ServerConfig> {
+    CURRENT.get_or_init(|| ArcSwap::from_pointee(super::get().clone()))
+}
+
+/// 注册配置重新加载后的回调
+pub fn on_reload(listener: impl Fn(&ServerConfig) + Send + Sync + 'static) {
+    LISTENERS.lock().unwrap().push(Box::new(listener));
+}
+
+/// 重新读取配置文件，校验通过且只改动了可重新加载的配置项时替换快照
+pub fn reload() -> Result<()> {
+    let (config, raw) = super::load()?;
+    config.validate()?;
+
+    let mut current_raw = RAW.lock().unwrap();
+    if let Some(old) = current_raw.as_ref() {
+        let changed = changed_immutable(old, &raw);
+        if !changed.is_empty() {
+            return Err(anyhow!(
+                "以下配置不支持重新加载，需重启服务生效: {}",
+                changed.into_iter().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        if *old == raw {
+            return Ok(());
+        }
+    }
+    *current_raw = Some(raw);
+
+    let config = Arc::new(config);
+    snapshot().store(config.clone());
+    for listener in LISTENERS.lock().unwrap().iter() {
+        listener(&config);
+    }
+    tracing::info!("config reloaded");
+    Ok(())
+}
+
+/// 不可重新加载且发生变化的顶层配置项
+fn changed_immutable(old: &Value, new: &Value) -> BTreeSet<String> {
+    let (mut old, mut new) = (old.clone(), new.clone());
+    for path in RELOADABLE {
+        remove_path(&mut old, path);
+        remove_path(&mut new, path);
+    }
+    let keys: BTreeSet<&String> = [&old, &new]
+        .into_iter()
+        .filter_map(Value::as_object)
+        .flat_map(|map| map.keys())
+        .collect();
+    keys.into_iter()
+        .filter(|key| old.get(key.as_str()) != new.get(key.as_str()))
+        .cloned()
+        .collect()
+}
+
+fn remove_path(value: &mut Value, path: &str) {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (value.pointer_mut(&format!("/{}", parent.replace('.', "/"))), key),
+        None => (Some(value), path),
+    };
+    if let Some(Value::Object(map)) = parent {
+        map.remove(key);
+    }
+}
+
+/// 监听配置文件与 SIGHUP，变化时重新加载
+pub fn spawn_watcher() -> Result<()> {
+    let files = super::config_files();
+    let file_names: Vec<_> = files
+        .iter()
+        .filter_map(|file| file.file_name())
+        .map(|name| name.to_os_string())
+        .collect();
+    // 环境配置文件与基础配置文件在同一目录
+    let directory = match files[0].parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let (tx, mut rx) = mpsc::channel::<()>(16);
+    let file_tx = tx.clone();
+    // 编辑器通常以替换文件的方式保存，监听所在目录再按文件名过滤
+    let mut watcher = RecommendedWatcher::new(
+        move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            if event.kind.is_access() {
+                return;
+            }
+            let matched = event
+                .paths
+                .iter()
+                .filter_map(|p| p.file_name())
+                .any(|name| file_names.iter().any(|file_name| file_name == name));
+            if matched {
+                let _ = file_tx.try_send(());
+            }
+        },
+        notify::Config::default(),
+    )?;
+    watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+    #[cfg(unix)]
+    {
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())?;
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                tracing::info!("SIGHUP received, reloading config");
+                let _ = tx.send(()).await;
+            }
+        });
+    }
+
+    tokio::spawn(async move {
+        // 任务结束前一直持有监听器
+        let _watcher = watcher;
+        while rx.recv().await.is_some() {
+            // 合并一次保存触发的多个事件
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            while rx.try_recv().is_ok() {}
+            if let Err(e) = reload() {
+                tracing::error!("config reload rejected: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_changed_immutable() {
+        let old = json!({
+            "listen_addr": "127.0.0.1:8008",
+            "log": {"filter_level": "info", "format": "full"},
+            "rate_limit": {"enabled": true},
+        });
+        let reloadable = json!({
+            "listen_addr": "127.0.0.1:8008",
+            "log": {"filter_level": "debug", "format": "full"},
+            "rate_limit": {"enabled": false},
+            "cors": {"allow_origins": ["https://example.com"]},
+        });
+        assert!(changed_immutable(&old, &reloadable).is_empty());
+
+        let immutable = json!({
+            "listen_addr": "0.0.0.0:8008",
+            "log": {"filter_level": "info", "format": "json"},
+            "rate_limit": {"enabled": true},
+        });
+        assert_eq!(
+            changed_immutable(&old, &immutable).into_iter().collect::<Vec<_>>(),
+            vec!["listen_addr", "log"]
+        );
+    }
+}
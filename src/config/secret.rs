@@ -0,0 +1,101 @@
+//! 配置中的密钥引用
+//!
+//! 任意字符串配置项都可以写成引用，在读取配置时替换为实际值，避免明文写入 `config.toml`：
+//!
+//! - `file:/run/secrets/db_password`：读取文件内容，去掉末尾换行
+//! - `env:DB_PASSWORD`：读取环境变量
+//!
+//! `file://` 开头的值视为普通 URL，不做替换。
+
+use std::fs;
+
+use anyhow::{anyhow, Result};
+use figment::value::Value;
+
+use super::ConfigError;
+
+const FILE_PREFIX: &str = "file:";
+const ENV_PREFIX: &str = "env:";
+
+/// 替换配置中的全部密钥引用，无法解析的引用记入 `errors`
+pub(super) fn resolve(value: &mut Value, errors: &mut ConfigError) {
+    resolve_at(value, "", errors);
+}
+
+fn resolve_at(value: &mut Value, path: &str, errors: &mut ConfigError) {
+    match value {
+        Value::String(_, text) => match resolve_reference(text) {
+            Ok(Some(secret)) => *text = secret,
+            Ok(None) => {}
+            Err(e) => {
+                let section = path.split('.').next().unwrap_or(path);
+                errors.push(section, format!("{}: {}", path, e));
+            }
+        },
+        Value::Dict(_, dict) => {
+            for (key, field) in dict.iter_mut() {
+                resolve_at(field, &join(path, key), errors);
+            }
+        }
+        Value::Array(_, items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                resolve_at(item, &join(path, &i.to_string()), errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// 不是引用时返回 None
+fn resolve_reference(text: &str) -> Result<Option<String>> {
+    if let Some(path) = text.strip_prefix(FILE_PREFIX).filter(|path| !path.starts_with("//")) {
+        let content = fs::read_to_string(path.trim())
+            .map_err(|e| anyhow!("密钥文件 {} 读取失败: {}", path.trim(), e))?;
+        return Ok(Some(content.trim_end_matches(['\r', '\n']).to_string()));
+    }
+    if let Some(name) = text.strip_prefix(ENV_PREFIX) {
+        let value = std::env::var(name.trim())
+            .map_err(|_| anyhow!("环境变量 {} 未设置", name.trim()))?;
+        return Ok(Some(value));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use figment::util::map;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_references() {
+        let file = std::env::temp_dir().join(format!("secret-{}", ulid::Ulid::new()));
+        fs::write(&file, "db-password\n").unwrap();
+
+        let mut value = Value::from(map! {
+            "db" => Value::from(map! {
+                "password" => format!("file:{}", file.display()),
+                "url" => "file:///var/lib/app.db".to_string(),
+            }),
+            "jwt" => Value::from(map! { "secret" => "env:PATH".to_string() }),
+            "redis" => Value::from(map! { "password" => "env:SECRET_TEST_MISSING".to_string() }),
+        });
+        let mut errors = ConfigError::default();
+        resolve(&mut value, &mut errors);
+        let _ = fs::remove_file(&file);
+
+        assert_eq!(value.find_ref("db.password").and_then(Value::as_str), Some("db-password"));
+        assert_eq!(value.find_ref("db.url").and_then(Value::as_str), Some("file:///var/lib/app.db"));
+        assert_eq!(value.find_ref("jwt.secret").and_then(Value::as_str), std::env::var("PATH").ok().as_deref());
+        assert_eq!(errors.issues.len(), 1);
+        assert_eq!(errors.issues[0].section, "redis");
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+pub const STORAGE_LOCAL: &str = "local";
+pub const STORAGE_S3: &str = "s3";
+
+/// 文件存储配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct StorageConfig {
+    /// 存储方式: local | s3，s3 需启用 s3 特性
+    #[serde(default = "default_driver")]
+    pub driver: String,
+    /// local 方式的存储目录
+    #[serde(default = "default_local_dir")]
+    pub local_dir: String,
+    /// local 方式生成下载地址使用的对外地址，如 `https://api.example.com`，为空时生成相对地址
+    #[serde(default)]
+    pub public_base_url: String,
+    /// local 方式下载地址的签名密钥
+    #[serde(default)]
+    pub signing_key: String,
+    /// 单个文件的最大字节数
+    #[serde(default = "default_max_size")]
+    pub max_size: usize,
+    /// 允许上传的文件类型，按文件内容识别
+    #[serde(default = "default_allowed_types")]
+    pub allowed_types: Vec<String>,
+    /// 图片缩略图边长（像素）
+    #[serde(default = "default_thumbnail_sizes")]
+    pub thumbnail_sizes: Vec<u32>,
+    /// 下载地址有效期（秒）
+    #[serde(default = "default_url_ttl")]
+    pub url_ttl: u64,
+    pub s3: Option<S3Config>,
+}
+
+/// S3 兼容存储配置（AWS S3、MinIO、OSS 等）
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct S3Config {
+    pub endpoint: String,
+    #[serde(default = "default_region")]
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// 使用路径风格访问，MinIO 等自建服务通常需要开启
+    #[serde(default = "default_false")]
+    pub path_style: bool,
+}
+
+fn default_driver() -> String {
+    STORAGE_LOCAL.into()
+}
+fn default_local_dir() -> String {
+    "data/files".into()
+}
+fn default_max_size() -> usize {
+    5 * 1024 * 1024
+}
+fn default_allowed_types() -> Vec<String> {
+    ["image/png", "image/jpeg", "image/gif", "image/webp"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+fn default_thumbnail_sizes() -> Vec<u32> {
+    vec![64, 256]
+}
+fn default_url_ttl() -> u64 {
+    3600
+}
+fn default_region() -> String {
+    "us-east-1".into()
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            driver: default_driver(),
+            local_dir: default_local_dir(),
+            public_base_url: String::new(),
+            signing_key: String::new(),
+            max_size: default_max_size(),
+            allowed_types: default_allowed_types(),
+            thumbnail_sizes: default_thumbnail_sizes(),
+            url_ttl: default_url_ttl(),
+            s3: None,
+        }
+    }
+}
+
+impl StorageConfig {
+    pub fn validate(&self) -> Result<()> {
+        match self.driver.as_str() {
+            STORAGE_LOCAL => {
+                if self.local_dir.trim().is_empty() {
+                    return Err(anyhow!("storage.local_dir 不能为空"));
+                }
+            }
+            STORAGE_S3 => {
+                let s3 = self
+                    .s3
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("storage.driver 为 s3 时必须配置 [storage.s3]"))?;
+                if s3.endpoint.trim().is_empty() || s3.bucket.trim().is_empty() {
+                    return Err(anyhow!("storage.s3.endpoint/storage.s3.bucket 不能为空"));
+                }
+            }
+            other => return Err(anyhow!("storage.driver 不支持: {}", other)),
+        }
+        if self.max_size == 0 {
+            return Err(anyhow!("storage.max_size 必须大于 0"));
+        }
+        if self.thumbnail_sizes.iter().any(|size| *size == 0 || *size > 2048) {
+            return Err(anyhow!("storage.thumbnail_sizes 必须在 1-2048 之间"));
+        }
+        if self.url_ttl == 0 {
+            return Err(anyhow!("storage.url_ttl 必须大于 0"));
+        }
+        Ok(())
+    }
+}
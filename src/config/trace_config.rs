@@ -0,0 +1,62 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::default_false;
+
+/// OpenTelemetry 链路追踪配置
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TraceConfig {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    /// OTLP/HTTP 采集端地址，需包含 `/v1/traces` 路径
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+    /// 采样比例，0.0 ~ 1.0；上游请求已带采样标记时沿用上游决定
+    #[serde(default = "default_sampling_ratio")]
+    pub sampling_ratio: f64,
+    /// 导出超时（毫秒）
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_endpoint() -> String {
+    "http://127.0.0.1:4318/v1/traces".into()
+}
+fn default_service_name() -> String {
+    env!("CARGO_PKG_NAME").into()
+}
+fn default_sampling_ratio() -> f64 {
+    1.0
+}
+fn default_timeout() -> u64 {
+    3000
+}
+
+impl Default for TraceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: default_endpoint(),
+            service_name: default_service_name(),
+            sampling_ratio: default_sampling_ratio(),
+            timeout: default_timeout(),
+        }
+    }
+}
+
+impl TraceConfig {
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.sampling_ratio) {
+            return Err(anyhow!("trace.sampling_ratio 必须在 0.0 ~ 1.0 之间"));
+        }
+        if self.enabled && !self.endpoint.starts_with("http://") && !self.endpoint.starts_with("https://") {
+            return Err(anyhow!("trace.endpoint 必须是 http(s) 地址: {}", self.endpoint));
+        }
+        if self.service_name.trim().is_empty() {
+            return Err(anyhow!("trace.service_name 不能为空"));
+        }
+        Ok(())
+    }
+}
@@ -1,28 +1,210 @@
+//! Postgres 连接
+//!
+//! 写操作使用主库（[`write`]），只读查询可以使用 [`read`]：配置了只读副本时在健康的副本间轮询，
+//! 副本全部不可用或未配置时回退到主库。副本之间存在复制延迟，写入后需要立即读到的查询应使用主库。
+//!
+//! 启动时主库连接失败会按指数退避重试，副本延迟连接并由后台任务定期检查，检查失败的副本暂停使用，
+//! 恢复后重新加入。
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::OnceLock;
 use std::time::Duration;
 
+use anyhow::{anyhow, Result};
 use sea_orm::entity::prelude::DatabaseConnection;
 use sea_orm::{ConnectOptions, Database};
 
-use crate::config::DbConfig;
+use crate::config::{self, DbConfig};
+use crate::{metrics, telemetry};
+
+/// 重试等待的上限
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
 
 pub static SEAORM_POOL: OnceLock<DatabaseConnection> = OnceLock::new();
+static REPLICAS: OnceLock<Vec<Replica>> = OnceLock::new();
+/// 轮询副本的游标
+static NEXT_REPLICA: AtomicUsize = AtomicUsize::new(0);
+
+struct Replica {
+    /// 脱敏后的地址，用于日志
+    name: String,
+    conn: DatabaseConnection,
+    healthy: AtomicBool,
+}
+
+pub async fn init(config: &DbConfig) -> Result<()> {
+    let pool = connect_with_retry(config).await?;
+    SEAORM_POOL
+        .set(pool)
+        .map_err(|_| anyhow!("seaorm pool should be set once"))?;
 
-pub async fn init(config: &DbConfig) {
-    let mut opt = ConnectOptions::new(config.url.to_owned());
+    // 副本延迟连接，不可用时不影响启动，首次健康检查通过后才开始使用
+    let mut replicas = Vec::with_capacity(config.replicas.len());
+    for url in &config.replicas {
+        let mut opt = options(config, url);
+        opt.connect_lazy(true);
+        let conn = Database::connect(opt)
+            .await
+            .map_err(|e| anyhow!("只读副本 {} 配置无效: {}", config::masked_url(url), e))?;
+        replicas.push(Replica {
+            name: config::masked_url(url),
+            conn: instrument(conn),
+            healthy: AtomicBool::new(false),
+        });
+    }
+    if !replicas.is_empty() {
+        let _ = REPLICAS.set(replicas);
+        spawn_replica_monitor(
+            Duration::from_secs(config.replica_check_interval),
+            Duration::from_secs(config.connect_timeout as u64),
+        );
+    }
+    Ok(())
+}
+
+fn options(config: &DbConfig, url: &str) -> ConnectOptions {
+    let mut opt = ConnectOptions::new(url.to_owned());
     opt.max_connections(config.max_connections)
         .min_connections(config.min_connections)
         .connect_timeout(Duration::from_secs(config.connect_timeout as u64))
         .idle_timeout(Duration::from_secs(config.idle_timeout as u64))
         .sqlx_logging(config.sqlx_logging)
         .sqlx_logging_level(log::LevelFilter::Debug); // 设置SQL日志级别
+    opt
+}
 
-    let pool = Database::connect(opt)
-        .await
-        .expect("db connection should connect");
-    SEAORM_POOL.set(pool).expect("seaorm pool should be set");
+/// 连接主库，失败时按 `retry_backoff` 指数退避重试 `connect_retries` 次
+async fn connect_with_retry(config: &DbConfig) -> Result<DatabaseConnection> {
+    let name = config::masked_url(&config.url);
+    let mut backoff = Duration::from_millis(config.retry_backoff);
+    let mut attempt = 0;
+    loop {
+        match Database::connect(options(config, &config.url)).await {
+            Ok(conn) => return Ok(instrument(conn)),
+            Err(e) if attempt < config.connect_retries => {
+                attempt += 1;
+                tracing::warn!(
+                    attempt,
+                    "connect database {} failed: {}, retry in {:?}",
+                    name,
+                    e,
+                    backoff
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            Err(e) => {
+                return Err(anyhow!("连接数据库 {} 失败（已重试 {} 次）: {}", name, attempt, e));
+            }
+        }
+    }
 }
 
-pub fn pool() -> &'static DatabaseConnection {
+/// 回调在执行 SQL 的任务中同步调用，需在连接放入全局之前注册
+fn instrument(mut conn: DatabaseConnection) -> DatabaseConnection {
+    conn.set_metric_callback(|info| {
+        metrics::observe_query(info);
+        telemetry::record_query(info);
+    });
+    conn
+}
+
+/// 定期检查副本，失败的副本暂停使用，恢复后重新加入
+fn spawn_replica_monitor(interval: Duration, timeout: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            for replica in REPLICAS.get().into_iter().flatten() {
+                let result = match tokio::time::timeout(timeout, replica.conn.ping()).await {
+                    Ok(result) => result.map_err(|e| e.to_string()),
+                    Err(_) => Err(format!("timed out after {:?}", timeout)),
+                };
+                let was_healthy = replica.healthy.swap(result.is_ok(), Ordering::AcqRel);
+                match result {
+                    Ok(()) if !was_healthy => tracing::info!("db replica {} is available", replica.name),
+                    Err(e) if was_healthy => tracing::warn!("db replica {} ejected: {}", replica.name, e),
+                    _ => {}
+                }
+            }
+        }
+    });
+}
+
+/// 主库连接，用于写操作及需要读到最新数据的查询
+pub fn write() -> &'static DatabaseConnection {
     SEAORM_POOL.get().expect("seaorm pool should set")
-}
\ No newline at end of file
+}
+
+/// 只读查询的连接，优先使用健康的副本
+pub fn read() -> &'static DatabaseConnection {
+    let replicas = REPLICAS.get().map(Vec::as_slice).unwrap_or_default();
+    let start = NEXT_REPLICA.fetch_add(1, Ordering::Relaxed);
+    route(replicas, start, write)
+}
+
+/// 选择只读连接：健康的副本优先，没有健康的副本时回退到主库
+fn route<'a>(
+    replicas: &'a [Replica],
+    start: usize,
+    primary: impl FnOnce() -> &'a DatabaseConnection,
+) -> &'a DatabaseConnection {
+    pick(replicas, start).map(|replica| &replica.conn).unwrap_or_else(primary)
+}
+
+/// 从 `start` 开始找到第一个健康的副本
+fn pick(replicas: &[Replica], start: usize) -> Option<&Replica> {
+    (0..replicas.len())
+        .map(|i| &replicas[(start + i) % replicas.len()])
+        .find(|replica| replica.healthy.load(Ordering::Acquire))
+}
+
+/// 同 [`write`]
+pub fn pool() -> &'static DatabaseConnection {
+    write()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica(name: &str, healthy: bool) -> Replica {
+        Replica {
+            name: name.to_string(),
+            conn: DatabaseConnection::Disconnected,
+            healthy: AtomicBool::new(healthy),
+        }
+    }
+
+    #[test]
+    fn test_pick_skips_ejected_replicas() {
+        let replicas = vec![replica("a", true), replica("b", false), replica("c", true)];
+        let picked: Vec<_> = (0..4).map(|i| pick(&replicas, i).unwrap().name.as_str()).collect();
+        assert_eq!(picked, vec!["a", "c", "c", "a"]);
+
+        replicas.iter().for_each(|r| r.healthy.store(false, Ordering::Release));
+        assert!(pick(&replicas, 0).is_none());
+        assert!(pick(&[], 0).is_none());
+    }
+
+    #[test]
+    fn test_route_falls_back_to_primary() {
+        let primary = DatabaseConnection::Disconnected;
+        let replicas = vec![replica("a", true), replica("b", true)];
+        let routed = |start| route(&replicas, start, || &primary);
+
+        assert!(std::ptr::eq(routed(0), &replicas[0].conn));
+        assert!(std::ptr::eq(routed(1), &replicas[1].conn));
+
+        replicas[0].healthy.store(false, Ordering::Release);
+        assert!(std::ptr::eq(routed(0), &replicas[1].conn));
+
+        replicas[1].healthy.store(false, Ordering::Release);
+        assert!(std::ptr::eq(routed(0), &primary));
+        assert!(std::ptr::eq(route(&[], 0, || &primary), &primary));
+
+        // 恢复的副本重新参与路由
+        replicas[0].healthy.store(true, Ordering::Release);
+        assert!(std::ptr::eq(routed(1), &replicas[0].conn));
+    }
+}
@@ -0,0 +1,41 @@
+//! 加密字段的 SeaORM 集成
+//!
+//! 在 `ActiveModelBehavior::before_save` 中调用 [`seal`]，写入前将明文加密并计算盲索引。
+//! 只有通过 `ActiveModelTrait::insert/update/save` 写入时才会触发，
+//! 直接使用 `Entity::insert/update` 时需自行加密。
+
+use sea_orm::{ActiveValue, DbErr};
+
+use crate::utils::crypto_util;
+
+/// 加密字段：明文加密保存并同步盲索引，能够解密的密文保持不变，置空时同时清空盲索引。
+/// 只带 `enc:` 前缀但无法解密的值按明文加密，避免伪造前缀绕过加密
+pub fn seal(
+    value: &mut ActiveValue<Option<String>>,
+    blind_index: Option<&mut ActiveValue<Option<String>>>,
+) -> Result<(), DbErr> {
+    let ActiveValue::Set(current) = value else {
+        return Ok(());
+    };
+    let plain = match current.as_deref() {
+        Some(v) if crypto_util::is_valid_ciphertext(v) => return Ok(()),
+        Some(v) if !v.is_empty() => v.to_string(),
+        _ => {
+            if let Some(index) = blind_index {
+                *index = ActiveValue::Set(None);
+            }
+            return Ok(());
+        }
+    };
+
+    if let Some(index) = blind_index {
+        *index = ActiveValue::Set(Some(crypto_util::blind_index(&plain).map_err(to_db_err)?));
+    }
+    *value = ActiveValue::Set(Some(crypto_util::encrypt(&plain).map_err(to_db_err)?));
+    Ok(())
+}
+
+fn to_db_err(e: anyhow::Error) -> DbErr {
+    tracing::error!("seal encrypted column failed: {}", e);
+    DbErr::Custom(format!("encrypt column failed: {}", e))
+}
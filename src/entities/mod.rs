@@ -2,4 +2,7 @@
 
 pub mod prelude;
 
+pub mod encryption;
+
 pub mod permission;
+pub mod system;
@@ -1,2 +1,6 @@
 pub mod sys_user;
-pub mod rs_employee01;
\ No newline at end of file
+pub mod rs_employee01;
+pub mod sys_api_key;
+pub mod sys_role;
+pub mod sys_user_role;
+pub mod sys_user_identity;
@@ -2,6 +2,9 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use chrono::NaiveDate;
 
+use crate::entities::encryption;
+use crate::utils::crypto_util;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "rs_employee01")]
 pub struct Model {
@@ -25,8 +28,16 @@ pub struct Model {
 
     pub idtype: Option<i16>,
 
+    /// 证件号密文
+    #[serde(skip_serializing)]
     pub idcardno: Option<String>,
 
+    /// 证件号盲索引，用于等值查询
+    #[serde(skip_serializing)]
+    pub idcardno_bidx: Option<String>,
+
+    /// 证件序列号密文
+    #[serde(skip_serializing)]
     pub idcardsn: Option<String>,
 
     pub jobtitle: Option<String>,
@@ -42,8 +53,14 @@ pub struct Model {
 
     pub idcardaddr: Option<String>,
 
+    /// 手机号密文
+    #[serde(skip_serializing)]
     pub mobileno: Option<String>,
 
+    /// 手机号盲索引，用于等值查询
+    #[serde(skip_serializing)]
+    pub mobileno_bidx: Option<String>,
+
     pub entrydate: Option<NaiveDate>,
 
     pub resigndate: Option<NaiveDate>,
@@ -79,7 +96,19 @@ pub struct Model {
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// 证件号、证件序列号与手机号加密保存，并同步盲索引
+    async fn before_save<C>(mut self, _db: &C, _insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        encryption::seal(&mut self.idcardno, Some(&mut self.idcardno_bidx))?;
+        encryption::seal(&mut self.idcardsn, None)?;
+        encryption::seal(&mut self.mobileno, Some(&mut self.mobileno_bidx))?;
+        Ok(self)
+    }
+}
 
 impl Model {
     /// 检查员工是否在职
@@ -87,4 +116,9 @@ impl Model {
         self.statusid.map(|s| s == 1).unwrap_or(false) &&
             self.resigndate.is_none()
     }
+
+    /// 解密后的手机号
+    pub fn mobileno_plain(&self) -> anyhow::Result<Option<String>> {
+        crypto_util::decrypt_opt(self.mobileno.as_deref())
+    }
 }
\ No newline at end of file
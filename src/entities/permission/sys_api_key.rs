@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "sys_api_key")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+
+    /// 密钥前缀，明文保存用于定位密钥
+    #[sea_orm(unique)]
+    pub key_prefix: String,
+    /// 密钥 SHA-256 摘要
+    #[serde(skip_serializing)]
+    pub key_hash: String,
+    pub name: String,
+    /// 所属用户
+    pub owner: String,
+    /// 授权范围，逗号分隔
+    pub scopes: String,
+    pub expires_at: Option<DateTime>,
+    pub revoked: i32,
+    pub created_at: DateTime,
+    pub last_used_at: Option<DateTime>,
+    /// 轮换前的密钥id
+    pub rotated_from: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 检查密钥是否已吊销,1为吊销
+    pub fn is_revoked(&self) -> bool {
+        self.revoked == 1
+    }
+
+    /// 检查密钥在指定时间是否已过期
+    pub fn is_expired(&self, now: DateTime) -> bool {
+        self.expires_at.map(|exp| exp <= now).unwrap_or(false)
+    }
+
+    pub fn scope_list(&self) -> Vec<String> {
+        self.scopes
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
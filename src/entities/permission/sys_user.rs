@@ -1,6 +1,8 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::entities::encryption;
+
 #[derive(Debug, Clone, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, Default)]
 #[sea_orm(table_name="sys_user")]
 pub struct Model {
@@ -22,14 +24,39 @@ pub struct Model {
     pub reg_time: Option<DateTime>,
     pub locked: i32,
     pub is_valid: i32,
+    /// 邮箱密码密文，见 `utils::crypto_util`
+    #[serde(skip_serializing)]
     pub email_password: Option<String>,
+    /// 认证来源: local | ldap，为空时使用全局默认来源
+    pub auth_source: Option<String>,
+    /// 是否开启两步验证,1为开启
+    pub mfa_enabled: i32,
+    /// TOTP 密钥（Base32）密文，见 `utils::crypto_util`
+    #[serde(skip_serializing)]
+    pub mfa_secret: Option<String>,
+    /// 恢复码 SHA-256 摘要，逗号分隔，使用后移除
+    #[serde(skip_serializing)]
+    pub mfa_recovery_codes: Option<String>,
+    /// 最近一次通过校验的 TOTP 时间步，防止验证码重放
+    pub mfa_last_step: Option<i64>,
 
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// 邮箱密码与 TOTP 密钥加密保存
+    async fn before_save<C>(mut self, _db: &C, _insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        encryption::seal(&mut self.email_password, None)?;
+        encryption::seal(&mut self.mfa_secret, None)?;
+        Ok(self)
+    }
+}
 
 impl Model {
     /// 检查用户是否被锁定,1为锁定
@@ -41,4 +68,9 @@ impl Model {
     pub fn is_valid(&self) -> bool {
         self.is_valid == 0
     }
+
+    /// 检查用户是否开启两步验证,1为开启
+    pub fn is_mfa_enabled(&self) -> bool {
+        self.mfa_enabled == 1
+    }
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 手动分配的角色，即数据库列的默认值
+pub const SOURCE_MANUAL: &str = "manual";
+/// 由 LDAP 组映射同步的角色，每次登录时刷新
+pub const SOURCE_LDAP: &str = "ldap";
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "sys_user_role")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub role_code: String,
+    /// 角色来源: manual | ldap
+    pub source: String,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
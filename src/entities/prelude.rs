@@ -2,3 +2,10 @@
 
 pub use super::permission::sys_user::Entity as SysUser;
 pub use super::permission::rs_employee01::Entity as RsEmployee1;
+pub use super::permission::sys_api_key::Entity as SysApiKey;
+pub use super::permission::sys_role::Entity as SysRole;
+pub use super::permission::sys_user_role::Entity as SysUserRole;
+pub use super::permission::sys_user_identity::Entity as SysUserIdentity;
+pub use super::system::sys_outbox_event::Entity as SysOutboxEvent;
+pub use super::system::sys_audit_log::Entity as SysAuditLog;
+pub use super::system::sys_file::Entity as SysFile;
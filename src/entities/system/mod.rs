@@ -0,0 +1,3 @@
+pub mod sys_audit_log;
+pub mod sys_outbox_event;
+pub mod sys_file;
@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const OUTCOME_SUCCESS: &str = "success";
+pub const OUTCOME_FAILURE: &str = "failure";
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "sys_audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+
+    /// 操作人，来自 JWT 中的 uid
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub client_ip: Option<String>,
+
+    /// 被操作对象类型，如 sys_user、redis_key
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    /// 变更字段，格式为 {"字段": {"before": .., "after": ..}}，敏感字段已脱敏
+    pub changes: Option<Json>,
+
+    pub outcome: String,
+    pub status_code: i32,
+    pub latency_ms: i64,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
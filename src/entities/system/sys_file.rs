@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 已上传文件，同一用户按内容摘要去重；不同用户上传相同内容时各有一条记录，共用同一个对象
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "sys_file")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+
+    /// 文件内容的 SHA-256 摘要（小写十六进制）
+    pub content_hash: String,
+    pub storage_key: String,
+    /// 首次上传时的文件名
+    pub file_name: Option<String>,
+    /// 按文件内容识别的类型
+    pub content_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// 已生成的缩略图边长，逗号分隔
+    pub thumbnail_sizes: Option<String>,
+    /// 上传用户，只有上传用户可以查看文件信息
+    pub created_by: Option<String>,
+    pub created_at: DateTime,
+}
+
+impl Model {
+    pub fn thumbnails(&self) -> Vec<u32> {
+        self.thumbnail_sizes
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|size| size.trim().parse().ok())
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 待发布事件状态
+pub const STATUS_PENDING: i16 = 0;
+/// 已发布
+pub const STATUS_PUBLISHED: i16 = 1;
+/// 超过最大重试次数，需人工处理
+pub const STATUS_DEAD: i16 = 2;
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "sys_outbox_event")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+
+    /// 聚合类型，如 sys_user
+    pub aggregate_type: String,
+    /// 聚合标识，同一聚合的事件按 id 顺序发布
+    pub aggregate_id: String,
+    pub event_type: String,
+    /// 发布目标主题
+    pub topic: String,
+    pub payload: Json,
+
+    pub status: i16,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime,
+    pub next_attempt_at: DateTime,
+    pub published_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
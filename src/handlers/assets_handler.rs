@@ -0,0 +1,169 @@
+//! 静态资源与管理后台页面
+
+use salvo::http::header::{
+    ACCEPT_ENCODING, ACCEPT_RANGES, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_RANGE, CONTENT_TYPE, ETAG,
+    IF_NONE_MATCH, RANGE, VARY,
+};
+use salvo::http::{StatusCode, StatusError};
+use salvo::prelude::*;
+
+use crate::assets;
+use crate::config;
+
+const ADMIN_INDEX: &str = "admin/index.html";
+const FAVICON: &str = "favicon.ico";
+/// 带内容指纹的资源缓存一年
+const IMMUTABLE_CACHE: &str = "public, max-age=31536000, immutable";
+
+/// 返回 `assets/` 下的资源
+#[handler]
+pub async fn serve(req: &mut Request, res: &mut Response) {
+    let path = req.param::<String>("path").unwrap_or_default();
+    render_asset(&path, req, res).await;
+}
+
+/// 管理后台入口页
+#[handler]
+pub async fn admin_index(req: &mut Request, res: &mut Response) {
+    render_asset(ADMIN_INDEX, req, res).await;
+}
+
+#[handler]
+pub async fn favicon(req: &mut Request, res: &mut Response) {
+    render_asset(FAVICON, req, res).await;
+}
+
+async fn render_asset(path: &str, req: &Request, res: &mut Response) {
+    // 范围请求按原始内容计算偏移，不使用压缩版本
+    let range = req.header::<String>(RANGE);
+    let accept_encoding = match range {
+        Some(_) => None,
+        None => req.header::<String>(ACCEPT_ENCODING),
+    };
+    let Some(asset) = assets::load(path, accept_encoding.as_deref()).await else {
+        res.render(StatusError::not_found());
+        return;
+    };
+
+    let config = &config::get().assets;
+    let cache_control = if config.serve_from_disk || asset.content_type.starts_with("text/html") {
+        "no-cache".to_string()
+    } else if assets::is_fingerprinted(path) {
+        IMMUTABLE_CACHE.to_string()
+    } else {
+        format!("public, max-age={}", config.max_age)
+    };
+    let _ = res.add_header(ETAG, &asset.etag, true);
+    let _ = res.add_header(CACHE_CONTROL, cache_control, true);
+    let _ = res.add_header(VARY, "Accept-Encoding", true);
+    let _ = res.add_header(ACCEPT_RANGES, "bytes", true);
+
+    if etag_matches(req.header::<String>(IF_NONE_MATCH).as_deref(), &asset.etag) {
+        res.status_code(StatusCode::NOT_MODIFIED);
+        return;
+    }
+    let _ = res.add_header(CONTENT_TYPE, &asset.content_type, true);
+    if let Some(encoding) = asset.content_encoding {
+        let _ = res.add_header(CONTENT_ENCODING, encoding, true);
+    }
+
+    let total = asset.data.len();
+    let body = match range.as_deref().map_or(ByteRange::Full, |range| parse_range(range, total)) {
+        ByteRange::Full => asset.data.into_owned(),
+        ByteRange::Partial(start, end) => {
+            res.status_code(StatusCode::PARTIAL_CONTENT);
+            let _ = res.add_header(CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, total), true);
+            asset.data[start..=end].to_vec()
+        }
+        ByteRange::Unsatisfiable => {
+            res.status_code(StatusCode::RANGE_NOT_SATISFIABLE);
+            let _ = res.add_header(CONTENT_RANGE, format!("bytes */{}", total), true);
+            return;
+        }
+    };
+    if let Err(e) = res.write_body(body) {
+        tracing::error!("write asset {} failed: {}", path, e);
+    }
+}
+
+/// `If-None-Match` 可能包含多个 ETag 或弱校验前缀 `W/`
+fn etag_matches(if_none_match: Option<&str>, etag: &str) -> bool {
+    if_none_match.is_some_and(|value| {
+        value
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag || tag == "*")
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum ByteRange {
+    /// 忽略 Range，返回完整内容
+    Full,
+    /// 闭区间
+    Partial(usize, usize),
+    /// 范围超出内容长度，返回 416
+    Unsatisfiable,
+}
+
+/// 解析单个字节范围 `bytes=start-end`、`bytes=start-` 或 `bytes=-suffix`；
+/// 不支持的单位、多个范围及格式错误时忽略 Range 返回完整内容
+fn parse_range(range: &str, total: usize) -> ByteRange {
+    let Some(spec) = range.trim().strip_prefix("bytes=").filter(|spec| !spec.contains(',')) else {
+        return ByteRange::Full;
+    };
+    let Some((start, end)) = spec.split_once('-') else {
+        return ByteRange::Full;
+    };
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => match suffix.parse::<usize>() {
+            Ok(0) => return ByteRange::Unsatisfiable,
+            Ok(suffix) => (total.saturating_sub(suffix), total.saturating_sub(1)),
+            Err(_) => return ByteRange::Full,
+        },
+        (start, end) => {
+            let Ok(start) = start.parse::<usize>() else {
+                return ByteRange::Full;
+            };
+            let end = match end {
+                "" => usize::MAX,
+                end => match end.parse::<usize>() {
+                    Ok(end) if end >= start => end,
+                    _ => return ByteRange::Full,
+                },
+            };
+            (start, end.min(total.saturating_sub(1)))
+        }
+    };
+    if total == 0 || start >= total {
+        return ByteRange::Unsatisfiable;
+    }
+    ByteRange::Partial(start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_matches() {
+        assert!(etag_matches(Some("\"abc\""), "\"abc\""));
+        assert!(etag_matches(Some("W/\"xyz\", \"abc\""), "\"abc\""));
+        assert!(etag_matches(Some("*"), "\"abc\""));
+        assert!(!etag_matches(Some("\"xyz\""), "\"abc\""));
+        assert!(!etag_matches(None, "\"abc\""));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), ByteRange::Partial(0, 99));
+        assert_eq!(parse_range("bytes=900-", 1000), ByteRange::Partial(900, 999));
+        assert_eq!(parse_range("bytes=-100", 1000), ByteRange::Partial(900, 999));
+        assert_eq!(parse_range("bytes=500-2000", 1000), ByteRange::Partial(500, 999));
+        assert_eq!(parse_range("bytes=1000-", 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-0", 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,5-9", 1000), ByteRange::Full);
+        assert_eq!(parse_range("items=0-1", 1000), ByteRange::Full);
+        assert_eq!(parse_range("bytes=9-1", 1000), ByteRange::Full);
+    }
+}
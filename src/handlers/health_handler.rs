@@ -0,0 +1,34 @@
+use salvo::http::StatusCode;
+use salvo::oapi::endpoint;
+use salvo::prelude::Json;
+use salvo::Response;
+
+use crate::models::system::health_dto::{HealthReport, STATUS_DOWN, STATUS_UP};
+use crate::services::health_service::HealthService;
+
+#[endpoint(tags("健康检查"), summary = "存活检查", description = "进程可以处理请求即返回 200，不检查外部依赖")]
+pub async fn live() -> Json<HealthReport> {
+    Json(HealthService::status_report(STATUS_UP))
+}
+
+#[endpoint(
+    tags("健康检查"),
+    summary = "就绪检查",
+    description = "检查 Postgres、Redis 与消息代理，关键依赖失败或服务停止中返回 503"
+)]
+pub async fn ready(res: &mut Response) -> Json<HealthReport> {
+    let (report, is_ready) = HealthService::readiness().await;
+    if !is_ready {
+        res.status_code(StatusCode::SERVICE_UNAVAILABLE);
+    }
+    Json(report)
+}
+
+#[endpoint(tags("健康检查"), summary = "启动检查", description = "初始化完成前返回 503")]
+pub async fn startup(res: &mut Response) -> Json<HealthReport> {
+    if HealthService::is_started() {
+        return Json(HealthService::status_report(STATUS_UP));
+    }
+    res.status_code(StatusCode::SERVICE_UNAVAILABLE);
+    Json(HealthService::status_report(STATUS_DOWN))
+}
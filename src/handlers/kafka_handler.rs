@@ -0,0 +1,103 @@
+use salvo::oapi::ToSchema;
+use salvo::oapi::extract::JsonBody;
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::common::api_response::{JsonResult, api_success};
+use crate::services::kafka_service::KafkaService;
+use crate::utils::param_validation_util::validate_param;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct SendRequest {
+    #[validate(length(min = 1, message = "topic 不能为空"))]
+    pub topic: String,
+    pub key: Option<String>,
+    pub payload: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct SubscribeRequest {
+    #[validate(length(min = 1, message = "topics 不能为空"))]
+    pub topics: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConsumeResponse {
+    pub topic: String,
+    pub key: Option<String>,
+    pub payload: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct OperationResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// 发送消息到指定主题。
+#[endpoint(tags("消息队列"), summary = "发送消息", description = "发送消息到指定主题")]
+pub async fn send(data: JsonBody<SendRequest>) -> JsonResult<OperationResponse> {
+    let data = data.into_inner();
+    validate_param(&data).await?;
+
+    match KafkaService::send_message(&data.topic, data.key.as_deref(), &data.payload).await {
+        Ok(_) => api_success(
+            OperationResponse {
+                success: true,
+                message: "发送成功".to_string(),
+            },
+            "发送成功",
+        ),
+        Err(e) => {
+            tracing::error!(topic = %data.topic, "发送消息失败: {}", e);
+            Err(salvo::http::StatusError::internal_server_error()
+                .brief("发送失败")
+                .into())
+        }
+    }
+}
+
+/// 订阅主题，之后可通过消费接口拉取消息。
+#[endpoint(tags("消息队列"), summary = "订阅主题", description = "订阅一个或多个主题")]
+pub async fn subscribe(data: JsonBody<SubscribeRequest>) -> JsonResult<OperationResponse> {
+    let data = data.into_inner();
+    validate_param(&data).await?;
+
+    match KafkaService::subscribe_topics(&data.topics).await {
+        Ok(_) => api_success(
+            OperationResponse {
+                success: true,
+                message: format!("已订阅{}个主题", data.topics.len()),
+            },
+            "订阅成功",
+        ),
+        Err(e) => {
+            tracing::error!(topics = ?data.topics, "订阅主题失败: {}", e);
+            Err(salvo::http::StatusError::internal_server_error()
+                .brief("订阅失败")
+                .into())
+        }
+    }
+}
+
+/// 从已订阅的主题中拉取一条消息。
+#[endpoint(tags("消息队列"), summary = "消费消息", description = "从已订阅的主题中拉取一条消息")]
+pub async fn consume() -> JsonResult<Option<ConsumeResponse>> {
+    match KafkaService::consume_message().await {
+        Ok(message) => api_success(
+            message.map(|m| ConsumeResponse {
+                payload: m.payload_str().map(str::to_string),
+                topic: m.topic,
+                key: m.key,
+            }),
+            "获取成功",
+        ),
+        Err(e) => {
+            tracing::error!("消费消息失败: {}", e);
+            Err(salvo::http::StatusError::bad_request()
+                .brief(e.to_string())
+                .into())
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use salvo::http::header::CONTENT_TYPE;
+use salvo::prelude::*;
+
+use crate::metrics;
+
+/// Prometheus 文本格式指标
+#[handler]
+pub async fn export(res: &mut Response) {
+    match metrics::gather() {
+        Ok(body) => {
+            let _ = res.add_header(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8", true);
+            let _ = res.write_body(body);
+        }
+        Err(e) => {
+            tracing::error!("gather metrics error: {}", e);
+            res.render(StatusError::internal_server_error().brief("指标导出失败"));
+        }
+    }
+}
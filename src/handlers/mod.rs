@@ -1,3 +1,7 @@
+pub mod assets_handler;
+pub mod kafka_handler;
+pub mod metrics_handler;
 pub mod permission;
 pub mod redis_handler;
+pub mod system;
 pub mod health_handler;
@@ -0,0 +1,76 @@
+use salvo::Depot;
+use salvo::Writer;
+use salvo::oapi::endpoint;
+use salvo::oapi::extract::{JsonBody, PathParam};
+
+use crate::common::api_response::{JsonResult, json_ok};
+use crate::hoops::{audit, auth};
+use crate::models::permission::api_key_dto::{ApiKeyRes, ApiKeySecretRes, CreateApiKeyReq};
+use crate::services::permission::api_key_service::ApiKeyService;
+use crate::utils::param_validation_util;
+use crate::db;
+
+#[endpoint(
+    tags("API Key管理"),
+    summary = "创建API Key",
+    description = "创建服务间调用使用的API Key，完整密钥只在创建时返回一次"
+)]
+pub async fn create(data: JsonBody<CreateApiKeyReq>, depot: &mut Depot) -> JsonResult<ApiKeySecretRes> {
+    let data = data.into_inner();
+    param_validation_util::validate_param(&data).await?;
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    let (created, api_key) = ApiKeyService::create_api_key(&principal, data, db).await?;
+    audit::record_change(depot, "sys_api_key", created.id.to_string(), None::<&()>, Some(&created));
+
+    json_ok(ApiKeySecretRes {
+        api_key,
+        info: created.into(),
+    })
+}
+
+#[endpoint(tags("API Key管理"), summary = "查询API Key", description = "查询当前用户的API Key")]
+pub async fn list(depot: &mut Depot) -> JsonResult<Vec<ApiKeyRes>> {
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    let keys = ApiKeyService::list_api_keys(&principal.user_id, db).await?;
+    json_ok(keys.into_iter().map(ApiKeyRes::from).collect())
+}
+
+#[endpoint(
+    tags("API Key管理"),
+    summary = "轮换API Key",
+    description = "生成新的API Key并吊销旧密钥，新密钥只返回一次"
+)]
+pub async fn rotate(id: PathParam<i64>, depot: &mut Depot) -> JsonResult<ApiKeySecretRes> {
+    let id = id.into_inner();
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    let (created, api_key) = ApiKeyService::rotate_api_key(&principal.user_id, id, db).await?;
+    audit::record_change(depot, "sys_api_key", id.to_string(), None::<&()>, Some(&created));
+
+    json_ok(ApiKeySecretRes {
+        api_key,
+        info: created.into(),
+    })
+}
+
+#[endpoint(tags("API Key管理"), summary = "吊销API Key", description = "吊销后立即失效")]
+pub async fn revoke(id: PathParam<i64>, depot: &mut Depot) -> JsonResult<i64> {
+    let id = id.into_inner();
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    ApiKeyService::revoke_api_key(&principal.user_id, id, db).await?;
+    audit::record_change(
+        depot,
+        "sys_api_key",
+        id.to_string(),
+        Some(&serde_json::json!({"revoked": false})),
+        Some(&serde_json::json!({"revoked": true})),
+    );
+    json_ok(id)
+}
@@ -0,0 +1,14 @@
+use salvo::oapi::endpoint;
+
+use crate::common::api_response::{JsonResult, json_ok};
+use crate::models::permission::challenge_dto::ChallengeRes;
+use crate::services::permission::challenge_service::ChallengeService;
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "获取人机验证",
+    description = "按配置下发图形验证码（captcha）或工作量证明（pow）"
+)]
+pub async fn issue() -> JsonResult<ChallengeRes> {
+    json_ok(ChallengeService::issue().await?)
+}
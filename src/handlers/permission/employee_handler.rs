@@ -0,0 +1,18 @@
+use salvo::oapi::endpoint;
+use salvo::oapi::extract::JsonBody;
+use salvo::Writer;
+
+use crate::common::api_response::{JsonResult, json_ok};
+use crate::models::permission::employee_dto::{EmployeeLookupReq, EmployeeRes};
+use crate::services::permission::employee_service::EmployeeService;
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "查询员工",
+    description = "按工号或手机号查询员工档案，手机号脱敏返回"
+)]
+pub async fn lookup(data: JsonBody<EmployeeLookupReq>) -> JsonResult<EmployeeRes> {
+    let data = data.into_inner();
+
+    json_ok(EmployeeService::lookup(&data).await?)
+}
@@ -0,0 +1,94 @@
+use salvo::oapi::endpoint;
+use salvo::oapi::extract::{JsonBody, PathParam};
+use salvo::{Depot, Response, Writer};
+
+use crate::common::api_response::{JsonResult, json_ok};
+use crate::hoops::{audit, auth};
+use crate::metrics;
+use crate::models::permission::mfa_dto::{MfaCodeReq, MfaEnrollRes, MfaRecoveryCodesRes, MfaVerifyReq};
+use crate::models::permission::user_dto::LogInRes;
+use crate::services::permission::mfa_service::MfaService;
+use crate::services::permission::user_service::UserService;
+use crate::utils::param_validation_util;
+use crate::db;
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "两步验证",
+    description = "提交登录票据与验证码（或恢复码），通过后签发登录令牌"
+)]
+pub async fn verify(data: JsonBody<MfaVerifyReq>, res: &mut Response) -> JsonResult<LogInRes> {
+    let data = data.into_inner();
+    param_validation_util::validate_param(&data).await?;
+    let db = db::postgres::pool();
+
+    let user = MfaService::verify_ticket(&data.ticket, data.code.trim(), db)
+        .await
+        .inspect_err(|_| metrics::record_login("mfa", false))?;
+    metrics::record_login("mfa", true);
+    let token = UserService::issue_token(&user.user_id).await?;
+    let _ = res.add_header("Authorization", &token, true);
+
+    json_ok(LogInRes {
+        authorization: vec![token],
+        mfa_ticket: None,
+    })
+}
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "绑定两步验证",
+    description = "生成 TOTP 密钥，返回的 otpauth 地址可直接生成二维码"
+)]
+pub async fn enroll(depot: &mut Depot) -> JsonResult<MfaEnrollRes> {
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    let user = UserService::get_user_by_id(&principal.user_id, db).await?;
+    json_ok(MfaService::enroll(&user).await?)
+}
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "开启两步验证",
+    description = "提交验证器中的验证码确认绑定，恢复码只返回一次"
+)]
+pub async fn activate(data: JsonBody<MfaCodeReq>, depot: &mut Depot) -> JsonResult<MfaRecoveryCodesRes> {
+    let data = data.into_inner();
+    param_validation_util::validate_param(&data).await?;
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    let user = UserService::get_user_by_id(&principal.user_id, db).await?;
+    let recovery_codes = MfaService::activate(&user, &data.code, db).await?;
+    audit::record_change(
+        depot,
+        "sys_user",
+        user.user_id,
+        Some(&serde_json::json!({"mfaEnabled": false})),
+        Some(&serde_json::json!({"mfaEnabled": true})),
+    );
+
+    json_ok(MfaRecoveryCodesRes { recovery_codes })
+}
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "重置两步验证",
+    description = "管理员关闭用户的两步验证，用户下次登录后可重新绑定"
+)]
+pub async fn reset(user_id: PathParam<String>, depot: &mut Depot) -> JsonResult<String> {
+    let user_id = user_id.into_inner();
+    let db = db::postgres::pool();
+
+    let user = MfaService::reset(&user_id, db).await?;
+    audit::record_change(
+        depot,
+        "sys_user",
+        &user.user_id,
+        Some(&serde_json::json!({"mfaEnabled": user.is_mfa_enabled()})),
+        Some(&serde_json::json!({"mfaEnabled": false})),
+    );
+
+    json_ok(user_id)
+}
@@ -1 +1,6 @@
-pub mod user_handler;
\ No newline at end of file
+pub mod api_key_handler;
+pub mod user_handler;
+pub mod oidc_handler;
+pub mod mfa_handler;
+pub mod challenge_handler;
+pub mod employee_handler;
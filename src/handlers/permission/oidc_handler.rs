@@ -0,0 +1,70 @@
+use salvo::http::StatusError;
+use salvo::prelude::Redirect;
+use salvo::Writer;
+use salvo::{
+    Response,
+    oapi::{endpoint, extract::QueryParam},
+};
+
+use crate::common::api_response::{AppResult, JsonResult, json_ok};
+use crate::config::{self, OidcConfig};
+use crate::metrics;
+use crate::{
+    db,
+    models::permission::user_dto::LogInRes,
+    services::permission::{mfa_service::MfaService, oidc_service::OidcService, user_service::UserService},
+};
+
+fn oidc_config() -> AppResult<&'static OidcConfig> {
+    config::get()
+        .oidc
+        .as_ref()
+        .ok_or_else(|| StatusError::not_found().brief("未启用单点登录").into())
+}
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "单点登录",
+    description = "跳转到身份提供方进行授权码 + PKCE 登录"
+)]
+pub async fn authorize(res: &mut Response) -> AppResult<()> {
+    let config = oidc_config()?;
+    let url = OidcService::authorize_url(config).await?;
+    res.render(Redirect::found(url));
+    Ok(())
+}
+
+#[endpoint(tags("用户与权限相关"), summary = "单点登录回调", description = "身份提供方回调，签发登录令牌")]
+pub async fn callback(
+    code: QueryParam<String, true>,
+    state: QueryParam<String, true>,
+    res: &mut Response,
+) -> JsonResult<LogInRes> {
+    let config = oidc_config()?;
+    let db = db::postgres::pool();
+
+    let user = OidcService::handle_callback(config, &code.into_inner(), &state.into_inner(), db)
+        .await
+        .inspect_err(|_| metrics::record_login("oidc", false))?;
+    UserService::verify_user_status(&user)
+        .await
+        .inspect_err(|_| metrics::record_login("oidc", false))?;
+
+    // 开启两步验证的用户与密码登录一样先返回票据
+    if user.is_mfa_enabled() {
+        return json_ok(LogInRes {
+            authorization: vec![],
+            mfa_ticket: Some(MfaService::create_ticket(&user.user_id).await?),
+        });
+    }
+
+    metrics::record_login("oidc", true);
+    let token = UserService::issue_token(&user.user_id).await?;
+    tracing::info!(user_id = %user.user_id, "oidc login success");
+    let _ = res.add_header("Authorization", &token, true);
+
+    json_ok(LogInRes {
+        authorization: vec![token],
+        mfa_ticket: None,
+    })
+}
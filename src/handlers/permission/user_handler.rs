@@ -1,20 +1,21 @@
-use std::sync::Arc;
+use std::collections::HashMap;
 
 use salvo::Writer;
+use salvo::http::StatusError;
 use salvo::{
-    Depot, Response,
-    oapi::{ToSchema, endpoint, extract::JsonBody},
+    Depot, Request, Response,
+    oapi::{ToSchema, endpoint, extract::{JsonBody, PathParam}},
 };
 
-use crate::app::AppState;
-use crate::common::api_response::{JsonResult, json_ok};
+use crate::common::api_response::{JsonResult, PageData, json_ok};
+use crate::mail::{self, MailMessage, MailSender};
+use crate::metrics;
 use crate::utils::param_validation_util;
 use crate::{
     db,
-    hoops::jwt,
-    models::permission::user_dto::{CreateReq, LogInRes, LoginReq},
-    services::permission::user_service,
-    services::redis_service::RedisService,
+    hoops::{audit, auth, rate_limit},
+    models::permission::user_dto::{CreateReq, LogInRes, LoginReq, UserPageReq, UserRes},
+    services::permission::{challenge_service::ChallengeService, mfa_service::MfaService, user_service},
 };
 use serde::Serialize;
 
@@ -28,55 +29,134 @@ pub struct CreateResponse {
 #[endpoint(
     tags("用户与权限相关"),
     summary = "分页查询用户信息",
-    description = "List page description"
+    description = "按用户id前缀、用户名称、锁定与有效状态分页查询用户"
 )]
-pub async fn list_page() -> JsonResult<()> {
-    todo!()
-}
-
-#[endpoint(tags("用户与权限相关"), summary = "用户登录", description = "用户登录")]
-pub async fn login(data: JsonBody<LoginReq>, res: &mut Response) -> JsonResult<LogInRes> {
+pub async fn list_page(data: JsonBody<UserPageReq>) -> JsonResult<PageData<UserRes>> {
     let data = data.into_inner();
-    // 执行数据验证，如果验证失败则返回错误
     param_validation_util::validate_param(&data).await?;
 
-    let db = db::postgres::pool();
+    let (total, records) = user_service::UserService::query_page(&data).await?;
+
+    json_ok(PageData {
+        total,
+        cur_page: data.cur_page,
+        page_size: data.page_size,
+        records: records.into_iter().map(UserRes::from).collect(),
+    })
+}
 
-    // 根据user_id查询用户信息
-    let user = user_service::UserService::get_user_by_id(&data.user_id, db).await?;
+#[endpoint(tags("用户与权限相关"), summary = "锁定用户", description = "锁定后用户无法登录")]
+pub async fn lock(user_id: PathParam<String>, depot: &mut Depot) -> JsonResult<String> {
+    set_locked(user_id.into_inner(), true, depot).await
+}
 
-    // 校验用户状态
-    user_service::UserService::verify_user_status(&user).await?;
+#[endpoint(tags("用户与权限相关"), summary = "解锁用户", description = "解除用户锁定")]
+pub async fn unlock(user_id: PathParam<String>, depot: &mut Depot) -> JsonResult<String> {
+    set_locked(user_id.into_inner(), false, depot).await
+}
 
-    // 校验用户密码
-    user_service::UserService::verify_user_credentials(&user.password, &data.password).await?;
+async fn set_locked(user_id: String, locked: bool, depot: &mut Depot) -> JsonResult<String> {
+    let db = db::postgres::pool();
 
-    let (token, exp) = jwt::get_token(&data.user_id)?;
-    let token = format!("Bearer {}", token);
+    let user = user_service::UserService::set_locked(&user_id, locked, db).await?;
+    audit::record_change(
+        depot,
+        "sys_user",
+        &user.user_id,
+        Some(&serde_json::json!({"locked": user.locked})),
+        Some(&serde_json::json!({"locked": i32::from(locked)})),
+    );
 
-    let _ = RedisService::set_login(&token, &data.user_id, Some(exp as usize)).await;
+    json_ok(user_id)
+}
+
+#[endpoint(tags("用户与权限相关"), summary = "用户登录", description = "用户登录")]
+pub async fn login(data: JsonBody<LoginReq>, req: &mut Request, res: &mut Response) -> JsonResult<LogInRes> {
+    let data = data.into_inner();
+    // 执行数据验证，如果验证失败则返回错误
+    param_validation_util::validate_param(&data).await?;
+
+    let db = db::postgres::pool();
+    let client_ip = rate_limit::client_ip(req);
+
+    // 连续登录失败达到阈值后要求先完成人机验证
+    if ChallengeService::login_required(&data.user_id, &client_ip).await {
+        ChallengeService::verify(data.challenge_id.as_deref(), data.challenge_answer.as_deref()).await?;
+    }
+
+    // 查询用户信息，校验用户状态与密码
+    let user = match user_service::UserService::authenticate(&data.user_id, &data.password, db).await {
+        Ok(user) => user,
+        Err(e) => {
+            ChallengeService::record_login_failure(&data.user_id, &client_ip).await;
+            metrics::record_login("password", false);
+            return Err(e);
+        }
+    };
+    ChallengeService::clear_login_failures(&data.user_id).await;
+
+    // 开启两步验证的用户先返回票据，验证码通过后再签发令牌
+    if user.is_mfa_enabled() {
+        return json_ok(LogInRes {
+            authorization: vec![],
+            mfa_ticket: Some(MfaService::create_ticket(&user.user_id).await?),
+        });
+    }
+
+    // 需要两步验证时由验证码校验记录登录结果
+    metrics::record_login("password", true);
+    let token = user_service::UserService::issue_token(&data.user_id).await?;
 
     let _ = res.add_header("Authorization", &token, true);
 
     json_ok(LogInRes {
         authorization: vec![token],
+        mfa_ticket: None,
     })
 }
 
 #[endpoint(tags("用户与权限相关"), summary = "用户注册", description = "用户注册")]
-pub async fn create(data: JsonBody<CreateReq>) -> JsonResult<CreateResponse> {
+pub async fn create(data: JsonBody<CreateReq>, req: &mut Request, depot: &mut Depot) -> JsonResult<CreateResponse> {
     let data = data.into_inner();
     param_validation_util::validate_param(&data).await?;
+    if ChallengeService::registration_required() {
+        ChallengeService::verify(data.challenge_id.as_deref(), data.challenge_answer.as_deref()).await?;
+    }
     let db = db::postgres::pool();
 
     tracing::info!(user_id = %data.user_id, "user register start");
     
     let created = user_service::UserService::create_user(data, db).await?;
     tracing::info!(user_id = %created.user_id, "user register success");
+    audit::record_change(depot, "sys_user", &created.user_id, None::<&()>, Some(&created));
+
+    user_service::UserService::send_welcome_mail(&created, &rate_limit::client_ip(req)).await;
 
     json_ok(CreateResponse {
         user_id: created.user_id,
         user_name: created.user_name,
         phone: created.phone,
     })
-}
\ No newline at end of file
+}
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "测试邮箱",
+    description = "使用当前用户保存的邮箱账号给自己发送一封测试邮件，邮件异步发送，以是否收到为准"
+)]
+pub async fn send_test_mail(depot: &mut Depot) -> JsonResult<String> {
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    let user = user_service::UserService::get_user_by_id(&principal.user_id, db).await?;
+    let Some(email) = user.email.clone().filter(|e| !e.is_empty()) else {
+        return Err(StatusError::bad_request().brief("未设置邮箱").into());
+    };
+    if user.email_password.as_deref().is_none_or(str::is_empty) {
+        return Err(StatusError::bad_request().brief("未设置邮箱密码").into());
+    }
+    let vars = HashMap::from([("user_name", user.user_name.clone())]);
+    MailMessage::from_template("mailbox_test", &vars, vec![email.clone()], MailSender::User(user.user_id))
+        .and_then(mail::enqueue)?;
+
+    json_ok(email)
+}
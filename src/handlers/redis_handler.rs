@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
 use salvo::oapi::ToSchema;
+use salvo::oapi::extract::QueryParam;
 use salvo::prelude::*;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::app::AppState;
 use crate::common::api_response::{api_success, JsonResult};
+use crate::hoops::audit;
 use crate::services::redis_service::RedisService;
 use crate::utils::param_validation_util::validate_param;
 use crate::AppError;
@@ -32,21 +34,32 @@ pub struct OperationResponse {
     pub message: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ScanResponse {
+    /// 下一次遍历的游标，为0表示遍历结束
+    pub cursor: u64,
+    pub keys: Vec<String>,
+}
+
 /// 设置Redis键值对（带校验与状态注入）。
 #[endpoint(tags("Redis操作"), summary = "设置键值对", description = "设置Redis键值对，可选过期时间")]
 pub async fn set(req: &mut Request, depot: &mut Depot) -> JsonResult<OperationResponse> {
     let data: SetRequest = req.parse_json().await?;
     validate_param(&data).await?;
     let state = get_state(depot)?;
+    let before = RedisService::get_with_pool(&state.redis, &data.key).await.ok().flatten();
 
     match RedisService::set_with_pool(&state.redis, &data.key, &data.value, data.ttl).await {
-        Ok(_) => api_success(
-            OperationResponse {
-                success: true,
-                message: "设置成功".to_string(),
-            },
-            "设置成功",
-        ),
+        Ok(_) => {
+            audit::record_change(depot, "redis_key", &data.key, before.as_ref(), Some(&data.value));
+            api_success(
+                OperationResponse {
+                    success: true,
+                    message: "设置成功".to_string(),
+                },
+                "设置成功",
+            )
+        }
         Err(e) => {
             tracing::error!("设置Redis键值对失败: {}", e);
             Err(salvo::http::StatusError::internal_server_error()
@@ -84,15 +97,19 @@ pub async fn get(req: &mut Request, depot: &mut Depot) -> JsonResult<GetResponse
 pub async fn delete(req: &mut Request, depot: &mut Depot) -> JsonResult<OperationResponse> {
     let key = req.param::<String>("key").unwrap_or_default();
     let state = get_state(depot)?;
+    let before = RedisService::get_with_pool(&state.redis, &key).await.ok().flatten();
 
     match RedisService::del_with_pool(&state.redis, &key).await {
-        Ok(count) => api_success(
-            OperationResponse {
-                success: true,
-                message: format!("删除成功，影响{}个键", count),
-            },
-            "删除成功",
-        ),
+        Ok(count) => {
+            audit::record_change(depot, "redis_key", &key, before.as_ref(), None::<&String>);
+            api_success(
+                OperationResponse {
+                    success: true,
+                    message: format!("删除成功，影响{}个键", count),
+                },
+                "删除成功",
+            )
+        }
         Err(e) => {
             tracing::error!("删除Redis键失败: {}", e);
             Err(salvo::http::StatusError::internal_server_error()
@@ -102,6 +119,29 @@ pub async fn delete(req: &mut Request, depot: &mut Depot) -> JsonResult<Operatio
     }
 }
 
+/// 按模式遍历Redis键（依赖 AppState 中的连接池），使用 SCAN 避免阻塞。
+#[endpoint(tags("Redis操作"), summary = "遍历键", description = "按模式分批遍历Redis键，pattern 默认为 *，每批最多1000个")]
+pub async fn scan(
+    pattern: QueryParam<String, false>,
+    cursor: QueryParam<u64, false>,
+    count: QueryParam<usize, false>,
+    depot: &mut Depot,
+) -> JsonResult<ScanResponse> {
+    let pattern = pattern.into_inner().filter(|p| !p.is_empty()).unwrap_or_else(|| "*".to_string());
+    let count = count.into_inner().unwrap_or(100).clamp(1, 1000);
+    let state = get_state(depot)?;
+
+    match RedisService::scan_with_pool(&state.redis, &pattern, cursor.into_inner().unwrap_or_default(), count).await {
+        Ok((cursor, keys)) => api_success(ScanResponse { cursor, keys }, "获取成功"),
+        Err(e) => {
+            tracing::error!("遍历Redis键失败: {}", e);
+            Err(salvo::http::StatusError::internal_server_error()
+                .brief("遍历失败")
+                .into())
+        }
+    }
+}
+
 fn get_state(depot: &mut Depot) -> Result<Arc<AppState>, AppError> {
     depot
         .get::<Arc<AppState>>("app_state")
@@ -0,0 +1,27 @@
+use salvo::oapi::endpoint;
+use salvo::oapi::extract::JsonBody;
+use salvo::Writer;
+
+use crate::common::api_response::{JsonResult, PageData, json_ok};
+use crate::models::system::audit_dto::{AuditLogRes, AuditPageReq};
+use crate::services::audit_service::AuditService;
+use crate::utils::param_validation_util;
+
+#[endpoint(
+    tags("系统管理"),
+    summary = "分页查询审计日志",
+    description = "按操作人、链路ID、路由、操作对象、结果与时间范围查询审计日志"
+)]
+pub async fn page(data: JsonBody<AuditPageReq>) -> JsonResult<PageData<AuditLogRes>> {
+    let data = data.into_inner();
+    param_validation_util::validate_param(&data).await?;
+
+    let (total, records) = AuditService::query_page(&data).await?;
+
+    json_ok(PageData {
+        total,
+        cur_page: data.cur_page,
+        page_size: data.page_size,
+        records: records.into_iter().map(AuditLogRes::from).collect(),
+    })
+}
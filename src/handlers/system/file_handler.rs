@@ -0,0 +1,110 @@
+use salvo::http::StatusError;
+use salvo::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use salvo::oapi::endpoint;
+use salvo::oapi::extract::{PathParam, QueryParam};
+use salvo::prelude::Redirect;
+use salvo::{Depot, Request, Response, Writer};
+
+use crate::common::api_response::{AppResult, JsonResult, json_ok};
+use crate::config;
+use crate::db;
+use crate::hoops::{audit, auth};
+use crate::models::system::file_dto::FileRes;
+use crate::repository::permission::user_repository;
+use crate::services::file_service::{self, FileService, UploadFile};
+use crate::services::permission::user_service::UserService;
+
+/// multipart 表单除文件外的额外开销
+const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+/// 读取 multipart 表单中的 `file` 字段
+async fn read_upload(req: &mut Request) -> AppResult<UploadFile> {
+    req.set_secure_max_size(config::get().storage.max_size + MULTIPART_OVERHEAD);
+    let file = req
+        .file("file")
+        .await
+        .ok_or_else(|| StatusError::bad_request().brief("请选择要上传的文件"))?;
+    let content = tokio::fs::read(file.path()).await.map_err(anyhow::Error::from)?;
+    Ok(UploadFile {
+        file_name: file.name().map(String::from),
+        content,
+    })
+}
+
+#[endpoint(
+    tags("系统管理"),
+    summary = "上传文件",
+    description = "multipart 表单上传，文件字段为 file；按文件内容识别类型，相同内容只保存一份"
+)]
+pub async fn upload(req: &mut Request, depot: &mut Depot) -> JsonResult<FileRes> {
+    let principal = auth::require_principal(depot)?;
+    let upload_file = read_upload(req).await?;
+    let db = db::postgres::pool();
+
+    let file = FileService::upload(upload_file, &principal.user_id, db).await?;
+    json_ok(FileService::to_res(file).await?)
+}
+
+#[endpoint(
+    tags("系统管理"),
+    summary = "查询文件",
+    description = "返回文件信息与带有效期的下载地址，仅上传用户与管理员可以查看"
+)]
+pub async fn get(id: PathParam<i64>, depot: &mut Depot) -> JsonResult<FileRes> {
+    let principal = auth::require_principal(depot)?;
+    let db = db::postgres::pool();
+
+    let file = FileService::get_file(id.into_inner(), &principal, db).await?;
+    json_ok(FileService::to_res(file).await?)
+}
+
+#[endpoint(tags("系统管理"), summary = "下载文件", description = "通过签名地址下载本地存储的文件")]
+pub async fn download(
+    key: PathParam<String>,
+    expires: QueryParam<u64, true>,
+    signature: QueryParam<String, true>,
+    res: &mut Response,
+) -> AppResult<()> {
+    let expires = expires.into_inner();
+    let (content, content_type) = FileService::download(&key.into_inner(), expires, &signature.into_inner()).await?;
+    // 缓存时间不超过签名的剩余有效期
+    let remaining = expires.saturating_sub(chrono::Utc::now().timestamp().max(0) as u64);
+    let _ = res.add_header(CONTENT_TYPE, content_type, true);
+    let _ = res.add_header(CACHE_CONTROL, format!("private, max-age={}", remaining), true);
+    res.write_body(content)?;
+    Ok(())
+}
+
+#[endpoint(
+    tags("用户与权限相关"),
+    summary = "上传头像",
+    description = "multipart 表单上传，文件字段为 file，仅支持图片；头像地址为 /rust/user/{userId}/avatar，无需登录"
+)]
+pub async fn upload_avatar(req: &mut Request, depot: &mut Depot) -> JsonResult<FileRes> {
+    let principal = auth::require_principal(depot)?;
+    let upload_file = read_upload(req).await?;
+    let db = db::postgres::pool();
+
+    let user = UserService::get_user_by_id(&principal.user_id, db).await?;
+    let file = FileService::upload_image(upload_file, &user.user_id, db).await?;
+    let image_url = file_service::avatar_url(&user.user_id, file.id);
+    user_repository::update_user_image_url(user.auto_id, Some(image_url.clone()), db).await?;
+    audit::record_change(
+        depot,
+        "sys_user",
+        &user.user_id,
+        Some(&serde_json::json!({"imageUrl": user.image_url})),
+        Some(&serde_json::json!({"imageUrl": image_url})),
+    );
+
+    json_ok(FileService::to_res(file).await?)
+}
+
+#[endpoint(tags("用户与权限相关"), summary = "用户头像", description = "跳转到用户头像的签名下载地址，可直接用于 img 标签")]
+pub async fn avatar(user_id: PathParam<String>, res: &mut Response) -> AppResult<()> {
+    let url = FileService::avatar_signed_url(&user_id.into_inner()).await?;
+    // 签名地址会过期，跳转本身不缓存
+    let _ = res.add_header(CACHE_CONTROL, "no-cache", true);
+    res.render(Redirect::found(url));
+    Ok(())
+}
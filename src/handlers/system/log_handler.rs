@@ -0,0 +1,29 @@
+use salvo::oapi::endpoint;
+use salvo::oapi::extract::JsonBody;
+use salvo::Writer;
+
+use crate::common::api_response::{JsonResult, json_ok};
+use crate::models::system::log_dto::{LogLevelReq, LogLevelRes};
+use crate::services::log_service::LogService;
+use crate::utils::param_validation_util;
+
+#[endpoint(tags("系统管理"), summary = "查询日志级别", description = "返回当前生效与配置文件中的日志过滤规则")]
+pub async fn get_level() -> JsonResult<LogLevelRes> {
+    json_ok(LogService::current())
+}
+
+#[endpoint(
+    tags("系统管理"),
+    summary = "调整日志级别",
+    description = "临时调整日志过滤规则，可设置到期自动恢复为配置规则"
+)]
+pub async fn set_level(data: JsonBody<LogLevelReq>) -> JsonResult<LogLevelRes> {
+    let data = data.into_inner();
+    param_validation_util::validate_param(&data).await?;
+    json_ok(LogService::set(data.directives.trim(), data.revert_after)?)
+}
+
+#[endpoint(tags("系统管理"), summary = "恢复日志级别", description = "撤销临时规则，恢复为配置文件中的日志过滤规则")]
+pub async fn reset_level() -> JsonResult<LogLevelRes> {
+    json_ok(LogService::reset()?)
+}
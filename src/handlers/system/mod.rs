@@ -0,0 +1,4 @@
+pub mod audit_handler;
+pub mod outbox_handler;
+pub mod file_handler;
+pub mod log_handler;
@@ -0,0 +1,41 @@
+use salvo::http::StatusError;
+use salvo::oapi::endpoint;
+use salvo::oapi::extract::{JsonBody, PathParam};
+use salvo::Writer;
+
+use crate::common::api_response::{JsonResult, PageData, json_ok};
+use crate::models::system::outbox_dto::{OutboxEventRes, OutboxPageReq};
+use crate::services::outbox_service::OutboxService;
+use crate::utils::param_validation_util;
+use crate::{config, db};
+
+#[endpoint(
+    tags("系统管理"),
+    summary = "查询卡住的发件箱事件",
+    description = "分页查询超过重试次数或长时间未发布的事件"
+)]
+pub async fn stuck_page(data: JsonBody<OutboxPageReq>) -> JsonResult<PageData<OutboxEventRes>> {
+    let data = data.into_inner();
+    param_validation_util::validate_param(&data).await?;
+
+    let (total, records) = OutboxService::stuck_events(&config::get().outbox, data.cur_page, data.page_size).await?;
+
+    json_ok(PageData {
+        total,
+        cur_page: data.cur_page,
+        page_size: data.page_size,
+        records: records.into_iter().map(OutboxEventRes::from).collect(),
+    })
+}
+
+#[endpoint(tags("系统管理"), summary = "重新发布发件箱事件", description = "将事件重置为待发布状态")]
+pub async fn retry(id: PathParam<i64>) -> JsonResult<i64> {
+    let id = id.into_inner();
+    let db = db::postgres::pool();
+
+    if !OutboxService::retry_event(id, db).await? {
+        return Err(StatusError::not_found().brief("事件不存在或已发布").into());
+    }
+    tracing::info!(event_id = id, "outbox event reset for retry");
+    json_ok(id)
+}
@@ -0,0 +1,222 @@
+//! 访问日志中间件
+//!
+//! 对配置中开启的路由按采样比例记录请求与响应的头和正文，用于排查对接问题。正文只记录能够脱敏的
+//! JSON 与表单（`application/x-www-form-urlencoded`），超过 `max_body` 的部分截断；其他类型及
+//! 无法解析的正文只记录长度。配置的字段与请求头脱敏后输出。日志目标为 `access_log`，
+//! 可通过日志过滤规则单独调整。
+
+use std::time::Instant;
+
+use salvo::http::header::CONTENT_TYPE;
+use salvo::http::{HeaderMap, ResBody};
+use salvo::prelude::*;
+use serde_json::{Map, Value};
+
+use crate::config::{self, AccessLogConfig};
+use crate::hoops::audit;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const REDACTED: &str = "******";
+const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+
+#[handler]
+pub async fn access_log_hoop(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    let config = config::current();
+    let config = &config.access_log;
+    if !config.enabled {
+        ctrl.call_next(req, depot, res).await;
+        return;
+    }
+    let sampled = config
+        .match_route(req.method().as_str(), req.uri().path())
+        .is_some_and(|route| rand::random::<f64>() < route.sample_rate);
+    if !sampled {
+        ctrl.call_next(req, depot, res).await;
+        return;
+    }
+
+    let request_headers = headers(req.headers(), config);
+    let request_body = request_body(req, config).await;
+    let started = Instant::now();
+    ctrl.call_next(req, depot, res).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let response_body = response_body(res, config);
+    tracing::info!(
+        target: "access_log",
+        request_id = %req.header::<String>(REQUEST_ID_HEADER).unwrap_or_default(),
+        user_id = %audit::actor(req, depot).unwrap_or_default(),
+        method = %req.method(),
+        path = %req.uri().path(),
+        status = res.status_code.unwrap_or(StatusCode::OK).as_u16(),
+        latency_ms,
+        request_headers = %request_headers,
+        request_body = %request_body,
+        response_headers = %headers(res.headers(), config),
+        response_body = %response_body,
+        "access"
+    );
+}
+
+/// 读取请求正文，读取后由 salvo 缓存，不影响后续处理器解析
+async fn request_body(req: &mut Request, config: &AccessLogConfig) -> String {
+    let content_type = req.header::<String>(CONTENT_TYPE).unwrap_or_default();
+    if !is_text(&content_type) {
+        return req
+            .header::<u64>("content-length")
+            .map(|len| format!("[{} bytes]", len))
+            .unwrap_or_default();
+    }
+    match req.payload().await {
+        Ok(body) => format_body(body, &content_type, config),
+        Err(e) => format!("[unreadable: {}]", e),
+    }
+}
+
+fn response_body(res: &Response, config: &AccessLogConfig) -> String {
+    let content_type = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    match &res.body {
+        ResBody::None => String::new(),
+        ResBody::Once(bytes) if is_text(content_type) => format_body(bytes, content_type, config),
+        ResBody::Chunks(chunks) if is_text(content_type) => {
+            let bytes: Vec<u8> = chunks.iter().flat_map(|chunk| chunk.iter().copied()).collect();
+            format_body(&bytes, content_type, config)
+        }
+        ResBody::Error(e) => e.brief.clone(),
+        ResBody::Once(bytes) => format!("[{} bytes]", bytes.len()),
+        _ => "[stream]".into(),
+    }
+}
+
+fn is_text(content_type: &str) -> bool {
+    let content_type = content_type.to_ascii_lowercase();
+    content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+        || content_type.starts_with(FORM_URLENCODED)
+}
+
+/// 脱敏后输出正文，无法脱敏时只记录长度
+fn format_body(body: &[u8], content_type: &str, config: &AccessLogConfig) -> String {
+    let content_type = content_type.to_ascii_lowercase();
+    if content_type.contains("json")
+        && let Ok(mut value) = serde_json::from_slice::<Value>(body)
+    {
+        redact_json(&mut value, config);
+        return truncate(value.to_string(), config.max_body);
+    }
+    if content_type.starts_with(FORM_URLENCODED)
+        && let Ok(form) = std::str::from_utf8(body)
+    {
+        return truncate(redact_form(form, config), config.max_body);
+    }
+    format!("[{} bytes]", body.len())
+}
+
+/// 按解码后的字段名脱敏，其余字段保持原样
+fn redact_form(form: &str, config: &AccessLogConfig) -> String {
+    form.split('&')
+        .map(|pair| {
+            let (raw_name, _) = pair.split_once('=').unwrap_or((pair, ""));
+            let name = raw_name.replace('+', " ");
+            let name = percent_encoding::percent_decode_str(&name).decode_utf8_lossy();
+            if config.is_redacted(&name) {
+                format!("{}={}", raw_name, REDACTED)
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn headers(headers: &HeaderMap, config: &AccessLogConfig) -> Value {
+    let mut map = Map::new();
+    for (name, value) in headers {
+        let value = if config.is_redacted(name.as_str()) {
+            REDACTED.to_string()
+        } else {
+            String::from_utf8_lossy(value.as_bytes()).into_owned()
+        };
+        map.insert(name.as_str().to_string(), Value::String(value));
+    }
+    Value::Object(map)
+}
+
+/// 递归替换需要脱敏的字段，嵌套对象与数组中的字段同样处理
+fn redact_json(value: &mut Value, config: &AccessLogConfig) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if config.is_redacted(key) {
+                    if !field.is_null() {
+                        *field = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    redact_json(field, config);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact_json(item, config)),
+        _ => {}
+    }
+}
+
+fn truncate(mut text: String, max: usize) -> String {
+    if text.len() <= max {
+        return text;
+    }
+    let total = text.len();
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(&format!("...[truncated, {} bytes]", total));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_format_body_redacts_nested_fields() {
+        let config = AccessLogConfig::default();
+        let body = json!({
+            "userId": "A001",
+            "password": "secret",
+            "data": {"token": "abc", "items": [{"EmailPassword": "x"}]},
+        });
+        let formatted = format_body(body.to_string().as_bytes(), "application/json", &config);
+        let value: Value = serde_json::from_str(&formatted).unwrap();
+        assert_eq!(value["userId"], "A001");
+        assert_eq!(value["password"], REDACTED);
+        assert_eq!(value["data"]["token"], REDACTED);
+        assert_eq!(value["data"]["items"][0]["EmailPassword"], REDACTED);
+    }
+
+    #[test]
+    fn test_format_body_redacts_form_and_hides_unparsable() {
+        let config = AccessLogConfig::default();
+        let form = "user_id=A001&pass%77ord=secret&api_key=k1";
+        assert_eq!(
+            format_body(form.as_bytes(), "application/x-www-form-urlencoded; charset=utf-8", &config),
+            "user_id=A001&pass%77ord=******&api_key=******"
+        );
+        assert_eq!(format_body(b"{\"password\": \"secret\"", "application/json", &config), "[21 bytes]");
+        assert_eq!(format_body(b"password=secret", "text/plain", &config), "[15 bytes]");
+    }
+
+    #[test]
+    fn test_truncate_on_char_boundary() {
+        assert_eq!(truncate("abc".into(), 5), "abc");
+        assert_eq!(truncate("张三李四".into(), 4), "张...[truncated, 12 bytes]");
+    }
+}
@@ -0,0 +1,223 @@
+//! 审计日志中间件
+//!
+//! 记录所有修改类请求（非 GET/HEAD/OPTIONS）的操作人、链路ID、路由、结果与耗时。
+//! 处理器通过 [`record_change`] 写入被操作对象及变更前后的数据，中间件计算字段级差异，
+//! 敏感字段脱敏后交由后台任务异步入库。
+
+use std::time::Instant;
+
+use salvo::http::Method;
+use salvo::jwt_auth::JwtAuthDepotExt;
+use salvo::prelude::*;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::entities::system::sys_audit_log;
+use crate::hoops::auth;
+use crate::hoops::jwt::{self, JwtClaims};
+use crate::services::audit_service::{AuditEntry, AuditService};
+
+const AUDIT_CONTEXT_KEY: &str = "audit_context";
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const REDACTED: &str = "******";
+
+/// 需要脱敏的字段，同时匹配 snake_case 与 camelCase 写法
+const REDACTED_FIELDS: [&str; 3] = ["password", "email_password", "emailPassword"];
+
+/// 处理器写入的审计上下文
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// 记录被操作对象及变更前后的数据，`before`/`after` 为 `None` 分别表示新增和删除
+pub fn record_change<B: Serialize, A: Serialize>(
+    depot: &mut Depot,
+    target_type: &str,
+    target_id: impl Into<String>,
+    before: Option<&B>,
+    after: Option<&A>,
+) {
+    let context = AuditContext {
+        target_type: Some(target_type.to_string()),
+        target_id: Some(target_id.into()),
+        before: before.and_then(|v| serde_json::to_value(v).ok()),
+        after: after.and_then(|v| serde_json::to_value(v).ok()),
+    };
+    depot.insert(AUDIT_CONTEXT_KEY, context);
+}
+
+#[handler]
+pub async fn audit_hoop(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    if !is_mutating(req.method()) {
+        ctrl.call_next(req, depot, res).await;
+        return;
+    }
+
+    let started = Instant::now();
+    ctrl.call_next(req, depot, res).await;
+    let latency_ms = started.elapsed().as_millis() as i64;
+
+    let status_code = res.status_code.unwrap_or(StatusCode::OK);
+    let context = depot
+        .get::<AuditContext>(AUDIT_CONTEXT_KEY)
+        .ok()
+        .cloned()
+        .unwrap_or_default();
+    let changes = diff_changes(context.before.as_ref(), context.after.as_ref());
+
+    AuditService::submit(AuditEntry {
+        actor: actor(req, depot),
+        request_id: req.header::<String>(REQUEST_ID_HEADER),
+        method: req.method().to_string(),
+        route: req.uri().path().to_string(),
+        client_ip: Some(req.remote_addr().to_string()),
+        target_type: context.target_type,
+        target_id: context.target_id,
+        changes,
+        outcome: if status_code.is_client_error() || status_code.is_server_error() {
+            sys_audit_log::OUTCOME_FAILURE
+        } else {
+            sys_audit_log::OUTCOME_SUCCESS
+        },
+        status_code: status_code.as_u16() as i32,
+        latency_ms,
+    });
+}
+
+fn is_mutating(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// 操作人优先取认证中间件写入的调用方，未挂载时自行解析 Authorization 头
+pub(super) fn actor(req: &Request, depot: &Depot) -> Option<String> {
+    if let Some(principal) = auth::principal(depot) {
+        return Some(match principal.api_key_id {
+            Some(id) => format!("{}#api_key:{}", principal.user_id, id),
+            None => principal.user_id.clone(),
+        });
+    }
+    if let Some(data) = depot.jwt_auth_data::<JwtClaims>() {
+        return Some(data.claims.uid().to_string());
+    }
+    req.header::<String>("Authorization")
+        .and_then(|token| jwt::decode_claims(&token))
+        .map(|claims| claims.uid().to_string())
+}
+
+/// 计算字段级差异，返回 {"字段": {"before": .., "after": ..}}，没有变化时返回 `None`
+pub fn diff_changes(before: Option<&Value>, after: Option<&Value>) -> Option<Value> {
+    let empty = Map::new();
+    let (before_map, after_map) = match (before, after) {
+        (None, None) => return None,
+        (Some(Value::Object(b)), Some(Value::Object(a))) => (b, a),
+        (Some(Value::Object(b)), None) => (b, &empty),
+        (None, Some(Value::Object(a))) => (&empty, a),
+        // 非对象值整体比较
+        (b, a) => {
+            if b == a {
+                return None;
+            }
+            let mut change = Map::new();
+            change.insert("before".into(), b.map(redact_nested).unwrap_or(Value::Null));
+            change.insert("after".into(), a.map(redact_nested).unwrap_or(Value::Null));
+            let mut changes = Map::new();
+            changes.insert("value".into(), Value::Object(change));
+            return Some(Value::Object(changes));
+        }
+    };
+
+    let mut changes = Map::new();
+    let fields = before_map.keys().chain(after_map.keys().filter(|k| !before_map.contains_key(*k)));
+    for field in fields {
+        let old = before_map.get(field).unwrap_or(&Value::Null);
+        let new = after_map.get(field).unwrap_or(&Value::Null);
+        if old == new {
+            continue;
+        }
+        let mut change = Map::new();
+        if REDACTED_FIELDS.contains(&field.as_str()) {
+            change.insert("before".into(), redact(old));
+            change.insert("after".into(), redact(new));
+        } else {
+            change.insert("before".into(), redact_nested(old));
+            change.insert("after".into(), redact_nested(new));
+        }
+        changes.insert(field.clone(), Value::Object(change));
+    }
+    if changes.is_empty() {
+        None
+    } else {
+        Some(Value::Object(changes))
+    }
+}
+
+fn redact(value: &Value) -> Value {
+    if value.is_null() {
+        Value::Null
+    } else {
+        Value::String(REDACTED.to_string())
+    }
+}
+
+/// 递归脱敏嵌套对象与数组中的敏感字段
+fn redact_nested(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, field)| {
+                    let field = if REDACTED_FIELDS.contains(&key.as_str()) {
+                        redact(field)
+                    } else {
+                        redact_nested(field)
+                    };
+                    (key.clone(), field)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_nested).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::diff_changes;
+
+    #[test]
+    fn test_diff_changes_redacts_sensitive_fields() {
+        let before = json!({"userName": "张三", "password": "old", "phone": "1"});
+        let after = json!({"userName": "李四", "password": "new", "phone": "1", "emailPassword": "x"});
+
+        let changes = diff_changes(Some(&before), Some(&after)).unwrap();
+
+        assert_eq!(changes["userName"], json!({"before": "张三", "after": "李四"}));
+        assert_eq!(changes["password"], json!({"before": "******", "after": "******"}));
+        assert_eq!(changes["emailPassword"], json!({"before": null, "after": "******"}));
+        assert!(changes.get("phone").is_none());
+    }
+
+    #[test]
+    fn test_diff_changes_redacts_nested_fields() {
+        let before = json!({"profile": {"password": "old", "name": "a"}});
+        let after = json!({"profile": {"password": "new", "name": "a"}, "accounts": [{"emailPassword": "x"}]});
+
+        let changes = diff_changes(Some(&before), Some(&after)).unwrap();
+
+        assert_eq!(changes["profile"]["before"], json!({"password": "******", "name": "a"}));
+        assert_eq!(changes["profile"]["after"], json!({"password": "******", "name": "a"}));
+        assert_eq!(changes["accounts"]["after"], json!([{"emailPassword": "******"}]));
+    }
+
+    #[test]
+    fn test_diff_changes_without_difference() {
+        let value = json!({"key": "a"});
+        assert!(diff_changes(Some(&value), Some(&value)).is_none());
+        assert!(diff_changes(None, None).is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! 认证中间件
+//!
+//! 同时支持用户 JWT（`Authorization` 头）与服务间调用的 API Key（`X-API-Key` 头），
+//! 认证通过后向 depot 写入统一的 [`Principal`]，处理器无需关心调用方的认证方式。
+
+use salvo::http::StatusError;
+use salvo::prelude::*;
+use salvo::Writer;
+use sea_orm::DatabaseConnection;
+
+use crate::db;
+use crate::hoops::jwt;
+use crate::repository::permission::{role_repository, user_repository};
+use crate::services::permission::api_key_service::ApiKeyService;
+use crate::services::permission::user_service::UserService;
+use crate::AppError;
+
+pub const API_KEY_HEADER: &str = "X-API-Key";
+const PRINCIPAL_KEY: &str = "principal";
+
+/// 管理员角色，拥有全部授权范围
+pub const ROLE_ADMIN: &str = "admin";
+pub const SCOPE_ADMIN: &str = "admin";
+pub const SCOPE_AUDIT_READ: &str = "audit:read";
+pub const SCOPE_METRICS_READ: &str = "metrics:read";
+/// 可以授予 API Key 的授权范围
+pub const SCOPES: &[&str] = &[SCOPE_ADMIN, SCOPE_AUDIT_READ, SCOPE_METRICS_READ];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrincipalKind {
+    User,
+    ApiKey,
+}
+
+/// 已认证的调用方
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub kind: PrincipalKind,
+    /// 用户id；API Key 调用时为密钥所属用户
+    pub user_id: String,
+    /// API Key 的授权范围，用户登录时为空
+    pub scopes: Vec<String>,
+    /// 用户拥有的角色编码；API Key 调用时为密钥所属用户的角色
+    pub roles: Vec<String>,
+    pub api_key_id: Option<i64>,
+}
+
+impl Principal {
+    pub fn user(user_id: impl Into<String>, roles: Vec<String>) -> Self {
+        Self {
+            kind: PrincipalKind::User,
+            user_id: user_id.into(),
+            scopes: Vec::new(),
+            roles,
+            api_key_id: None,
+        }
+    }
+
+    /// 用户按角色判断：管理员拥有全部授权范围，其他角色编码即授权范围；
+    /// API Key 仅拥有授权范围内的权限
+    pub fn has_scope(&self, scope: &str) -> bool {
+        match self.kind {
+            PrincipalKind::User => self.roles.iter().any(|role| role == ROLE_ADMIN || role == scope),
+            PrincipalKind::ApiKey => self.scopes.iter().any(|s| s == scope),
+        }
+    }
+
+    pub fn is_user(&self) -> bool {
+        self.kind == PrincipalKind::User
+    }
+}
+
+/// 获取当前请求的调用方，需挂载 [`auth_hoop`]
+pub fn principal(depot: &Depot) -> Option<&Principal> {
+    depot.get::<Principal>(PRINCIPAL_KEY).ok()
+}
+
+/// 获取当前请求的调用方，未认证时返回 401
+pub fn require_principal(depot: &Depot) -> Result<Principal, AppError> {
+    principal(depot)
+        .cloned()
+        .ok_or_else(|| StatusError::unauthorized().brief("未登录或登录已过期").into())
+}
+
+/// 认证请求，API Key 优先于 JWT；限流时已校验过的 API Key 不再重复校验
+#[handler]
+pub async fn auth_hoop(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    let result = match principal(depot) {
+        Some(principal) => Ok(principal.clone()),
+        None => authenticate(req).await,
+    };
+    match result {
+        Ok(principal) => {
+            depot.insert(PRINCIPAL_KEY, principal);
+            ctrl.call_next(req, depot, res).await;
+        }
+        Err(e) => {
+            e.write(req, depot, res).await;
+            ctrl.skip_rest();
+        }
+    }
+}
+
+/// 校验请求携带的 API Key，通过时写入 depot；未携带或校验失败时返回 None
+pub async fn api_key_principal(req: &Request, depot: &mut Depot) -> Option<Principal> {
+    if let Some(principal) = principal(depot) {
+        return (principal.kind == PrincipalKind::ApiKey).then(|| principal.clone());
+    }
+    let api_key = req.header::<String>(API_KEY_HEADER)?;
+    let principal = ApiKeyService::authenticate(&api_key, db::postgres::pool()).await.ok()?;
+    depot.insert(PRINCIPAL_KEY, principal.clone());
+    Some(principal)
+}
+
+async fn authenticate(req: &Request) -> Result<Principal, AppError> {
+    if let Some(api_key) = req.header::<String>(API_KEY_HEADER) {
+        return ApiKeyService::authenticate(&api_key, db::postgres::pool()).await;
+    }
+    let claims = req
+        .header::<String>("Authorization")
+        .and_then(|token| jwt::decode_claims(&token))
+        .ok_or_else(|| StatusError::unauthorized().brief("未登录或登录已过期"))?;
+    let db = db::postgres::pool();
+    // 锁定或失效的用户立即拒绝，锁定时作废的令牌在解锁后也不再有效
+    ensure_active(claims.uid(), db).await?;
+    if UserService::is_token_revoked(claims.uid(), claims.issued_at()).await {
+        return Err(StatusError::unauthorized().brief("登录已失效，请重新登录").into());
+    }
+    let roles = role_repository::query_user_role_codes(claims.uid(), db).await?;
+    Ok(Principal::user(claims.uid(), roles))
+}
+
+/// 校验用户存在且未锁定、未失效
+pub async fn ensure_active(user_id: &str, db: &DatabaseConnection) -> Result<(), AppError> {
+    match user_repository::query_user_by_user_id(user_id, db).await? {
+        Some(user) if user.is_valid() && !user.is_locked() => Ok(()),
+        _ => Err(StatusError::unauthorized().brief("用户已锁定或无效").into()),
+    }
+}
+
+/// 要求调用方拥有指定授权范围
+pub struct RequireScope(pub &'static str);
+
+#[async_trait]
+impl Handler for RequireScope {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let allowed = principal(depot).map(|p| p.has_scope(self.0)).unwrap_or(false);
+        if !allowed {
+            AppError::from(StatusError::forbidden().brief(format!("缺少授权范围: {}", self.0)))
+                .write(req, depot, res)
+                .await;
+            ctrl.skip_rest();
+        }
+    }
+}
+
+/// 仅允许用户登录访问，拒绝 API Key
+#[handler]
+pub async fn user_only(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    if !principal(depot).map(Principal::is_user).unwrap_or(false) {
+        AppError::from(StatusError::forbidden().brief("该接口仅允许用户登录访问"))
+            .write(req, depot, res)
+            .await;
+        ctrl.skip_rest();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_scopes_follow_roles() {
+        let user = Principal::user("u1", Vec::new());
+        assert!(!user.has_scope(SCOPE_ADMIN));
+        assert!(!user.has_scope(SCOPE_AUDIT_READ));
+
+        let auditor = Principal::user("u2", vec![SCOPE_AUDIT_READ.to_string()]);
+        assert!(auditor.has_scope(SCOPE_AUDIT_READ));
+        assert!(!auditor.has_scope(SCOPE_ADMIN));
+
+        let admin = Principal::user("u3", vec![ROLE_ADMIN.to_string()]);
+        assert!(admin.has_scope(SCOPE_ADMIN) && admin.has_scope(SCOPE_AUDIT_READ));
+    }
+}
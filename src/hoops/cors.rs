@@ -1,9 +1,65 @@
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
 use salvo::cors::{AllowHeaders, AllowMethods, AllowOrigin, Cors, CorsHandler};
+use salvo::http::{HeaderName, HeaderValue, Method};
+use salvo::prelude::*;
+
+use crate::config::{self, CorsConfig, CORS_ANY};
+
+/// 当前生效的跨域处理器，配置重新加载时替换
+static CORS: LazyLock<ArcSwap<CorsHandler>> =
+    LazyLock::new(|| ArcSwap::from_pointee(build(&config::current().cors)));
+
+/// 按配置处理跨域请求的中间件
+pub struct ConfiguredCors;
+
+#[async_trait]
+impl Handler for ConfiguredCors {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let cors = CORS.load_full();
+        cors.handle(req, depot, res, ctrl).await;
+    }
+}
+
+/// 由 `app::build_service` 挂在 `Service` 上，[`reload_cors`] 替换后下一个请求即生效
+pub fn cors_hoop() -> ConfiguredCors {
+    ConfiguredCors
+}
+
+/// 按新的配置重建跨域处理器
+pub fn reload_cors(config: &CorsConfig) {
+    CORS.store(Arc::new(build(config)));
+}
 
-pub fn cors_hoop() -> CorsHandler {
-    Cors::new()
-        .allow_origin(AllowOrigin::any())
-        .allow_methods(AllowMethods::any())
-        .allow_headers(AllowHeaders::any())
-        .into_handler()
+fn build(config: &CorsConfig) -> CorsHandler {
+    let any = |values: &[String]| values.iter().any(|v| v == CORS_ANY);
+    let mut cors = Cors::new();
+    cors = if any(&config.allow_origins) {
+        cors.allow_origin(AllowOrigin::any())
+    } else {
+        cors.allow_origin(AllowOrigin::list(
+            config.allow_origins.iter().filter_map(|o| HeaderValue::from_str(o).ok()),
+        ))
+    };
+    cors = if any(&config.allow_methods) {
+        cors.allow_methods(AllowMethods::any())
+    } else {
+        // 配置校验已保证可以解析
+        cors.allow_methods(AllowMethods::list(
+            config.allow_methods.iter().filter_map(|m| Method::from_bytes(m.as_bytes()).ok()),
+        ))
+    };
+    cors = if any(&config.allow_headers) {
+        cors.allow_headers(AllowHeaders::any())
+    } else {
+        cors.allow_headers(AllowHeaders::list(
+            config.allow_headers.iter().filter_map(|h| HeaderName::from_bytes(h.as_bytes()).ok()),
+        ))
+    };
+    if let Some(max_age) = config.max_age {
+        cors = cors.max_age(Duration::from_secs(max_age));
+    }
+    cors.into_handler()
 }
@@ -11,8 +11,22 @@ use crate::config::{self, JwtConfig};
 pub struct JwtClaims {
     uid: String,
     exp: i64,
+    /// 签发时间（毫秒），早于用户令牌作废时间的令牌无效；旧令牌没有该字段时视为 0
+    #[serde(default)]
+    iat_ms: i64,
 }
 
+impl JwtClaims {
+    pub fn uid(&self) -> &str {
+        &self.uid
+    }
+
+    pub fn issued_at(&self) -> i64 {
+        self.iat_ms
+    }
+}
+
+#[allow(dead_code)]
 pub fn auth_hoop(config: &JwtConfig) -> JwtAuth<JwtClaims, ConstDecoder> {
     JwtAuth::new(ConstDecoder::from_secret(
         config.secret.to_owned().as_bytes(),
@@ -26,10 +40,12 @@ pub fn auth_hoop(config: &JwtConfig) -> JwtAuth<JwtClaims, ConstDecoder> {
 }
 
 pub fn get_token(uid: impl Into<String>) -> Result<(String, i64)> {
-    let exp = OffsetDateTime::now_utc() + Duration::seconds(config::get().jwt.expiry);
+    let now = OffsetDateTime::now_utc();
+    let exp = now + Duration::seconds(config::get().jwt.expiry);
     let claim = JwtClaims {
         uid: uid.into(),
         exp: exp.unix_timestamp(),
+        iat_ms: (now.unix_timestamp_nanos() / 1_000_000) as i64,
     };
     let token: String = jsonwebtoken::encode(
         &jsonwebtoken::Header::default(),
@@ -49,3 +65,16 @@ pub fn decode_token(token: &str) -> bool {
     )
     .is_ok()
 }
+
+/// 解析令牌中的声明，支持带 `Bearer ` 前缀的令牌
+pub fn decode_claims(token: &str) -> Option<JwtClaims> {
+    let token = token.strip_prefix("Bearer ").unwrap_or(token).trim();
+    let validation = Validation::new(Algorithm::HS256);
+    decode::<JwtClaims>(
+        token,
+        &DecodingKey::from_secret(config::get().jwt.secret.as_bytes()),
+        &validation,
+    )
+    .ok()
+    .map(|data| data.claims)
+}
@@ -0,0 +1,93 @@
+//! 请求指标中间件
+
+use salvo::prelude::*;
+use tokio::time::Instant;
+
+use crate::metrics;
+
+/// 记录请求数与耗时，路由标签使用路由模板，见 [`route_label`]
+#[handler]
+pub async fn metrics_hoop(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    let start = Instant::now();
+    let route = route_label(req, res);
+    ctrl.call_next(req, depot, res).await;
+
+    let status = res.status_code.unwrap_or(StatusCode::OK).as_u16();
+    metrics::observe_http(req.method().as_str(), &route, status, start.elapsed());
+}
+
+/// 未匹配任何路由的请求（404/405）使用的固定路由标签，避免任意路径产生大量标签
+pub(super) const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// 请求的路由标签，需在服务级中间件中调用 `call_next` 之前取得：
+/// 未匹配路由时 salvo 在执行服务级中间件前已设置 404/405 状态码
+pub(super) fn route_label(req: &Request, res: &Response) -> String {
+    if res.status_code.is_some() {
+        return UNMATCHED_ROUTE.to_string();
+    }
+    route_template(req.uri().path(), req.params().iter())
+}
+
+/// 将路径中的参数值替换为 `{参数名}`，如 `/rust/user/A001/lock` -> `/rust/user/{user_id}/lock`
+pub(super) fn route_template<'a>(path: &str, params: impl Iterator<Item = (&'a String, &'a String)>) -> String {
+    let mut segments: Vec<String> = path.split('/').map(String::from).collect();
+    for (name, value) in params {
+        if value.is_empty() {
+            continue;
+        }
+        let value_segments: Vec<&str> = value.split('/').collect();
+        let len = value_segments.len();
+        // 通配参数（如 <**path>）可能跨多个路径段，取最后一次出现的位置
+        let position = (0..segments.len().saturating_sub(len - 1)).rev().find(|&i| {
+            segments[i..i + len]
+                .iter()
+                .zip(&value_segments)
+                .all(|(segment, value)| decode(segment) == *value)
+        });
+        if let Some(i) = position {
+            segments.splice(i..i + len, [format!("{{{}}}", name)]);
+        }
+    }
+    segments.join("/")
+}
+
+fn decode(segment: &str) -> String {
+    percent_encoding::percent_decode_str(segment)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_template() {
+        let params = [("user_id".to_string(), "A001".to_string())];
+        assert_eq!(
+            route_template("/rust/user/A001/lock", params.iter().map(|(k, v)| (k, v))),
+            "/rust/user/{user_id}/lock"
+        );
+
+        let params = [("path".to_string(), "js/alpinejs.js".to_string())];
+        assert_eq!(
+            route_template("/assets/js/alpinejs.js", params.iter().map(|(k, v)| (k, v))),
+            "/assets/{path}"
+        );
+
+        let params = [("key".to_string(), "a b".to_string())];
+        assert_eq!(
+            route_template("/redis/get/a%20b", params.iter().map(|(k, v)| (k, v))),
+            "/redis/get/{key}"
+        );
+    }
+
+    #[test]
+    fn test_unmatched_route_label() {
+        let req = Request::new();
+        let mut res = Response::new();
+        assert_eq!(route_label(&req, &res), "/");
+        res.status_code(StatusCode::NOT_FOUND);
+        assert_eq!(route_label(&req, &res), UNMATCHED_ROUTE);
+    }
+}
@@ -1,13 +1,19 @@
 use salvo::http::ResBody;
 use salvo::prelude::*;
 
+pub mod access_log;
+pub mod audit;
+pub mod auth;
 pub mod custom_middleware_example;
 pub mod jwt;
+pub mod metrics;
+pub mod rate_limit;
 mod cors;
-pub use cors::cors_hoop;
+pub use cors::{cors_hoop, reload_cors};
 mod state;
 pub use state::StateInjector;
 mod trace;
+pub use trace::trace_hoop;
 
 #[handler]
 pub async fn error_404(&self, res: &mut Response, ctrl: &mut FlowCtrl) {
@@ -0,0 +1,278 @@
+//! 限流中间件
+//!
+//! 按配置中的规则对请求计数，计数维度可以是客户端 IP、用户（JWT uid）或 API Key。
+//! 采用滑动窗口计数：当前窗口计数 + 上一窗口计数按剩余比例折算。
+//! 计数默认保存在 Redis 中供多实例共享，Redis 不可用时降级为进程内计数。
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use salvo::prelude::*;
+use salvo::Writer;
+
+use crate::cache::redis_manager;
+use crate::config::{self, RateLimitConfig, RateLimitRule, BACKEND_MEMORY, KEY_BY_API_KEY, KEY_BY_USER};
+use crate::hoops::{auth, jwt};
+use crate::AppError;
+
+/// 原子地递增当前窗口并读取上一窗口计数
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local current = redis.call('INCR', KEYS[1])
+if current == 1 then
+    redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+local previous = tonumber(redis.call('GET', KEYS[2]) or '0')
+return {current, previous}
+"#;
+
+/// 清理过期进程内计数的间隔（秒）
+const SWEEP_INTERVAL: u64 = 60;
+
+/// 进程内计数: (窗口序号, 当前窗口计数, 上一窗口计数, 窗口长度)
+type MemoryCounter = (u64, u64, u64, u64);
+
+static MEMORY_COUNTERS: LazyLock<Mutex<HashMap<String, MemoryCounter>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// 上次清理进程内计数的时间
+static LAST_SWEEP: AtomicU64 = AtomicU64::new(0);
+
+/// 限流判定结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u64,
+    pub remaining: u64,
+    /// 距当前窗口结束的秒数
+    pub reset: u64,
+}
+
+#[handler]
+pub async fn rate_limit_hoop(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    let config = config::current();
+    let config = &config.rate_limit;
+    if !config.enabled {
+        ctrl.call_next(req, depot, res).await;
+        return;
+    }
+    let method = req.method().to_string();
+    let path = req.uri().path().to_string();
+    let Some(rule) = config.match_rule(&method, &path) else {
+        ctrl.call_next(req, depot, res).await;
+        return;
+    };
+
+    let key = format!(
+        "{}{}:{}:{}",
+        config.key_prefix,
+        rule.path,
+        rule.method.as_deref().unwrap_or("*"),
+        client_key(req, depot, rule).await
+    );
+    let now = unix_now();
+    let decision = if config.backend == BACKEND_MEMORY {
+        check_memory(&key, rule, now)
+    } else {
+        match check_redis(&key, rule, now).await {
+            Ok(decision) => decision,
+            Err(e) => {
+                tracing::warn!("rate limit redis unavailable, fallback to memory: {}", e);
+                check_memory(&key, rule, now)
+            }
+        }
+    };
+
+    let _ = res.add_header("RateLimit-Limit", decision.limit.to_string(), true);
+    let _ = res.add_header("RateLimit-Remaining", decision.remaining.to_string(), true);
+    let _ = res.add_header("RateLimit-Reset", decision.reset.to_string(), true);
+    if !decision.allowed {
+        tracing::warn!(key = %key, "rate limit exceeded");
+        AppError::RateLimited(decision.reset.max(1))
+            .write(req, depot, res)
+            .await;
+        ctrl.skip_rest();
+        return;
+    }
+    ctrl.call_next(req, depot, res).await;
+}
+
+/// 计算计数维度标识，取不到用户或有效的 API Key 时按 IP 计数
+async fn client_key(req: &Request, depot: &mut Depot, rule: &RateLimitRule) -> String {
+    match rule.key_by.as_str() {
+        KEY_BY_USER => {
+            if let Some(claims) = req
+                .header::<String>("Authorization")
+                .and_then(|token| jwt::decode_claims(&token))
+            {
+                return format!("user:{}", claims.uid());
+            }
+        }
+        KEY_BY_API_KEY => {
+            // 只按校验通过的密钥计数，随机的请求头不能换取新的计数桶
+            if let Some(api_key_id) = auth::api_key_principal(req, depot).await.and_then(|p| p.api_key_id) {
+                return format!("api_key:{}", api_key_id);
+            }
+        }
+        _ => {}
+    }
+    format!("ip:{}", client_ip(req))
+}
+
+/// 客户端 IP
+///
+/// 只有直连地址属于 `rate_limit.trusted_proxies` 时才读取 `X-Forwarded-For`，从右向左跳过可信代理，
+/// 取第一个不可信的地址；否则使用直连地址，避免客户端伪造请求头绕过按 IP 的限制。
+pub fn client_ip(req: &Request) -> String {
+    let remote = req
+        .remote_addr()
+        .as_ipv4()
+        .map(|addr| IpAddr::V4(*addr.ip()))
+        .or_else(|| req.remote_addr().as_ipv6().map(|addr| IpAddr::V6(*addr.ip())));
+    let Some(remote) = remote else {
+        return "unknown".to_string();
+    };
+    let config = config::current();
+    let config = &config.rate_limit;
+    if !config.is_trusted_proxy(remote) {
+        return remote.to_string();
+    }
+    let forwarded = req.header::<String>("X-Forwarded-For").unwrap_or_default();
+    forwarded_client(config, &forwarded).unwrap_or(remote).to_string()
+}
+
+/// 从右向左取 `X-Forwarded-For` 中第一个不属于可信代理的地址
+fn forwarded_client(config: &RateLimitConfig, forwarded: &str) -> Option<IpAddr> {
+    let mut last = None;
+    for hop in forwarded.split(',').rev() {
+        // 无法解析的地址不可信，之前的地址也不再采用
+        let ip = hop.trim().parse::<IpAddr>().ok()?;
+        if !config.is_trusted_proxy(ip) {
+            return Some(ip);
+        }
+        last = Some(ip);
+    }
+    last
+}
+
+async fn check_redis(key: &str, rule: &RateLimitRule, now: u64) -> Result<RateLimitDecision> {
+    let window_index = now / rule.window;
+    let mut conn = redis_manager::get_redis_connection().await?;
+    let (current, previous): (u64, u64) = deadpool_redis::redis::Script::new(SLIDING_WINDOW_SCRIPT)
+        .key(format!("{}:{}", key, window_index))
+        .key(format!("{}:{}", key, window_index.saturating_sub(1)))
+        .arg(rule.window * 2)
+        .invoke_async(&mut conn)
+        .await?;
+    Ok(decide(rule, now, current, previous))
+}
+
+fn check_memory(key: &str, rule: &RateLimitRule, now: u64) -> RateLimitDecision {
+    let window_index = now / rule.window;
+    let mut counters = MEMORY_COUNTERS.lock().expect("rate limit counters lock poisoned");
+    let last_sweep = LAST_SWEEP.load(Ordering::Relaxed);
+    if now >= last_sweep + SWEEP_INTERVAL || now < last_sweep {
+        LAST_SWEEP.store(now, Ordering::Relaxed);
+        sweep(&mut counters, now);
+    }
+    let entry = counters.entry(key.to_string()).or_insert((window_index, 0, 0, rule.window));
+    if entry.0 != window_index {
+        let previous = if entry.0 + 1 == window_index { entry.1 } else { 0 };
+        *entry = (window_index, 0, previous, rule.window);
+    }
+    entry.1 += 1;
+    decide(rule, now, entry.1, entry.2)
+}
+
+/// 移除已超过两个窗口的计数，这些计数不再参与折算
+fn sweep(counters: &mut HashMap<String, MemoryCounter>, now: u64) {
+    counters.retain(|_, (window_index, _, _, window)| now / *window <= *window_index + 1);
+}
+
+/// 按滑动窗口折算请求数并给出判定
+pub fn decide(rule: &RateLimitRule, now: u64, current: u64, previous: u64) -> RateLimitDecision {
+    let elapsed = now % rule.window;
+    let weight = (rule.window - elapsed) as f64 / rule.window as f64;
+    let estimated = current + (previous as f64 * weight).floor() as u64;
+    RateLimitDecision {
+        allowed: estimated <= rule.limit,
+        limit: rule.limit,
+        remaining: rule.limit.saturating_sub(estimated),
+        reset: rule.window - elapsed,
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(limit: u64, window: u64) -> RateLimitRule {
+        RateLimitRule {
+            path: "/rust/auth/login".into(),
+            method: Some("POST".into()),
+            key_by: "ip".into(),
+            limit,
+            window,
+        }
+    }
+
+    #[test]
+    fn test_memory_limit_within_window() {
+        let rule = rule(3, 60);
+        let now = 600;
+        for remaining in [2, 1, 0] {
+            let decision = check_memory("test:window", &rule, now);
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, remaining);
+        }
+        let decision = check_memory("test:window", &rule, now + 1);
+        assert!(!decision.allowed);
+        assert_eq!(decision.reset, 59);
+    }
+
+    #[test]
+    fn test_previous_window_is_weighted() {
+        let rule = rule(10, 60);
+        // 新窗口开始 30 秒，上一窗口 10 次按一半折算
+        let decision = decide(&rule, 630, 5, 10);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        let decision = decide(&rule, 630, 6, 10);
+        assert!(!decision.allowed);
+    }
+
+    #[test]
+    fn test_sweep_drops_expired_counters() {
+        let mut counters = HashMap::new();
+        counters.insert("active".to_string(), (10, 1, 0, 60));
+        counters.insert("previous".to_string(), (9, 1, 0, 60));
+        counters.insert("expired".to_string(), (8, 1, 0, 60));
+        sweep(&mut counters, 630);
+        let mut keys: Vec<_> = counters.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["active", "previous"]);
+    }
+
+    #[test]
+    fn test_forwarded_client_skips_trusted_proxies() {
+        let config = RateLimitConfig {
+            trusted_proxies: vec!["10.0.0.0/8".into(), "192.168.1.1".into()],
+            ..Default::default()
+        };
+        let ip = |text: &str| text.parse::<IpAddr>().ok();
+        assert_eq!(forwarded_client(&config, "1.1.1.1, 2.2.2.2, 10.1.2.3"), ip("2.2.2.2"));
+        assert_eq!(forwarded_client(&config, "10.0.0.1, 192.168.1.1"), ip("10.0.0.1"));
+        assert_eq!(forwarded_client(&config, "1.1.1.1, bogus, 10.0.0.1"), None);
+        assert!(!config.is_trusted_proxy("192.168.1.2".parse().unwrap()));
+        assert!(config.validate().is_ok());
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use salvo::prelude::*;
+
+use crate::app::AppState;
+
+/// 将应用状态注入 `Depot`，处理器通过 `depot.get::<Arc<AppState>>("app_state")` 读取
+pub struct StateInjector {
+    state: Arc<AppState>,
+}
+
+impl StateInjector {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl Handler for StateInjector {
+    async fn handle(&self, _req: &mut Request, depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+        depot.insert("app_state", self.state.clone());
+    }
+}
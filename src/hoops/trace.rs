@@ -0,0 +1,45 @@
+//! 链路追踪中间件
+//!
+//! 为每个请求创建服务端 span，从 W3C `traceparent` 头继承上游链路，并把 salvo 生成的
+//! 链路ID（`x-request-id`）记为 span 属性；响应头 `x-trace-id` 返回 trace id，
+//! 便于由链路ID或日志定位到完整链路。挂在 `Service` 上，未匹配路由的请求同样创建 span，
+//! 名称使用固定的路由标签。
+
+use salvo::prelude::*;
+use tracing::field::Empty;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::telemetry;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const TRACE_ID_HEADER: &str = "x-trace-id";
+
+#[handler]
+pub async fn trace_hoop(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    let route = super::metrics::route_label(req, res);
+    let request_id = req.header::<String>(REQUEST_ID_HEADER).unwrap_or_default();
+    let span = tracing::info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", req.method(), route),
+        otel.kind = "server",
+        otel.status_code = Empty,
+        http.request.method = %req.method(),
+        http.route = %route,
+        url.path = %req.uri().path(),
+        request_id = %request_id,
+        http.response.status_code = Empty,
+    );
+    let _ = span.set_parent(telemetry::extract_http(req.headers()));
+    if let Some(trace_id) = telemetry::trace_id(&span) {
+        let _ = res.add_header(TRACE_ID_HEADER, trace_id, true);
+    }
+
+    ctrl.call_next(req, depot, res).instrument(span.clone()).await;
+
+    let status = res.status_code.unwrap_or(StatusCode::OK);
+    span.record("http.response.status_code", status.as_u16());
+    if status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+}
@@ -0,0 +1,246 @@
+//! 消费者组
+//!
+//! 每个消费者组在独立任务中拉取消息并交给 [`MessageHandler`] 处理，处理失败时按退避重试，
+//! 超过 `max_retries` 后投递到死信主题并提交位移。进程退出时调用 [`shutdown`]，
+//! 消费者在处理完当前消息后停止。
+
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::{BrokerMessage, MessageBroker, Subscription};
+use crate::config::KafkaConfig;
+use crate::telemetry;
+
+pub const HEADER_ORIGINAL_TOPIC: &str = "x-original-topic";
+pub const HEADER_CONSUMER_GROUP: &str = "x-consumer-group";
+pub const HEADER_ERROR: &str = "x-error";
+pub const HEADER_ATTEMPTS: &str = "x-attempts";
+
+/// 停机信号
+static SHUTDOWN: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::channel(false).0);
+/// 运行中的消费者任务
+static WORKERS: LazyLock<Mutex<Vec<JoinHandle<()>>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// 消息处理器
+#[async_trait]
+pub trait MessageHandler: Send + Sync + 'static {
+    async fn handle(&self, message: &BrokerMessage) -> Result<()>;
+}
+
+pub struct ConsumerGroup {
+    broker: Arc<dyn MessageBroker>,
+    group_id: String,
+    topics: Vec<String>,
+    max_retries: u32,
+    config: KafkaConfig,
+}
+
+impl ConsumerGroup {
+    /// 使用全局消息代理与配置创建消费者组
+    #[allow(dead_code)] // 供业务模块注册消费者组，当前服务尚未订阅任何主题
+    pub fn new(group_id: impl Into<String>, topics: Vec<String>) -> Self {
+        Self::with_broker(super::broker(), &super::broker_config(), group_id, topics)
+    }
+
+    pub fn with_broker(
+        broker: Arc<dyn MessageBroker>,
+        config: &KafkaConfig,
+        group_id: impl Into<String>,
+        topics: Vec<String>,
+    ) -> Self {
+        Self {
+            broker,
+            group_id: group_id.into(),
+            topics,
+            max_retries: config.max_retries,
+            config: config.clone(),
+        }
+    }
+
+    /// 订阅主题并在后台任务中开始消费
+    #[allow(dead_code)]
+    pub async fn spawn<H: MessageHandler>(self, handler: H) -> Result<()> {
+        let subscription = self.broker.subscribe(&self.group_id, &self.topics).await?;
+        tracing::info!(group = %self.group_id, topics = ?self.topics, "consumer group started");
+        let handle = tokio::spawn(self.run(subscription, Arc::new(handler)));
+        WORKERS
+            .lock()
+            .expect("consumer workers lock poisoned")
+            .push(handle);
+        Ok(())
+    }
+
+    async fn run(self, mut subscription: Box<dyn Subscription>, handler: Arc<dyn MessageHandler>) {
+        let mut shutdown = SHUTDOWN.subscribe();
+        if *shutdown.borrow() {
+            return;
+        }
+        loop {
+            let received = tokio::select! {
+                _ = shutdown.changed() => break,
+                received = subscription.recv() => received,
+            };
+            match received {
+                Ok(Some(message)) => {
+                    let span = tracing::info_span!(
+                        "kafka.consume",
+                        otel.name = %format!("{} process", message.topic),
+                        otel.kind = "consumer",
+                        messaging.destination.name = %message.topic,
+                        messaging.consumer.group.name = %self.group_id,
+                    );
+                    let _ = span.set_parent(telemetry::extract_message(&message.headers));
+                    if self.process(&message, handler.as_ref()).instrument(span).await
+                        && let Err(e) = subscription.commit().await
+                    {
+                        tracing::error!(group = %self.group_id, "commit failed: {}", e);
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::error!(group = %self.group_id, "consume failed: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+        tracing::info!(group = %self.group_id, "consumer group stopped");
+    }
+
+    /// 处理单条消息，返回是否可以提交位移
+    async fn process(&self, message: &BrokerMessage, handler: &dyn MessageHandler) -> bool {
+        let mut attempt = 0;
+        let error = loop {
+            match handler.handle(message).await {
+                Ok(_) => return true,
+                Err(e) if attempt < self.max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        group = %self.group_id,
+                        topic = %message.topic,
+                        attempt,
+                        "message handle failed, retrying: {}",
+                        e
+                    );
+                    tokio::time::sleep(retry_backoff(attempt)).await;
+                }
+                Err(e) => break e,
+            }
+        };
+
+        let dead_letter_topic = self.config.dead_letter_topic(&message.topic);
+        tracing::error!(
+            group = %self.group_id,
+            topic = %message.topic,
+            dead_letter_topic = %dead_letter_topic,
+            "message moved to dead letter topic: {}",
+            error
+        );
+        let mut dead_letter = message.clone();
+        dead_letter.topic = dead_letter_topic;
+        let dead_letter = dead_letter
+            .with_header(HEADER_ORIGINAL_TOPIC, &message.topic)
+            .with_header(HEADER_CONSUMER_GROUP, &self.group_id)
+            .with_header(HEADER_ERROR, error.to_string())
+            .with_header(HEADER_ATTEMPTS, (attempt + 1).to_string());
+        match self.broker.publish(dead_letter).await {
+            Ok(_) => true,
+            Err(e) => {
+                // 死信发送失败时不提交，重启后重新消费
+                tracing::error!(group = %self.group_id, "dead letter publish failed: {}", e);
+                false
+            }
+        }
+    }
+}
+
+fn retry_backoff(attempt: u32) -> Duration {
+    Duration::from_millis(200 * 2u64.pow(attempt.min(6)))
+}
+
+/// 通知所有消费者停止，并在超时时间内等待其退出
+pub async fn shutdown(timeout: Duration) {
+    let _ = SHUTDOWN.send(true);
+    let workers: Vec<_> = WORKERS
+        .lock()
+        .expect("consumer workers lock poisoned")
+        .drain(..)
+        .collect();
+    if workers.is_empty() {
+        return;
+    }
+    tracing::info!("waiting for {} consumer group(s) to stop", workers.len());
+    let wait_all = async {
+        for worker in workers {
+            let _ = worker.await;
+        }
+    };
+    if tokio::time::timeout(timeout, wait_all).await.is_err() {
+        tracing::warn!("consumer groups did not stop within {:?}", timeout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka::InMemoryBroker;
+
+    struct RejectBad;
+
+    #[async_trait]
+    impl MessageHandler for RejectBad {
+        async fn handle(&self, message: &BrokerMessage) -> Result<()> {
+            if message.payload_str() == Some("bad") {
+                anyhow::bail!("bad payload");
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_message_goes_to_dead_letter_topic() {
+        let broker = InMemoryBroker::new();
+        let config = KafkaConfig {
+            max_retries: 1,
+            ..Default::default()
+        };
+        ConsumerGroup::with_broker(
+            Arc::new(broker.clone()),
+            &config,
+            "test_group",
+            vec!["orders".to_string()],
+        )
+        .spawn(RejectBad)
+        .await
+        .unwrap();
+
+        broker
+            .publish(BrokerMessage::new("orders", None, "good"))
+            .await
+            .unwrap();
+        broker
+            .publish(BrokerMessage::new("orders", Some("k1".into()), "bad"))
+            .await
+            .unwrap();
+
+        let mut dead_letters = Vec::new();
+        for _ in 0..50 {
+            dead_letters = broker.messages("orders.dlq").await;
+            if !dead_letters.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].payload_str(), Some("bad"));
+        assert_eq!(dead_letters[0].key.as_deref(), Some("k1"));
+        assert_eq!(dead_letters[0].header(HEADER_ORIGINAL_TOPIC), Some("orders"));
+        assert_eq!(dead_letters[0].header(HEADER_ATTEMPTS), Some("2"));
+    }
+}
@@ -0,0 +1,136 @@
+//! 基于 rdkafka 的消息代理实现
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::producer::Producer;
+use rdkafka::message::{Header, Headers, Message, OwnedHeaders, OwnedMessage};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+
+use super::{BrokerMessage, MessageBroker, Subscription};
+use crate::config::KafkaConfig;
+
+pub struct KafkaBroker {
+    config: KafkaConfig,
+    producer: FutureProducer,
+}
+
+impl KafkaBroker {
+    pub fn new(config: &KafkaConfig) -> Result<Self> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("security.protocol", &config.security_protocol)
+            .set("acks", &config.ack_mode)
+            .set("message.timeout.ms", config.send_timeout.to_string())
+            .create()?;
+        Ok(Self {
+            config: config.clone(),
+            producer,
+        })
+    }
+}
+
+#[async_trait]
+impl MessageBroker for KafkaBroker {
+    async fn publish(&self, message: BrokerMessage) -> Result<()> {
+        let mut headers = OwnedHeaders::new();
+        for (key, value) in &message.headers {
+            headers = headers.insert(Header {
+                key,
+                value: Some(value.as_bytes()),
+            });
+        }
+        let mut record = FutureRecord::to(&message.topic)
+            .payload(&message.payload)
+            .headers(headers);
+        if let Some(key) = &message.key {
+            record = record.key(key);
+        }
+        self.producer
+            .send(record, Duration::from_millis(self.config.send_timeout))
+            .await
+            .map_err(|(e, _)| anyhow!("kafka send to {} failed: {}", message.topic, e))?;
+        Ok(())
+    }
+
+    async fn subscribe(&self, group_id: &str, topics: &[String]) -> Result<Box<dyn Subscription>> {
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &self.config.bootstrap_servers)
+            .set("security.protocol", &self.config.security_protocol)
+            .set("group.id", group_id)
+            .set("enable.auto.commit", self.config.enable_auto_commit.to_string())
+            .set("auto.offset.reset", &self.config.auto_offset_reset)
+            .create()?;
+        let topics: Vec<&str> = topics.iter().map(String::as_str).collect();
+        consumer.subscribe(&topics)?;
+        Ok(Box::new(KafkaSubscription {
+            consumer,
+            last: None,
+            auto_commit: self.config.enable_auto_commit,
+        }))
+    }
+
+    async fn ping(&self, timeout: Duration) -> Result<()> {
+        // 拉取集群元数据是阻塞调用
+        let producer = self.producer.clone();
+        tokio::task::spawn_blocking(move || producer.client().fetch_metadata(None, timeout))
+            .await?
+            .map_err(|e| anyhow!("kafka fetch metadata failed: {}", e))?;
+        Ok(())
+    }
+}
+
+struct KafkaSubscription {
+    consumer: StreamConsumer,
+    last: Option<OwnedMessage>,
+    auto_commit: bool,
+}
+
+#[async_trait]
+impl Subscription for KafkaSubscription {
+    async fn recv(&mut self) -> Result<Option<BrokerMessage>> {
+        let borrowed = self.consumer.recv().await?;
+        let owned = borrowed.detach();
+        let headers = owned
+            .headers()
+            .map(|h| {
+                h.iter()
+                    .map(|header| {
+                        let value = header
+                            .value
+                            .map(|v| String::from_utf8_lossy(v).into_owned())
+                            .unwrap_or_default();
+                        (header.key.to_string(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let message = BrokerMessage {
+            topic: owned.topic().to_string(),
+            key: owned.key().map(|k| String::from_utf8_lossy(k).into_owned()),
+            payload: owned.payload().map(<[u8]>::to_vec).unwrap_or_default(),
+            headers,
+        };
+        self.last = Some(owned);
+        Ok(Some(message))
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        if self.auto_commit {
+            return Ok(());
+        }
+        if let Some(last) = self.last.take() {
+            let mut tpl = rdkafka::TopicPartitionList::new();
+            tpl.add_partition_offset(
+                last.topic(),
+                last.partition(),
+                rdkafka::Offset::Offset(last.offset() + 1),
+            )?;
+            self.consumer.commit(&tpl, CommitMode::Async)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! 内存消息代理
+//!
+//! 同一消费者组内的订阅者共享消息，不同消费者组各自收到完整消息。
+//! 新消费者组订阅时会回放主题中的历史消息，行为等同于 `auto.offset.reset = earliest`；
+//! 每个主题只保留最近 [`DEFAULT_RETENTION`] 条历史消息，超出后丢弃最早的消息。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::sync::{mpsc, Mutex};
+
+use super::{BrokerMessage, MessageBroker, Subscription};
+
+/// 每个主题默认保留的历史消息条数
+pub const DEFAULT_RETENTION: usize = 10_000;
+
+type GroupReceiver = Arc<Mutex<mpsc::UnboundedReceiver<BrokerMessage>>>;
+
+#[derive(Default)]
+struct GroupState {
+    sender: Option<mpsc::UnboundedSender<BrokerMessage>>,
+    receiver: Option<GroupReceiver>,
+    topics: Vec<String>,
+}
+
+#[derive(Default)]
+struct State {
+    /// 每个主题的历史消息
+    logs: HashMap<String, VecDeque<BrokerMessage>>,
+    /// 消费者组 -> 组状态
+    groups: HashMap<String, GroupState>,
+}
+
+#[derive(Clone)]
+pub struct InMemoryBroker {
+    state: Arc<Mutex<State>>,
+    retention: usize,
+}
+
+impl Default for InMemoryBroker {
+    fn default() -> Self {
+        Self::with_retention(DEFAULT_RETENTION)
+    }
+}
+
+impl InMemoryBroker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 指定每个主题保留的历史消息条数
+    pub fn with_retention(retention: usize) -> Self {
+        Self {
+            state: Arc::default(),
+            retention,
+        }
+    }
+
+    /// 获取主题中的全部历史消息，便于测试断言。
+    #[allow(dead_code)]
+    pub async fn messages(&self, topic: &str) -> Vec<BrokerMessage> {
+        let state = self.state.lock().await;
+        state
+            .logs
+            .get(topic)
+            .map(|log| log.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl MessageBroker for InMemoryBroker {
+    async fn publish(&self, message: BrokerMessage) -> Result<()> {
+        let mut state = self.state.lock().await;
+        for group in state.groups.values() {
+            if group.topics.contains(&message.topic)
+                && let Some(sender) = &group.sender
+            {
+                let _ = sender.send(message.clone());
+            }
+        }
+        let log = state.logs.entry(message.topic.clone()).or_default();
+        log.push_back(message);
+        while log.len() > self.retention {
+            log.pop_front();
+        }
+        Ok(())
+    }
+
+    async fn subscribe(&self, group_id: &str, topics: &[String]) -> Result<Box<dyn Subscription>> {
+        if topics.is_empty() {
+            return Err(anyhow!("订阅主题不能为空"));
+        }
+        let mut state = self.state.lock().await;
+        let State { logs, groups } = &mut *state;
+        let group = groups.entry(group_id.to_string()).or_default();
+        if group.sender.is_none() {
+            let (tx, rx) = mpsc::unbounded_channel();
+            group.sender = Some(tx);
+            group.receiver = Some(Arc::new(Mutex::new(rx)));
+        }
+        let sender = group.sender.clone().expect("group sender should be set");
+        for topic in topics {
+            if group.topics.contains(topic) {
+                continue;
+            }
+            group.topics.push(topic.clone());
+            for message in logs.get(topic).into_iter().flatten() {
+                let _ = sender.send(message.clone());
+            }
+        }
+        let receiver = group.receiver.clone().expect("group receiver should be set");
+        Ok(Box::new(InMemorySubscription { receiver }))
+    }
+}
+
+struct InMemorySubscription {
+    receiver: GroupReceiver,
+}
+
+#[async_trait]
+impl Subscription for InMemorySubscription {
+    async fn recv(&mut self) -> Result<Option<BrokerMessage>> {
+        Ok(self.receiver.lock().await.recv().await)
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        // 内存实现中消息出队即视为已提交
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_retention_drops_oldest() {
+        let broker = InMemoryBroker::with_retention(2);
+        for i in 0..3 {
+            broker
+                .publish(BrokerMessage::new("orders", None, format!("m{}", i)))
+                .await
+                .unwrap();
+        }
+        let payloads: Vec<_> = broker
+            .messages("orders")
+            .await
+            .iter()
+            .map(|m| m.payload_str().unwrap().to_string())
+            .collect();
+        assert_eq!(payloads, vec!["m1", "m2"]);
+
+        // 新消费者组只回放保留的消息
+        let mut subscription = broker.subscribe("g1", &["orders".to_string()]).await.unwrap();
+        let first = subscription.recv().await.unwrap().unwrap();
+        assert_eq!(first.payload_str(), Some("m1"));
+    }
+}
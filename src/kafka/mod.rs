@@ -0,0 +1,150 @@
+//! 消息中间件模块
+//!
+//! 定义统一的消息代理抽象 [`MessageBroker`]，提供 Kafka 实现（需启用 `kafka` 特性）
+//! 以及内存实现（用于本地开发与测试）。
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+use crate::config::KafkaConfig;
+
+pub mod consumer;
+#[cfg(feature = "kafka")]
+mod kafka_broker;
+mod memory;
+pub mod producer;
+
+pub use memory::InMemoryBroker;
+
+/// 消息代理全局实例
+static BROKER: OnceLock<Arc<dyn MessageBroker>> = OnceLock::new();
+/// 初始化时使用的配置，消费者组读取重试次数与死信主题后缀
+static BROKER_CONFIG: OnceLock<KafkaConfig> = OnceLock::new();
+
+/// 代理中传递的消息
+#[derive(Clone, Debug, Default)]
+pub struct BrokerMessage {
+    pub topic: String,
+    pub key: Option<String>,
+    pub payload: Vec<u8>,
+    pub headers: Vec<(String, String)>,
+}
+
+impl BrokerMessage {
+    pub fn new(topic: impl Into<String>, key: Option<String>, payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            topic: topic.into(),
+            key,
+            payload: payload.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn payload_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.payload).ok()
+    }
+
+    /// 将消息体按 JSON 反序列化
+    #[allow(dead_code)]
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.payload)?)
+    }
+}
+
+/// 消息代理抽象
+#[async_trait]
+pub trait MessageBroker: Send + Sync {
+    /// 发送消息，返回时消息已被代理确认
+    async fn publish(&self, message: BrokerMessage) -> Result<()>;
+
+    /// 以指定消费者组订阅主题
+    async fn subscribe(&self, group_id: &str, topics: &[String]) -> Result<Box<dyn Subscription>>;
+
+    /// 检查与代理的连接，用于健康检查；进程内实现始终可用
+    async fn ping(&self, _timeout: Duration) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 订阅句柄
+#[async_trait]
+pub trait Subscription: Send {
+    /// 拉取下一条消息，订阅关闭时返回 `None`
+    async fn recv(&mut self) -> Result<Option<BrokerMessage>>;
+
+    /// 提交最近一次拉取的消息位移
+    async fn commit(&mut self) -> Result<()>;
+}
+
+/// 根据配置初始化消息代理，未配置时使用内存实现。
+pub fn init(config: Option<&KafkaConfig>) -> Result<()> {
+    let config = config.cloned().unwrap_or_default();
+    let broker = build_broker(&config)?;
+    BROKER
+        .set(broker)
+        .map_err(|_| anyhow!("message broker should only be set once"))?;
+    let _ = BROKER_CONFIG.set(config);
+    Ok(())
+}
+
+#[cfg(feature = "kafka")]
+fn build_broker(config: &KafkaConfig) -> Result<Arc<dyn MessageBroker>> {
+    use crate::config::DRIVER_KAFKA;
+
+    if config.driver == DRIVER_KAFKA {
+        tracing::info!(servers = %config.bootstrap_servers, "kafka broker enabled");
+        return Ok(Arc::new(kafka_broker::KafkaBroker::new(config)?));
+    }
+    Ok(Arc::new(InMemoryBroker::new()))
+}
+
+#[cfg(not(feature = "kafka"))]
+fn build_broker(config: &KafkaConfig) -> Result<Arc<dyn MessageBroker>> {
+    use crate::config::DRIVER_KAFKA;
+
+    if config.driver == DRIVER_KAFKA {
+        return Err(anyhow!(
+            "kafka.driver = \"kafka\" 需要使用 `--features kafka` 编译"
+        ));
+    }
+    Ok(Arc::new(InMemoryBroker::new()))
+}
+
+/// 获取全局消息代理（需先初始化）。
+pub fn broker() -> Arc<dyn MessageBroker> {
+    BROKER
+        .get()
+        .cloned()
+        .expect("message broker should be initialized before use")
+}
+
+/// 检查消息代理连接，未初始化时返回错误
+pub async fn ping(timeout: Duration) -> Result<()> {
+    let broker = BROKER
+        .get()
+        .cloned()
+        .ok_or_else(|| anyhow!("message broker not initialized"))?;
+    broker.ping(timeout).await
+}
+
+/// 获取消息代理配置（未初始化时为默认配置）。
+pub fn broker_config() -> KafkaConfig {
+    BROKER_CONFIG.get().cloned().unwrap_or_default()
+}
@@ -0,0 +1,67 @@
+//! 类型化生产者
+//!
+//! 将业务结构体序列化为 JSON 后发送到固定主题。
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{BrokerMessage, MessageBroker};
+use crate::telemetry;
+
+pub const HEADER_CONTENT_TYPE: &str = "content-type";
+pub const HEADER_MESSAGE_TYPE: &str = "x-message-type";
+
+/// 绑定主题与消息类型的生产者
+pub struct TypedProducer<T> {
+    broker: Arc<dyn MessageBroker>,
+    topic: String,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for TypedProducer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            broker: self.broker.clone(),
+            topic: self.topic.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Serialize> TypedProducer<T> {
+    pub fn new(broker: Arc<dyn MessageBroker>, topic: impl Into<String>) -> Self {
+        Self {
+            broker,
+            topic: topic.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// 使用全局消息代理创建生产者
+    #[allow(dead_code)] // 供业务模块发送消息，当前服务尚未使用
+    pub fn with_global(topic: impl Into<String>) -> Self {
+        Self::new(super::broker(), topic)
+    }
+
+    #[allow(dead_code)]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// 发送消息，`key` 相同的消息在 Kafka 中会落到同一分区以保证顺序
+    #[allow(dead_code)]
+    pub async fn send(&self, key: Option<&str>, value: &T) -> Result<()> {
+        let payload = serde_json::to_vec(value)?;
+        let mut message = BrokerMessage::new(&self.topic, key.map(str::to_string), payload)
+            .with_header(HEADER_CONTENT_TYPE, "application/json")
+            .with_header(HEADER_MESSAGE_TYPE, std::any::type_name::<T>());
+        // 传递链路上下文，消费者处理时接续同一条链路
+        for (name, value) in telemetry::current_headers() {
+            message = message.with_header(name, value);
+        }
+        self.broker.publish(message).await
+    }
+}
@@ -0,0 +1,172 @@
+//! 邮件发送模块
+//!
+//! 业务代码通过 [`enqueue`] 提交邮件，后台任务按顺序发送，失败后按指数退避重试，
+//! 超过最大次数后记录错误日志并丢弃。等待重试的邮件由发送任务保存，停止时与队列中剩余的邮件一样
+//! 各尝试发送一次。发件人可以是系统账号，也可以是用户本人
+//! （使用 `sys_user` 中加密保存的邮箱密码）。
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::config::MailConfig;
+
+pub mod template;
+mod transport;
+
+static QUEUE: OnceLock<mpsc::Sender<MailJob>> = OnceLock::new();
+static SHUTDOWN: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::channel(false).0);
+static WORKER: LazyLock<Mutex<Option<JoinHandle<()>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// 发件人
+#[derive(Debug, Clone, PartialEq)]
+pub enum MailSender {
+    /// 使用配置中的系统账号发送
+    System,
+    /// 以指定用户的邮箱账号发送
+    User(String),
+}
+
+/// 待发送的邮件
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub to: Vec<String>,
+    pub subject: String,
+    pub html_body: String,
+    pub sender: MailSender,
+}
+
+impl MailMessage {
+    /// 使用模板生成邮件，模板说明见 [`template`]
+    pub fn from_template(
+        name: &str,
+        vars: &HashMap<&str, String>,
+        to: Vec<String>,
+        sender: MailSender,
+    ) -> Result<Self> {
+        let rendered = template::render(name, vars)?;
+        Ok(Self {
+            to,
+            subject: rendered.subject,
+            html_body: rendered.html_body,
+            sender,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct MailJob {
+    message: MailMessage,
+    attempts: u32,
+}
+
+/// 启动后台发送任务，未启用时提交的邮件会被忽略
+pub fn init(config: &MailConfig, db: &'static DatabaseConnection) {
+    if !config.enabled {
+        tracing::info!("mail disabled");
+        return;
+    }
+    let (tx, rx) = mpsc::channel::<MailJob>(config.queue_capacity);
+    if QUEUE.set(tx).is_err() {
+        return;
+    }
+    let handle = tokio::spawn(run_worker(config.clone(), db, rx));
+    *WORKER.lock().expect("mail worker lock poisoned") = Some(handle);
+    tracing::info!(transport = %config.transport, "mail worker started");
+}
+
+/// 提交邮件，不等待发送结果
+pub fn enqueue(message: MailMessage) -> Result<()> {
+    let Some(queue) = QUEUE.get() else {
+        tracing::debug!(subject = %message.subject, "mail disabled, message dropped");
+        return Ok(());
+    };
+    if message.to.is_empty() {
+        return Err(anyhow!("mail has no recipient"));
+    }
+    queue
+        .try_send(MailJob { message, attempts: 0 })
+        .map_err(|e| anyhow!("mail queue unavailable: {}", e))
+}
+
+/// 停止发送任务，队列中剩余与等待重试的邮件各尝试发送一次
+pub async fn shutdown(timeout: Duration) {
+    let _ = SHUTDOWN.send(true);
+    let handle = WORKER.lock().expect("mail worker lock poisoned").take();
+    if let Some(handle) = handle
+        && tokio::time::timeout(timeout, handle).await.is_err()
+    {
+        tracing::warn!("mail worker did not stop within {:?}", timeout);
+    }
+}
+
+async fn run_worker(config: MailConfig, db: &'static DatabaseConnection, mut rx: mpsc::Receiver<MailJob>) {
+    let mut shutdown = SHUTDOWN.subscribe();
+    // 等待重试的邮件及其到期时间
+    let mut delayed: Vec<(Instant, MailJob)> = Vec::new();
+    loop {
+        let next_retry = delayed.iter().map(|(due, _)| *due).min();
+        tokio::select! {
+            _ = shutdown.changed() => break,
+            job = rx.recv() => match job {
+                Some(job) => delayed.extend(deliver(&config, db, job, true).await),
+                None => break,
+            },
+            _ = tokio::time::sleep_until(next_retry.unwrap_or_else(Instant::now)), if next_retry.is_some() => {
+                let now = Instant::now();
+                let (due, pending): (Vec<_>, Vec<_>) = delayed.into_iter().partition(|(at, _)| *at <= now);
+                delayed = pending;
+                for (_, job) in due {
+                    delayed.extend(deliver(&config, db, job, true).await);
+                }
+            }
+        }
+    }
+    while let Ok(job) = rx.try_recv() {
+        deliver(&config, db, job, false).await;
+    }
+    for (_, job) in delayed {
+        deliver(&config, db, job, false).await;
+    }
+    tracing::info!("mail worker stopped");
+}
+
+/// 发送邮件，需要重试时返回重试时间与邮件
+async fn deliver(
+    config: &MailConfig,
+    db: &DatabaseConnection,
+    mut job: MailJob,
+    retry: bool,
+) -> Option<(Instant, MailJob)> {
+    job.attempts += 1;
+    let Err(e) = transport::send(config, &job.message, db).await else {
+        tracing::info!(to = ?job.message.to, subject = %job.message.subject, "mail sent");
+        return None;
+    };
+    if !retry || job.attempts >= config.max_attempts {
+        tracing::error!(
+            to = ?job.message.to,
+            subject = %job.message.subject,
+            attempts = job.attempts,
+            "mail send failed, giving up: {}",
+            e
+        );
+        return None;
+    }
+
+    let backoff = Duration::from_secs(config.retry_backoff.saturating_mul(1 << (job.attempts - 1).min(10)));
+    tracing::warn!(
+        to = ?job.message.to,
+        attempts = job.attempts,
+        "mail send failed, retry in {:?}: {}",
+        backoff,
+        e
+    );
+    Some((Instant::now() + backoff, job))
+}
@@ -0,0 +1,122 @@
+//! 邮件模板
+//!
+//! 模板位于 `templates/mail/{name}.html` 并编译进二进制，首行为 `Subject: 标题`，
+//! 空行之后为 HTML 正文。`{{key}}` 占位符会被替换，正文中的值做 HTML 转义；
+//! 替换只扫描一遍模板，值中的 `{{...}}` 不会再被展开。
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use rust_embed::RustEmbed;
+
+const SUBJECT_PREFIX: &str = "Subject:";
+
+#[derive(RustEmbed)]
+#[folder = "templates/mail/"]
+struct MailTemplates;
+
+/// 渲染后的邮件标题与正文
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedMail {
+    pub subject: String,
+    pub html_body: String,
+}
+
+/// 按名称渲染模板
+pub fn render(name: &str, vars: &HashMap<&str, String>) -> Result<RenderedMail> {
+    let file = MailTemplates::get(&format!("{}.html", name))
+        .ok_or_else(|| anyhow!("mail template not found: {}", name))?;
+    let content = std::str::from_utf8(&file.data)?;
+    render_str(content, vars)
+}
+
+fn render_str(content: &str, vars: &HashMap<&str, String>) -> Result<RenderedMail> {
+    let (first_line, body) = content.split_once('\n').unwrap_or((content, ""));
+    let subject = first_line
+        .trim()
+        .strip_prefix(SUBJECT_PREFIX)
+        .ok_or_else(|| anyhow!("mail template must start with `{}`", SUBJECT_PREFIX))?
+        .trim();
+
+    Ok(RenderedMail {
+        subject: substitute(subject, vars, |v| v.to_string()),
+        html_body: substitute(body.trim_start(), vars, escape_html),
+    })
+}
+
+/// 一次扫描替换 `{{key}}` 占位符，未知的占位符原样保留
+fn substitute(template: &str, vars: &HashMap<&str, String>, encode: fn(&str) -> String) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        match vars.get(&after[..end]) {
+            Some(value) => output.push_str(&encode(value)),
+            None => output.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_render_str_escapes_body() {
+        let vars = HashMap::from([("name", "<张三>".to_string())]);
+        let rendered = render_str("Subject: 你好 {{name}}\n\n<p>{{name}}</p>\n", &vars).unwrap();
+
+        assert_eq!(rendered.subject, "你好 <张三>");
+        assert_eq!(rendered.html_body, "<p>&lt;张三&gt;</p>\n");
+    }
+
+    #[test]
+    fn test_render_str_does_not_expand_values() {
+        let vars = HashMap::from([
+            ("name", "{{code}}".to_string()),
+            ("code", "123456".to_string()),
+        ]);
+        let rendered = render_str("Subject: {{name}} {{unknown}}\n\n<p>{{name}}{{code}}</p>", &vars).unwrap();
+
+        assert_eq!(rendered.subject, "{{code}} {{unknown}}");
+        assert_eq!(rendered.html_body, "<p>{{code}}123456</p>");
+    }
+
+    #[test]
+    fn test_render_builtin_template() {
+        let vars = HashMap::from([
+            ("user_id", "10001".to_string()),
+            ("user_name", "张三".to_string()),
+        ]);
+        let rendered = render("welcome", &vars).unwrap();
+
+        assert!(rendered.subject.contains("张三"));
+        assert!(rendered.html_body.contains("10001"));
+        assert!(render_str("<p>no subject</p>", &vars).is_err());
+    }
+}
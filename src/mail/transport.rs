@@ -0,0 +1,111 @@
+//! 邮件发送通道
+//!
+//! 系统发件使用配置中的账号并复用连接池；代发邮件使用用户保存的邮箱与邮箱密码，
+//! 每次发送单独建立连接。file 方式将邮件以 `.eml` 写入本地目录。
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sea_orm::DatabaseConnection;
+
+use crate::config::{MailConfig, MAIL_TRANSPORT_FILE, SMTP_TLS_NONE, SMTP_TLS_STARTTLS};
+use crate::repository::permission::user_repository;
+use crate::utils::crypto_util;
+
+use super::{MailMessage, MailSender};
+
+static SYSTEM_TRANSPORT: OnceLock<AsyncSmtpTransport<Tokio1Executor>> = OnceLock::new();
+
+pub async fn send(config: &MailConfig, message: &MailMessage, db: &DatabaseConnection) -> Result<()> {
+    let (from, credentials) = match &message.sender {
+        MailSender::System => {
+            let credentials = config
+                .username
+                .clone()
+                .zip(config.password.clone())
+                .map(|(username, password)| Credentials::new(username, password));
+            (config.from.clone(), credentials)
+        }
+        MailSender::User(user_id) => {
+            let (email, credentials) = user_credentials(user_id, db).await?;
+            (email, Some(credentials))
+        }
+    };
+    let email = build_message(&from, message)?;
+
+    if config.transport == MAIL_TRANSPORT_FILE {
+        tokio::fs::create_dir_all(&config.file_dir).await?;
+        AsyncFileTransport::<Tokio1Executor>::new(&config.file_dir)
+            .send(email)
+            .await?;
+        return Ok(());
+    }
+
+    match &message.sender {
+        MailSender::System => {
+            let transport = match SYSTEM_TRANSPORT.get() {
+                Some(transport) => transport,
+                None => {
+                    let transport = smtp_transport(config, credentials)?;
+                    SYSTEM_TRANSPORT.get_or_init(|| transport)
+                }
+            };
+            transport.send(email).await?;
+        }
+        MailSender::User(_) => {
+            smtp_transport(config, credentials)?.send(email).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 读取用户的邮箱与解密后的邮箱密码
+async fn user_credentials(user_id: &str, db: &DatabaseConnection) -> Result<(String, Credentials)> {
+    let user = user_repository::query_user_by_user_id(user_id, db)
+        .await
+        .map_err(|e| anyhow!("query user {} failed: {:?}", user_id, e))?
+        .ok_or_else(|| anyhow!("user {} not found", user_id))?;
+    let email = user
+        .email
+        .filter(|e| !e.is_empty())
+        .ok_or_else(|| anyhow!("user {} has no email", user_id))?;
+    let password = user
+        .email_password
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| anyhow!("user {} has no email password", user_id))?;
+    let password = crypto_util::decrypt(&password)?;
+    Ok((email.clone(), Credentials::new(email, password)))
+}
+
+fn smtp_transport(
+    config: &MailConfig,
+    credentials: Option<Credentials>,
+) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+    let builder = match config.tls.as_str() {
+        SMTP_TLS_STARTTLS => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?,
+        SMTP_TLS_NONE => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        _ => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+    };
+    let mut builder = builder
+        .port(config.port)
+        .timeout(Some(Duration::from_secs(config.timeout)));
+    if let Some(credentials) = credentials {
+        builder = builder.credentials(credentials);
+    }
+    Ok(builder.build())
+}
+
+fn build_message(from: &str, message: &MailMessage) -> Result<Message> {
+    let mut builder = Message::builder()
+        .from(from.parse()?)
+        .subject(&message.subject)
+        .header(ContentType::TEXT_HTML);
+    for to in &message.to {
+        builder = builder.to(to.parse()?);
+    }
+    Ok(builder.body(message.html_body.clone())?)
+}
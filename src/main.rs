@@ -6,10 +6,15 @@ use tokio::signal;
 use tracing::info;
 
 mod app;
+mod assets;
 mod cache;
+mod cli;
 mod config;
 mod db;
 mod hoops;
+mod kafka;
+mod mail;
+mod metrics;
 mod models;
 mod entities;
 mod routers;
@@ -17,15 +22,38 @@ mod utils;
 mod handlers;
 mod repository;
 mod services;
+mod storage;
+mod telemetry;
 pub use common::error::AppError;
 mod common;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // 子命令执行完直接退出，不启动服务
+    let command = cli::parse(std::env::args().skip(1))?;
+    if command != cli::Command::Serve {
+        return cli::run(command).await;
+    }
+
+    config::init();
+    // 日志需在其他组件之前安装，guard 持有到进程退出以便写完缓冲的日志
+    let _log_guard = config::get().log.guard()?;
     let state = app::AppState::bootstrap().await?;
     let state = app::set_app_state(state);
+    utils::crypto_util::init(state.config.crypto.as_ref())?;
+    kafka::init(state.config.kafka.as_ref())?;
+    mail::init(&state.config.mail, db::postgres::pool());
+    storage::init(&state.config.storage)?;
+    services::audit_service::AuditService::init_writer(db::postgres::pool());
+    services::outbox_service::OutboxService::spawn_relay(state.config.outbox.clone(), db::postgres::pool());
     let service = app::build_service(state.clone());
+    watch_config()?;
+    start_metrics_server(&state.config.metrics).await?;
     start_server(state.config, service).await;
+    // 服务已停止接收请求，写完剩余的审计日志
+    services::audit_service::AuditService::shutdown(std::time::Duration::from_secs(10)).await;
+    // 导出剩余的 span
+    let _ = tokio::task::spawn_blocking(telemetry::shutdown).await;
     Ok(())
 }
 
@@ -42,6 +70,7 @@ async fn start_server(config: &'static config::ServerConfig, service: Service) {
         let tls_config =
             RustlsConfig::new(Keycert::new().cert(tls.cert.clone()).key(tls.key.clone()));
         let acceptor = TcpListener::new(listen_addr).rustls(tls_config).bind().await;
+        services::health_service::HealthService::mark_started();
         let server = Server::new(acceptor);
         tokio::spawn(shutdown_signal(server.handle()));
         server.serve(service).await;
@@ -51,12 +80,40 @@ async fn start_server(config: &'static config::ServerConfig, service: Service) {
             config.listen_addr.replace("0.0.0.0", "127.0.0.1")
         );
         let acceptor = TcpListener::new(&config.listen_addr).bind().await;
+        services::health_service::HealthService::mark_started();
         let server = Server::new(acceptor);
         tokio::spawn(shutdown_signal(server.handle()));
         server.serve(service).await;
     }
 }
 
+/// 配置文件变化或收到 SIGHUP 时重新加载配置，并把变化应用到日志级别与跨域；
+/// 限流与访问日志每次请求读取最新配置
+fn watch_config() -> Result<()> {
+    config::on_reload(|config| {
+        if let Err(e) = services::log_service::LogService::apply_config(&config.log) {
+            tracing::error!("apply log filter error: {}", e);
+        }
+    });
+    config::on_reload(|config| hoops::reload_cors(&config.cors));
+    config::spawn_watcher()
+}
+
+/// 配置了单独的指标监听地址时，在该地址上提供 `/metrics`，不经过主服务的中间件
+async fn start_metrics_server(config: &'static config::MetricsConfig) -> Result<()> {
+    let Some(listen_addr) = config.listen_addr.as_deref().filter(|_| config.enabled) else {
+        return Ok(());
+    };
+    tracing::info!("Starting metrics server at {}", listen_addr);
+    let acceptor = TcpListener::new(listen_addr)
+        .try_bind()
+        .await
+        .map_err(|e| anyhow::anyhow!("指标服务监听 {} 失败: {}", listen_addr, e))?;
+    let router = routers::metrics_router::metrics_router();
+    tokio::spawn(Server::new(acceptor).serve(router));
+    Ok(())
+}
+
 async fn shutdown_signal(handle: ServerHandle) {
     let ctrl_c = async {
         signal::ctrl_c()
@@ -79,6 +136,17 @@ async fn shutdown_signal(handle: ServerHandle) {
         _ = ctrl_c => info!("ctrl_c signal received"),
         _ = terminate => info!("terminate signal received"),
     }
+    // 就绪检查先返回失败，等待负载均衡摘除实例后再停止
+    services::health_service::HealthService::start_draining();
+    let drain_delay = config::get().health.drain_delay;
+    if drain_delay > 0 {
+        info!("draining for {}s before shutdown", drain_delay);
+        tokio::time::sleep(std::time::Duration::from_secs(drain_delay)).await;
+    }
+    // 先停止消费者，保证正在处理的消息完成后再关闭服务
+    kafka::consumer::shutdown(std::time::Duration::from_secs(30)).await;
+    services::outbox_service::OutboxService::shutdown(std::time::Duration::from_secs(10)).await;
+    mail::shutdown(std::time::Duration::from_secs(10)).await;
     handle.stop_graceful(std::time::Duration::from_secs(60));
 }
 
@@ -91,7 +159,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_openapi_docs() {
-        config::init();
+        config::init_test();
 
         let service = Service::new(crate::routers::root());
 
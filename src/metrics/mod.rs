@@ -0,0 +1,203 @@
+//! Prometheus 指标
+//!
+//! 指标注册在进程内的 [`REGISTRY`]，由 `/metrics` 以文本格式导出。连接池等状态类指标
+//! 在导出时读取，请求、SQL 与 Redis 命令耗时在执行时记录。
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use anyhow::Result;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramTimer, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use sea_orm::metric::Info;
+
+use crate::cache::redis_manager;
+use crate::db;
+
+pub static REGISTRY: LazyLock<Registry> = LazyLock::new(|| {
+    let registry = Registry::new();
+    #[cfg(target_os = "linux")]
+    registry
+        .register(Box::new(prometheus::process_collector::ProcessCollector::for_self()))
+        .expect("register process collector");
+    registry
+});
+
+static HTTP_REQUESTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new("http_requests_total", "HTTP 请求数"),
+        &["method", "route", "status"],
+    ))
+});
+
+static HTTP_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register(HistogramVec::new(
+        HistogramOpts::new("http_request_duration_seconds", "HTTP 请求耗时"),
+        &["method", "route"],
+    ))
+});
+
+static DB_QUERY_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register(HistogramVec::new(
+        HistogramOpts::new("db_query_duration_seconds", "SQL 执行耗时")
+            .buckets(vec![0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0]),
+        &["operation", "status"],
+    ))
+});
+
+static DB_POOL_CONNECTIONS: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register(IntGaugeVec::new(
+        Opts::new("db_pool_connections", "数据库连接池连接数"),
+        &["state"],
+    ))
+});
+
+static REDIS_COMMAND_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register(HistogramVec::new(
+        HistogramOpts::new("redis_command_duration_seconds", "Redis 命令耗时")
+            .buckets(vec![0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+        &["command"],
+    ))
+});
+
+static REDIS_POOL_CONNECTIONS: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register(IntGaugeVec::new(
+        Opts::new("redis_pool_connections", "Redis 连接池连接数"),
+        &["state"],
+    ))
+});
+
+static REDIS_POOL_WAITING: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new("redis_pool_waiting", "等待 Redis 连接的任务数"))
+});
+
+static REDIS_POOL_WAIT: LazyLock<Histogram> = LazyLock::new(|| {
+    register(Histogram::with_opts(
+        HistogramOpts::new("redis_pool_wait_seconds", "获取 Redis 连接的等待时间")
+            .buckets(vec![0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0]),
+    ))
+});
+
+static LOGINS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new("auth_logins_total", "登录次数"),
+        &["method", "result"],
+    ))
+});
+
+fn register<T: prometheus::core::Collector + Clone + 'static>(metric: prometheus::Result<T>) -> T {
+    let metric = metric.expect("valid metric");
+    REGISTRY
+        .register(Box::new(metric.clone()))
+        .expect("metric registered once");
+    metric
+}
+
+/// 记录一次 HTTP 请求，`route` 为路由模板，避免路径参数导致标签基数膨胀
+pub fn observe_http(method: &str, route: &str, status: u16, elapsed: Duration) {
+    HTTP_REQUESTS
+        .with_label_values(&[method, route, &status.to_string()])
+        .inc();
+    HTTP_DURATION
+        .with_label_values(&[method, route])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// 记录一次 SQL 执行耗时，由数据库连接的回调调用
+pub fn observe_query(info: &Info<'_>) {
+    let operation = sql_operation(&info.statement.sql);
+    let status = if info.failed { "error" } else { "ok" };
+    DB_QUERY_DURATION
+        .with_label_values(&[operation, status])
+        .observe(info.elapsed.as_secs_f64());
+}
+
+/// 开始计时 Redis 命令，返回的计时器在释放时记录耗时
+pub fn redis_timer(command: &str) -> HistogramTimer {
+    REDIS_COMMAND_DURATION.with_label_values(&[command]).start_timer()
+}
+
+pub fn observe_redis_pool_wait(elapsed: Duration) {
+    REDIS_POOL_WAIT.observe(elapsed.as_secs_f64());
+}
+
+/// 记录登录结果，`method` 为 password | mfa | oidc
+pub fn record_login(method: &str, success: bool) {
+    LOGINS
+        .with_label_values(&[method, if success { "success" } else { "failure" }])
+        .inc();
+}
+
+/// 以 Prometheus 文本格式导出全部指标
+pub fn gather() -> Result<String> {
+    refresh_pool_gauges();
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&REGISTRY.gather(), &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+fn refresh_pool_gauges() {
+    if let Some(db) = db::postgres::SEAORM_POOL.get() {
+        let pool = db.get_postgres_connection_pool();
+        let idle = pool.num_idle() as i64;
+        let size = pool.size() as i64;
+        DB_POOL_CONNECTIONS.with_label_values(&["idle"]).set(idle);
+        DB_POOL_CONNECTIONS.with_label_values(&["active"]).set(size - idle);
+        DB_POOL_CONNECTIONS
+            .with_label_values(&["max"])
+            .set(pool.options().get_max_connections() as i64);
+    }
+    if let Some(pool) = redis_manager::try_pool() {
+        let status = pool.status();
+        REDIS_POOL_CONNECTIONS
+            .with_label_values(&["size"])
+            .set(status.size as i64);
+        REDIS_POOL_CONNECTIONS
+            .with_label_values(&["available"])
+            .set(status.available as i64);
+        REDIS_POOL_CONNECTIONS
+            .with_label_values(&["max"])
+            .set(status.max_size as i64);
+        REDIS_POOL_WAITING.set(status.waiting as i64);
+    }
+}
+
+/// 取 SQL 的首个关键字作为操作类型
+pub fn sql_operation(sql: &str) -> &'static str {
+    let keyword = sql
+        .trim_start()
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    match keyword.as_str() {
+        "SELECT" | "WITH" => "select",
+        "INSERT" => "insert",
+        "UPDATE" => "update",
+        "DELETE" => "delete",
+        "BEGIN" | "COMMIT" | "ROLLBACK" | "SAVEPOINT" | "RELEASE" => "transaction",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_operation() {
+        assert_eq!(sql_operation("SELECT 1"), "select");
+        assert_eq!(sql_operation("  insert INTO sys_user"), "insert");
+        assert_eq!(sql_operation("WITH t AS (SELECT 1) SELECT * FROM t"), "select");
+        assert_eq!(sql_operation("ALTER TABLE sys_user"), "other");
+    }
+
+    #[test]
+    fn test_gather_contains_registered_metrics() {
+        record_login("password", true);
+        let text = gather().unwrap();
+        assert!(text.contains("auth_logins_total{method=\"password\",result=\"success\"}"));
+    }
+}
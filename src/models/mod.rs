@@ -1,7 +1,9 @@
 pub mod permission;
+pub mod system;
 use salvo::oapi::ToSchema;
 use serde::Serialize;
 
+#[allow(dead_code)]
 #[derive(Serialize, ToSchema, Debug)]
 pub struct SafeUser {
     pub id: String,
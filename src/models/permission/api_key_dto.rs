@@ -0,0 +1,60 @@
+use chrono::NaiveDateTime;
+use salvo::oapi::ToSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::entities::permission::sys_api_key;
+
+#[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyReq {
+    /// 密钥名称
+    #[validate(length(min = 1, max = 64, message = "名称长度必须在1-64之间"))]
+    pub name: String,
+
+    /// 授权范围
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    /// 有效天数，为空表示永不过期
+    #[validate(range(min = 1, max = 3650, message = "有效天数必须在1-3650之间"))]
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyRes {
+    pub id: i64,
+    pub key_prefix: String,
+    pub name: String,
+    pub owner: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub revoked: bool,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+impl From<sys_api_key::Model> for ApiKeyRes {
+    fn from(key: sys_api_key::Model) -> Self {
+        Self {
+            scopes: key.scope_list(),
+            revoked: key.is_revoked(),
+            id: key.id,
+            key_prefix: key.key_prefix,
+            name: key.name,
+            owner: key.owner,
+            expires_at: key.expires_at,
+            created_at: key.created_at,
+            last_used_at: key.last_used_at,
+        }
+    }
+}
+
+/// 新建或轮换密钥的结果，完整密钥只返回这一次
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeySecretRes {
+    pub api_key: String,
+    pub info: ApiKeyRes,
+}
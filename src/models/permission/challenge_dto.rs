@@ -0,0 +1,21 @@
+use salvo::oapi::ToSchema;
+use serde::Serialize;
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeRes {
+    /// 验证id，提交登录或注册时一并传入
+    pub challenge_id: String,
+    /// 验证类型: captcha | pow
+    pub kind: String,
+    /// 图形验证码，`data:image/png;base64,...`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// 工作量证明前缀，需找到 `solution` 使 SHA-256(prefix + solution) 的前导零位数不少于 `difficulty`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<u32>,
+    /// 有效期（秒）
+    pub expires_in: usize,
+}
@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use salvo::oapi::ToSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::permission::rs_employee01;
+
+#[derive(Debug, ToSchema, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmployeeLookupReq {
+    /// 工号
+    pub emp_no: Option<String>,
+    /// 手机号
+    pub mobile: Option<String>,
+}
+
+/// 员工档案，证件号不返回，手机号脱敏
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmployeeRes {
+    pub emp_no: Option<String>,
+    pub emp_name: Option<String>,
+    pub dept_no: Option<String>,
+    pub dept_name: Option<String>,
+    pub duty_name: Option<String>,
+    pub post_name: Option<String>,
+    pub job_title: Option<String>,
+    pub gender: i32,
+    /// 脱敏后的手机号，如 138****0000
+    pub mobile: Option<String>,
+    pub entry_date: Option<NaiveDate>,
+    pub resign_date: Option<NaiveDate>,
+    pub active: bool,
+}
+
+impl EmployeeRes {
+    pub fn new(employee: rs_employee01::Model, mobile: Option<String>) -> Self {
+        Self {
+            active: employee.is_active(),
+            emp_no: employee.empno,
+            emp_name: employee.empname,
+            dept_no: employee.deptno,
+            dept_name: employee.deptname,
+            duty_name: employee.dutyname,
+            post_name: employee.postname,
+            job_title: employee.jobtitle,
+            gender: employee.gender,
+            mobile: mobile.as_deref().map(mask_mobile),
+            entry_date: employee.entrydate,
+            resign_date: employee.resigndate,
+        }
+    }
+}
+
+/// 保留前三位与后四位
+fn mask_mobile(mobile: &str) -> String {
+    let chars: Vec<char> = mobile.trim().chars().collect();
+    if chars.len() <= 7 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..3].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}{}{}", head, "*".repeat(chars.len() - 7), tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_mobile() {
+        assert_eq!(mask_mobile("13800001234"), "138****1234");
+        assert_eq!(mask_mobile("12345"), "*****");
+    }
+}
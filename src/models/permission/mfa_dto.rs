@@ -0,0 +1,39 @@
+use salvo::oapi::ToSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaEnrollRes {
+    /// TOTP 密钥（Base32），供无法扫码时手动输入
+    pub secret: String,
+    /// otpauth 地址，即二维码内容
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaCodeReq {
+    /// 验证器中的 6 位验证码
+    #[validate(length(equal = 6, message = "验证码必须为6位"))]
+    pub code: String,
+}
+
+#[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaVerifyReq {
+    /// 登录时返回的两步验证票据
+    #[validate(length(min = 1, message = "票据不能为空"))]
+    pub ticket: String,
+
+    /// 6 位验证码或恢复码
+    #[validate(length(min = 6, max = 32, message = "验证码格式不正确"))]
+    pub code: String,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaRecoveryCodesRes {
+    /// 恢复码，只在开启时返回一次，每个只能使用一次
+    pub recovery_codes: Vec<String>,
+}
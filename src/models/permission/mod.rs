@@ -1 +1,5 @@
-pub mod user_dto;
\ No newline at end of file
+pub mod api_key_dto;
+pub mod user_dto;
+pub mod mfa_dto;
+pub mod challenge_dto;
+pub mod employee_dto;
@@ -1,20 +1,64 @@
+use chrono::NaiveDateTime;
 use salvo::oapi::ToSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use crate::{entities::permission::sys_user, utils};
 
-#[derive(Debug, ToSchema, Clone, Deserialize)]
+#[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct UserPageReq {
-    pub cur_page: i32,
-    pub page_size: i32,
-    
+    #[validate(range(min = 1, message = "当前页必须大于0"))]
+    pub cur_page: u64,
+    #[validate(range(min = 1, max = 500, message = "每页条数必须在1-500之间"))]
+    pub page_size: u64,
+
+    /// 用户id前缀
     pub user_id: Option<String>,
+    /// 用户名称，模糊匹配
     pub user_name: Option<String>,
-    pub locked: Option<String>,
-    pub is_valid: Option<String>
+    /// 是否锁定,1为锁定
+    pub locked: Option<i32>,
+    /// 是否有效,0为有效
+    pub is_valid: Option<i32>,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRes {
+    pub user_id: String,
+    pub user_name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub remark: Option<String>,
+    pub image_url: Option<String>,
+    pub locked: i32,
+    pub is_valid: i32,
+    pub auth_source: Option<String>,
+    pub mfa_enabled: i32,
+    pub last_login: Option<NaiveDateTime>,
+    pub login_ip: Option<String>,
+    pub reg_time: Option<NaiveDateTime>,
+}
+
+impl From<sys_user::Model> for UserRes {
+    fn from(user: sys_user::Model) -> Self {
+        Self {
+            user_id: user.user_id,
+            user_name: user.user_name,
+            phone: user.phone,
+            email: user.email,
+            remark: user.remark,
+            image_url: user.image_url,
+            locked: user.locked,
+            is_valid: user.is_valid,
+            auth_source: user.auth_source,
+            mfa_enabled: user.mfa_enabled,
+            last_login: user.last_login,
+            login_ip: user.login_ip,
+            reg_time: user.reg_time,
+        }
+    }
 }
 
 #[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
@@ -26,14 +70,24 @@ pub struct LoginReq {
 
     /// 密码
     #[validate(length(min = 1, message = "密码不能为空"))]
-    pub password: String
+    pub password: String,
+
+    /// 人机验证id，连续登录失败后必填
+    pub challenge_id: Option<String>,
+
+    /// 人机验证答案：验证码文字或工作量证明的解
+    pub challenge_answer: Option<String>,
 }
 
 #[derive(Debug, ToSchema, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LogInRes {
     #[serde(rename = "Authorization")]
-    pub authorization: Vec<String>
+    pub authorization: Vec<String>,
+
+    /// 开启两步验证时返回票据，需调用验证接口换取令牌
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mfa_ticket: Option<String>,
 }
 
 #[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
@@ -50,6 +104,7 @@ pub struct RegisterReq {
 }
 
 impl RegisterReq {
+    #[allow(dead_code)]
     pub fn into_model(self) -> sys_user::Model {
         sys_user::Model {
             user_id: self.user_id,
@@ -97,4 +152,31 @@ pub struct CreateReq {
 
     /// 是否有效
     pub is_valid: Option<i16>,
-}
\ No newline at end of file
+
+    /// 人机验证id，按配置注册时必填
+    pub challenge_id: Option<String>,
+
+    /// 人机验证答案：验证码文字或工作量证明的解
+    pub challenge_answer: Option<String>,
+}
+
+/// 用户创建事件，经发件箱发布到 `user_events`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserCreatedEvent {
+    pub user_id: String,
+    pub user_name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+}
+
+impl From<&sys_user::Model> for UserCreatedEvent {
+    fn from(user: &sys_user::Model) -> Self {
+        Self {
+            user_id: user.user_id.clone(),
+            user_name: user.user_name.clone(),
+            phone: user.phone.clone(),
+            email: user.email.clone(),
+        }
+    }
+}
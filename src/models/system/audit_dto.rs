@@ -0,0 +1,67 @@
+use chrono::NaiveDateTime;
+use salvo::oapi::ToSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::entities::system::sys_audit_log;
+
+#[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditPageReq {
+    #[validate(range(min = 1, message = "当前页必须大于0"))]
+    pub cur_page: u64,
+    #[validate(range(min = 1, max = 500, message = "每页条数必须在1-500之间"))]
+    pub page_size: u64,
+
+    /// 操作人
+    pub actor: Option<String>,
+    /// 链路ID
+    pub request_id: Option<String>,
+    pub method: Option<String>,
+    /// 路由前缀
+    pub route: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    /// success | failure
+    pub outcome: Option<String>,
+    pub start_time: Option<NaiveDateTime>,
+    pub end_time: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogRes {
+    pub id: i64,
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub client_ip: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub changes: Option<serde_json::Value>,
+    pub outcome: String,
+    pub status_code: i32,
+    pub latency_ms: i64,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<sys_audit_log::Model> for AuditLogRes {
+    fn from(log: sys_audit_log::Model) -> Self {
+        Self {
+            id: log.id,
+            actor: log.actor,
+            request_id: log.request_id,
+            method: log.method,
+            route: log.route,
+            client_ip: log.client_ip,
+            target_type: log.target_type,
+            target_id: log.target_id,
+            changes: log.changes,
+            outcome: log.outcome,
+            status_code: log.status_code,
+            latency_ms: log.latency_ms,
+            created_at: log.created_at,
+        }
+    }
+}
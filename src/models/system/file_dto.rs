@@ -0,0 +1,29 @@
+use chrono::NaiveDateTime;
+use salvo::oapi::ToSchema;
+use serde::Serialize;
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRes {
+    pub id: i64,
+    pub file_name: Option<String>,
+    pub content_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// 原文件下载地址，带有效期
+    pub url: String,
+    pub thumbnails: Vec<ThumbnailRes>,
+    /// 下载地址有效期（秒）
+    pub expires_in: u64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailRes {
+    /// 缩略图最长边（像素）
+    pub size: u32,
+    pub url: String,
+}
+
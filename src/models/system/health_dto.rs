@@ -0,0 +1,34 @@
+use salvo::oapi::ToSchema;
+use serde::Serialize;
+
+pub const STATUS_UP: &str = "up";
+/// 非关键依赖检查失败，服务仍可接收流量
+pub const STATUS_DEGRADED: &str = "degraded";
+pub const STATUS_DOWN: &str = "down";
+/// 收到停止信号，正在摘除流量
+pub const STATUS_DRAINING: &str = "draining";
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// up | degraded | down | draining
+    pub status: String,
+    /// 进程启动以来的秒数
+    pub uptime_secs: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<HealthCheckRes>,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckRes {
+    /// 依赖名称: postgres | redis | broker
+    pub name: String,
+    /// up | down
+    pub status: String,
+    pub critical: bool,
+    pub latency_ms: u64,
+    /// 失败原因: timeout | unavailable，详细错误见服务日志
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+use salvo::oapi::ToSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevelReq {
+    /// 过滤规则，格式同 RUST_LOG，如 `info,sea_orm=debug,base_web::services=trace`
+    #[validate(length(min = 1, max = 1024, message = "过滤规则长度必须在1-1024之间"))]
+    pub directives: String,
+    /// 到期自动恢复为配置规则（秒），为空时一直生效
+    #[validate(range(min = 1, max = 86400, message = "自动恢复时间必须在1-86400秒之间"))]
+    pub revert_after: Option<u64>,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevelRes {
+    /// 当前生效的规则
+    pub directives: String,
+    /// 配置文件给出的规则
+    pub configured: String,
+    /// 临时规则自动恢复的时间
+    pub revert_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,5 @@
+pub mod audit_dto;
+pub mod outbox_dto;
+pub mod file_dto;
+pub mod health_dto;
+pub mod log_dto;
@@ -0,0 +1,47 @@
+use chrono::NaiveDateTime;
+use salvo::oapi::ToSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::entities::system::sys_outbox_event;
+
+#[derive(Debug, ToSchema, Clone, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxPageReq {
+    #[validate(range(min = 1, message = "当前页必须大于0"))]
+    pub cur_page: u64,
+    #[validate(range(min = 1, max = 500, message = "每页条数必须在1-500之间"))]
+    pub page_size: u64,
+}
+
+#[derive(Debug, ToSchema, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxEventRes {
+    pub id: i64,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub topic: String,
+    pub status: i16,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub next_attempt_at: NaiveDateTime,
+}
+
+impl From<sys_outbox_event::Model> for OutboxEventRes {
+    fn from(event: sys_outbox_event::Model) -> Self {
+        Self {
+            id: event.id,
+            aggregate_type: event.aggregate_type,
+            aggregate_id: event.aggregate_id,
+            event_type: event.event_type,
+            topic: event.topic,
+            status: event.status,
+            attempts: event.attempts,
+            last_error: event.last_error,
+            created_at: event.created_at,
+            next_attempt_at: event.next_attempt_at,
+        }
+    }
+}
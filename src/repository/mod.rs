@@ -1 +1,7 @@
-pub mod permission;
\ No newline at end of file
+//! 数据访问层
+//!
+//! 仓储函数的连接由服务层传入：可以接受复制延迟的只读查询使用 [`crate::db::postgres::read`] 分发到只读副本，
+//! 写操作、事务及写入后需要立即读到结果的查询使用 [`crate::db::postgres::write`]。
+
+pub mod permission;
+pub mod system;
@@ -0,0 +1,93 @@
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+
+use crate::{
+    common::api_response::AppResult,
+    entities::{permission::sys_api_key, prelude::SysApiKey},
+    utils::error_util,
+};
+
+pub async fn insert_api_key<C: ConnectionTrait>(
+    api_key: sys_api_key::ActiveModel,
+    db: &C,
+) -> AppResult<sys_api_key::Model> {
+    SysApiKey::insert(api_key)
+        .exec_with_returning(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("insert_api_key error: {}", e);
+            error_util::system_error()
+        })
+}
+
+pub async fn query_api_key_by_prefix(
+    key_prefix: &str,
+    db: &DatabaseConnection,
+) -> AppResult<Option<sys_api_key::Model>> {
+    SysApiKey::find()
+        .filter(sys_api_key::Column::KeyPrefix.eq(key_prefix))
+        .one(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_api_key_by_prefix error: {}", e);
+            error_util::system_error()
+        })
+}
+
+pub async fn query_api_key_by_id(
+    id: i64,
+    db: &DatabaseConnection,
+) -> AppResult<Option<sys_api_key::Model>> {
+    SysApiKey::find_by_id(id).one(db).await.map_err(|e| {
+        tracing::error!("query_api_key_by_id error: {}", e);
+        error_util::system_error()
+    })
+}
+
+pub async fn query_api_keys_by_owner(
+    owner: &str,
+    db: &DatabaseConnection,
+) -> AppResult<Vec<sys_api_key::Model>> {
+    SysApiKey::find()
+        .filter(sys_api_key::Column::Owner.eq(owner))
+        .order_by_desc(sys_api_key::Column::Id)
+        .all(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_api_keys_by_owner error: {}", e);
+            error_util::system_error()
+        })
+}
+
+pub async fn revoke_api_key<C: ConnectionTrait>(id: i64, db: &C) -> AppResult<()> {
+    SysApiKey::update(sys_api_key::ActiveModel {
+        id: Set(id),
+        revoked: Set(1),
+        ..Default::default()
+    })
+    .exec(db)
+    .await
+    .map_err(|e| {
+        tracing::error!("revoke_api_key error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
+
+pub async fn touch_last_used(id: i64, now: NaiveDateTime, db: &DatabaseConnection) -> AppResult<()> {
+    SysApiKey::update(sys_api_key::ActiveModel {
+        id: Set(id),
+        last_used_at: Set(Some(now)),
+        ..Default::default()
+    })
+    .exec(db)
+    .await
+    .map_err(|e| {
+        tracing::error!("touch_last_used error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
@@ -1,6 +1,9 @@
-use crate::{entities::permission::rs_employee01, utils::error_util};
+use crate::{entities::permission::rs_employee01, utils::{crypto_util, error_util}};
 use crate::entities::prelude::RsEmployee1;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
 use crate::common::api_response::AppResult;
 
 pub async fn query_employee_by_emp_no(
@@ -13,7 +16,8 @@ pub async fn query_employee_by_emp_no(
         .await
     {
         Ok(Some(employee)) => {
-            tracing::info!("Successfully found employee with emp_no {}: {:?}", emp_no, employee);
+            // 员工档案包含证件号等敏感信息，只记录主键
+            tracing::info!("Successfully found employee with emp_no {}: empid {}", emp_no, employee.empid);
             Ok(Some(employee))
         },
         Ok(None) => {
@@ -25,4 +29,56 @@ pub async fn query_employee_by_emp_no(
             Err(error_util::system_error())
         }
     }
-}
\ No newline at end of file
+}
+
+/// 按手机号查询员工，通过盲索引匹配密文，同时兼容尚未加密的存量数据
+pub async fn query_employee_by_mobile(
+    mobile: &str,
+    db: &DatabaseConnection,
+) -> AppResult<Option<rs_employee01::Model>> {
+    let index = crypto_util::blind_index(mobile).map_err(|e| {
+        tracing::error!("query_employee_by_mobile blind index error: {}", e);
+        error_util::system_error()
+    })?;
+    RsEmployee1::find()
+        .filter(
+            Condition::any()
+                .add(rs_employee01::Column::MobilenoBidx.eq(index))
+                .add(rs_employee01::Column::Mobileno.eq(mobile.trim())),
+        )
+        .one(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_employee_by_mobile error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 按主键顺序分批查询员工
+pub async fn query_employees_after(
+    after_empid: i64,
+    limit: u64,
+    db: &DatabaseConnection,
+) -> AppResult<Vec<rs_employee01::Model>> {
+    RsEmployee1::find()
+        .filter(rs_employee01::Column::Empid.gt(after_empid))
+        .order_by_asc(rs_employee01::Column::Empid)
+        .limit(limit)
+        .all(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_employees_after error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 通过 ActiveModel 更新以触发敏感字段加密
+pub async fn update_employee(
+    employee: rs_employee01::ActiveModel,
+    db: &DatabaseConnection,
+) -> AppResult<rs_employee01::Model> {
+    employee.update(db).await.map_err(|e| {
+        tracing::error!("update_employee error: {}", e);
+        error_util::system_error()
+    })
+}
@@ -0,0 +1,47 @@
+use chrono::Local;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+
+use crate::{
+    common::api_response::AppResult,
+    entities::{permission::sys_user_identity, prelude::SysUserIdentity},
+    utils::error_util,
+};
+
+/// 按签发方与 subject 查询已绑定的外部身份
+pub async fn query_identity<C: ConnectionTrait>(
+    issuer: &str,
+    subject: &str,
+    db: &C,
+) -> AppResult<Option<sys_user_identity::Model>> {
+    SysUserIdentity::find()
+        .filter(sys_user_identity::Column::Issuer.eq(issuer))
+        .filter(sys_user_identity::Column::Subject.eq(subject))
+        .one(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_identity error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 绑定外部身份到本地用户
+pub async fn insert_identity<C: ConnectionTrait>(
+    issuer: &str,
+    subject: &str,
+    user_id: &str,
+    db: &C,
+) -> AppResult<sys_user_identity::Model> {
+    sys_user_identity::ActiveModel {
+        issuer: Set(issuer.to_string()),
+        subject: Set(subject.to_string()),
+        user_id: Set(user_id.to_string()),
+        created_at: Set(Local::now().naive_local()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| {
+        tracing::error!("insert_identity error: {}", e);
+        error_util::system_error()
+    })
+}
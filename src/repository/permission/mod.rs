@@ -1,2 +1,5 @@
 pub mod user_repository;
-pub mod employee_repository;
\ No newline at end of file
+pub mod employee_repository;
+pub mod api_key_repository;
+pub mod role_repository;
+pub mod identity_repository;
@@ -0,0 +1,101 @@
+use chrono::Local;
+use sea_orm::{ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect};
+
+use crate::{
+    common::api_response::AppResult,
+    entities::{
+        permission::{sys_role, sys_user_role},
+        prelude::{SysRole, SysUserRole},
+    },
+    utils::error_util,
+};
+
+/// 过滤出已存在的角色编码
+pub async fn query_existing_role_codes<C: ConnectionTrait>(
+    role_codes: &[String],
+    db: &C,
+) -> AppResult<Vec<String>> {
+    if role_codes.is_empty() {
+        return Ok(Vec::new());
+    }
+    SysRole::find()
+        .select_only()
+        .column(sys_role::Column::RoleCode)
+        .filter(sys_role::Column::RoleCode.is_in(role_codes.iter().cloned()))
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_existing_role_codes error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 查询用户拥有的全部角色编码
+pub async fn query_user_role_codes<C: ConnectionTrait>(user_id: &str, db: &C) -> AppResult<Vec<String>> {
+    SysUserRole::find()
+        .select_only()
+        .column(sys_user_role::Column::RoleCode)
+        .filter(sys_user_role::Column::UserId.eq(user_id))
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_user_role_codes error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 删除用户指定来源的角色
+pub async fn delete_user_roles_by_source<C: ConnectionTrait>(
+    user_id: &str,
+    source: &str,
+    db: &C,
+) -> AppResult<u64> {
+    SysUserRole::delete_many()
+        .filter(sys_user_role::Column::UserId.eq(user_id))
+        .filter(sys_user_role::Column::Source.eq(source))
+        .exec(db)
+        .await
+        .map(|res| res.rows_affected)
+        .map_err(|e| {
+            tracing::error!("delete_user_roles_by_source error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 为用户添加角色，已拥有的角色保持不变
+pub async fn insert_user_roles<C: ConnectionTrait>(
+    user_id: &str,
+    role_codes: &[String],
+    source: &str,
+    db: &C,
+) -> AppResult<()> {
+    if role_codes.is_empty() {
+        return Ok(());
+    }
+    let now = Local::now().naive_local();
+    let models = role_codes.iter().map(|role_code| sys_user_role::ActiveModel {
+        user_id: Set(user_id.to_string()),
+        role_code: Set(role_code.clone()),
+        source: Set(source.to_string()),
+        created_at: Set(now),
+    });
+    SysUserRole::insert_many(models)
+        .on_conflict(
+            sea_orm::sea_query::OnConflict::columns([
+                sys_user_role::Column::UserId,
+                sys_user_role::Column::RoleCode,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec(db)
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            tracing::error!("insert_user_roles error: {}", e);
+            error_util::system_error()
+        })
+}
@@ -1,10 +1,13 @@
 use salvo::http::StatusError;
-use sea_orm::{ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+};
 
 use crate::{
     common::api_response::AppResult,
-    entities::{permission::sys_user, prelude::SysUser},
-    models::permission::user_dto::CreateReq, utils::error_util,
+    entities::{encryption, permission::sys_user, prelude::SysUser},
+    models::permission::user_dto::{CreateReq, UserPageReq}, utils::error_util,
 };
 
 pub async fn query_user_by_user_id(
@@ -21,7 +24,7 @@ pub async fn query_user_by_user_id(
         })
 }
 
-pub async fn create_user(data: CreateReq, db: &DatabaseConnection) -> AppResult<sys_user::Model> {
+pub async fn create_user<C: ConnectionTrait>(data: CreateReq, db: &C) -> AppResult<sys_user::Model> {
     let insert_data = sys_user::ActiveModel {
         user_id: Set(data.user_id),
         user_name: Set(data.user_name),
@@ -33,8 +36,9 @@ pub async fn create_user(data: CreateReq, db: &DatabaseConnection) -> AppResult<
         ..Default::default()
     };
 
-    let res = SysUser::insert(insert_data)
-        .exec_with_returning(db)
+    // 通过 ActiveModel 写入以触发敏感字段加密
+    let res = insert_data
+        .insert(db)
         .await
         .map_err(|e| {
             let error_msg = e.to_string();
@@ -51,3 +55,158 @@ pub async fn create_user(data: CreateReq, db: &DatabaseConnection) -> AppResult<
 
     Ok(res)
 }
+
+/// 更新两步验证状态，开启与重置时使用，同时清空已使用的时间步；密钥加密保存
+pub async fn update_user_mfa(
+    auto_id: i64,
+    enabled: i32,
+    secret: Option<String>,
+    recovery_codes: Option<String>,
+    db: &DatabaseConnection,
+) -> AppResult<()> {
+    let mut model = sys_user::ActiveModel {
+        auto_id: Set(auto_id),
+        mfa_enabled: Set(enabled),
+        mfa_secret: Set(secret),
+        mfa_recovery_codes: Set(recovery_codes),
+        mfa_last_step: Set(None),
+        ..Default::default()
+    };
+    encryption::seal(&mut model.mfa_secret, None).map_err(|_| error_util::system_error())?;
+    SysUser::update(model).exec(db).await.map_err(|e| {
+        tracing::error!("update_user_mfa error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
+
+pub async fn query_user_page(
+    req: &UserPageReq,
+    db: &DatabaseConnection,
+) -> AppResult<(u64, Vec<sys_user::Model>)> {
+    let mut condition = Condition::all();
+    if let Some(user_id) = req.user_id.as_deref().filter(|s| !s.is_empty()) {
+        condition = condition.add(sys_user::Column::UserId.starts_with(user_id));
+    }
+    if let Some(user_name) = req.user_name.as_deref().filter(|s| !s.is_empty()) {
+        condition = condition.add(sys_user::Column::UserName.contains(user_name));
+    }
+    if let Some(locked) = req.locked {
+        condition = condition.add(sys_user::Column::Locked.eq(locked));
+    }
+    if let Some(is_valid) = req.is_valid {
+        condition = condition.add(sys_user::Column::IsValid.eq(is_valid));
+    }
+
+    let paginator = SysUser::find()
+        .filter(condition)
+        .order_by_asc(sys_user::Column::AutoId)
+        .paginate(db, req.page_size);
+    let total = paginator.num_items().await.map_err(|e| {
+        tracing::error!("query_user_page error: {}", e);
+        error_util::system_error()
+    })?;
+    let records = paginator
+        .fetch_page(req.cur_page.saturating_sub(1))
+        .await
+        .map_err(|e| {
+            tracing::error!("query_user_page error: {}", e);
+            error_util::system_error()
+        })?;
+    Ok((total, records))
+}
+
+pub async fn update_user_locked(auto_id: i64, locked: i32, db: &DatabaseConnection) -> AppResult<()> {
+    SysUser::update(sys_user::ActiveModel {
+        auto_id: Set(auto_id),
+        locked: Set(locked),
+        ..Default::default()
+    })
+    .exec(db)
+    .await
+    .map_err(|e| {
+        tracing::error!("update_user_locked error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
+
+pub async fn update_user_image_url(auto_id: i64, image_url: Option<String>, db: &DatabaseConnection) -> AppResult<()> {
+    SysUser::update(sys_user::ActiveModel {
+        auto_id: Set(auto_id),
+        image_url: Set(image_url),
+        ..Default::default()
+    })
+    .exec(db)
+    .await
+    .map_err(|e| {
+        tracing::error!("update_user_image_url error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
+
+/// 记录通过校验的 TOTP 时间步，仅当大于上次记录时更新，返回是否更新成功
+pub async fn update_mfa_last_step(auto_id: i64, step: i64, db: &DatabaseConnection) -> AppResult<bool> {
+    SysUser::update_many()
+        .col_expr(sys_user::Column::MfaLastStep, Expr::value(step))
+        .filter(sys_user::Column::AutoId.eq(auto_id))
+        .filter(
+            Condition::any()
+                .add(sys_user::Column::MfaLastStep.is_null())
+                .add(sys_user::Column::MfaLastStep.lt(step)),
+        )
+        .exec(db)
+        .await
+        .map(|res| res.rows_affected == 1)
+        .map_err(|e| {
+            tracing::error!("update_mfa_last_step error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 替换恢复码，仅当恢复码未被并发修改时更新，返回是否更新成功
+pub async fn update_mfa_recovery_codes(
+    auto_id: i64,
+    expected: &str,
+    recovery_codes: String,
+    db: &DatabaseConnection,
+) -> AppResult<bool> {
+    SysUser::update_many()
+        .col_expr(sys_user::Column::MfaRecoveryCodes, Expr::value(recovery_codes))
+        .filter(sys_user::Column::AutoId.eq(auto_id))
+        .filter(sys_user::Column::MfaRecoveryCodes.eq(expected))
+        .exec(db)
+        .await
+        .map(|res| res.rows_affected == 1)
+        .map_err(|e| {
+            tracing::error!("update_mfa_recovery_codes error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 按主键顺序分批查询用户
+pub async fn query_users_after(
+    after_auto_id: i64,
+    limit: u64,
+    db: &DatabaseConnection,
+) -> AppResult<Vec<sys_user::Model>> {
+    SysUser::find()
+        .filter(sys_user::Column::AutoId.gt(after_auto_id))
+        .order_by_asc(sys_user::Column::AutoId)
+        .limit(limit)
+        .all(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_users_after error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 通过 ActiveModel 更新以触发敏感字段加密
+pub async fn update_user(user: sys_user::ActiveModel, db: &DatabaseConnection) -> AppResult<sys_user::Model> {
+    user.update(db).await.map_err(|e| {
+        tracing::error!("update_user error: {}", e);
+        error_util::system_error()
+    })
+}
@@ -0,0 +1,75 @@
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+use crate::{
+    common::api_response::AppResult,
+    entities::{prelude::SysAuditLog, system::sys_audit_log},
+    models::system::audit_dto::AuditPageReq,
+    utils::error_util,
+};
+
+pub async fn insert_logs(
+    logs: Vec<sys_audit_log::ActiveModel>,
+    db: &DatabaseConnection,
+) -> AppResult<()> {
+    if logs.is_empty() {
+        return Ok(());
+    }
+    SysAuditLog::insert_many(logs).exec(db).await.map_err(|e| {
+        tracing::error!("insert_logs error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
+
+pub async fn query_log_page(
+    req: &AuditPageReq,
+    db: &DatabaseConnection,
+) -> AppResult<(u64, Vec<sys_audit_log::Model>)> {
+    let mut condition = Condition::all();
+    if let Some(actor) = &req.actor {
+        condition = condition.add(sys_audit_log::Column::Actor.eq(actor));
+    }
+    if let Some(request_id) = &req.request_id {
+        condition = condition.add(sys_audit_log::Column::RequestId.eq(request_id));
+    }
+    if let Some(method) = &req.method {
+        condition = condition.add(sys_audit_log::Column::Method.eq(method.to_uppercase()));
+    }
+    if let Some(route) = &req.route {
+        condition = condition.add(sys_audit_log::Column::Route.starts_with(route));
+    }
+    if let Some(target_type) = &req.target_type {
+        condition = condition.add(sys_audit_log::Column::TargetType.eq(target_type));
+    }
+    if let Some(target_id) = &req.target_id {
+        condition = condition.add(sys_audit_log::Column::TargetId.eq(target_id));
+    }
+    if let Some(outcome) = &req.outcome {
+        condition = condition.add(sys_audit_log::Column::Outcome.eq(outcome));
+    }
+    if let Some(start_time) = req.start_time {
+        condition = condition.add(sys_audit_log::Column::CreatedAt.gte(start_time));
+    }
+    if let Some(end_time) = req.end_time {
+        condition = condition.add(sys_audit_log::Column::CreatedAt.lt(end_time));
+    }
+
+    let paginator = SysAuditLog::find()
+        .filter(condition)
+        .order_by_desc(sys_audit_log::Column::Id)
+        .paginate(db, req.page_size);
+    let total = paginator.num_items().await.map_err(|e| {
+        tracing::error!("query_log_page error: {}", e);
+        error_util::system_error()
+    })?;
+    let records = paginator
+        .fetch_page(req.cur_page.saturating_sub(1))
+        .await
+        .map_err(|e| {
+            tracing::error!("query_log_page error: {}", e);
+            error_util::system_error()
+        })?;
+    Ok((total, records))
+}
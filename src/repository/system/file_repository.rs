@@ -0,0 +1,54 @@
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+
+use crate::{
+    common::api_response::AppResult,
+    entities::{prelude::SysFile, system::sys_file},
+    utils::error_util,
+};
+
+pub async fn query_file_by_id(id: i64, db: &DatabaseConnection) -> AppResult<Option<sys_file::Model>> {
+    SysFile::find_by_id(id).one(db).await.map_err(|e| {
+        tracing::error!("query_file_by_id error: {}", e);
+        error_util::system_error()
+    })
+}
+
+/// 查询用户上传过的相同内容
+pub async fn query_file_by_hash(
+    content_hash: &str,
+    created_by: &str,
+    db: &DatabaseConnection,
+) -> AppResult<Option<sys_file::Model>> {
+    SysFile::find()
+        .filter(sys_file::Column::ContentHash.eq(content_hash))
+        .filter(sys_file::Column::CreatedBy.eq(created_by))
+        .one(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_file_by_hash error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 写入文件记录，同一用户并发上传相同内容时以先写入的记录为准
+pub async fn insert_file(file: sys_file::ActiveModel, db: &DatabaseConnection) -> AppResult<sys_file::Model> {
+    let content_hash = file.content_hash.clone().unwrap();
+    let created_by = file.created_by.clone().unwrap().unwrap_or_default();
+    SysFile::insert(file)
+        .on_conflict(
+            OnConflict::columns([sys_file::Column::CreatedBy, sys_file::Column::ContentHash])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("insert_file error: {}", e);
+            error_util::system_error()
+        })?;
+    query_file_by_hash(&content_hash, &created_by, db)
+        .await?
+        .ok_or_else(error_util::system_error)
+}
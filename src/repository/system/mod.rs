@@ -0,0 +1,3 @@
+pub mod audit_repository;
+pub mod outbox_repository;
+pub mod file_repository;
@@ -0,0 +1,159 @@
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::{Alias, Expr, Query};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+
+use crate::{
+    common::api_response::AppResult,
+    entities::{system::sys_outbox_event, prelude::SysOutboxEvent},
+    utils::error_util,
+};
+
+/// 写入发件箱事件，需与业务数据使用同一事务连接
+pub async fn insert_event<C: ConnectionTrait>(
+    event: sys_outbox_event::ActiveModel,
+    db: &C,
+) -> AppResult<sys_outbox_event::Model> {
+    SysOutboxEvent::insert(event)
+        .exec_with_returning(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("insert_event error: {}", e);
+            error_util::system_error()
+        })
+}
+
+/// 按 id 顺序查询到期的待发布事件
+///
+/// 只返回各聚合中最早的未发布事件：同一聚合存在更早的待发布（包括退避中）或死信事件时，
+/// 后续事件不参与发布，保证同一聚合的发布顺序
+pub async fn query_pending_events(
+    limit: u64,
+    now: NaiveDateTime,
+    db: &DatabaseConnection,
+) -> AppResult<Vec<sys_outbox_event::Model>> {
+    use sys_outbox_event::Column;
+
+    let earlier = Alias::new("earlier");
+    let blocking = Query::select()
+        .expr(Expr::val(1))
+        .from_as(SysOutboxEvent, earlier.clone())
+        .and_where(
+            Expr::col((earlier.clone(), Column::AggregateType))
+                .equals((SysOutboxEvent, Column::AggregateType)),
+        )
+        .and_where(
+            Expr::col((earlier.clone(), Column::AggregateId))
+                .equals((SysOutboxEvent, Column::AggregateId)),
+        )
+        .and_where(Expr::col((earlier.clone(), Column::Id)).lt(Expr::col((SysOutboxEvent, Column::Id))))
+        .and_where(Expr::col((earlier, Column::Status)).ne(sys_outbox_event::STATUS_PUBLISHED))
+        .to_owned();
+
+    SysOutboxEvent::find()
+        .filter(Column::Status.eq(sys_outbox_event::STATUS_PENDING))
+        .filter(Column::NextAttemptAt.lte(now))
+        .filter(Expr::exists(blocking).not())
+        .order_by_asc(Column::Id)
+        .limit(limit)
+        .all(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("query_pending_events error: {}", e);
+            error_util::system_error()
+        })
+}
+
+pub async fn mark_published(id: i64, now: NaiveDateTime, db: &DatabaseConnection) -> AppResult<()> {
+    let event = sys_outbox_event::ActiveModel {
+        id: Set(id),
+        status: Set(sys_outbox_event::STATUS_PUBLISHED),
+        published_at: Set(Some(now)),
+        last_error: Set(None),
+        ..Default::default()
+    };
+    event.update(db).await.map_err(|e| {
+        tracing::error!("mark_published error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
+
+/// 记录发布失败，`status` 为待发布时在 `next_attempt_at` 后重试
+pub async fn mark_failed(
+    id: i64,
+    attempts: i32,
+    status: i16,
+    next_attempt_at: NaiveDateTime,
+    error: String,
+    db: &DatabaseConnection,
+) -> AppResult<()> {
+    let event = sys_outbox_event::ActiveModel {
+        id: Set(id),
+        status: Set(status),
+        attempts: Set(attempts),
+        next_attempt_at: Set(next_attempt_at),
+        last_error: Set(Some(error)),
+        ..Default::default()
+    };
+    event.update(db).await.map_err(|e| {
+        tracing::error!("mark_failed error: {}", e);
+        error_util::system_error()
+    })?;
+    Ok(())
+}
+
+/// 将死信或卡住的事件重置为待发布
+pub async fn reset_event(id: i64, now: NaiveDateTime, db: &DatabaseConnection) -> AppResult<bool> {
+    let result = SysOutboxEvent::update_many()
+        .col_expr(
+            sys_outbox_event::Column::Status,
+            sea_orm::sea_query::Expr::value(sys_outbox_event::STATUS_PENDING),
+        )
+        .col_expr(sys_outbox_event::Column::Attempts, sea_orm::sea_query::Expr::value(0))
+        .col_expr(sys_outbox_event::Column::NextAttemptAt, sea_orm::sea_query::Expr::value(now))
+        .filter(sys_outbox_event::Column::Id.eq(id))
+        .filter(sys_outbox_event::Column::Status.ne(sys_outbox_event::STATUS_PUBLISHED))
+        .exec(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("reset_event error: {}", e);
+            error_util::system_error()
+        })?;
+    Ok(result.rows_affected > 0)
+}
+
+/// 分页查询卡住的事件：死信事件，或创建时间早于 `created_before` 仍未发布的事件
+pub async fn query_stuck_events(
+    created_before: NaiveDateTime,
+    cur_page: u64,
+    page_size: u64,
+    db: &DatabaseConnection,
+) -> AppResult<(u64, Vec<sys_outbox_event::Model>)> {
+    let paginator = SysOutboxEvent::find()
+        .filter(
+            sea_orm::Condition::any()
+                .add(sys_outbox_event::Column::Status.eq(sys_outbox_event::STATUS_DEAD))
+                .add(
+                    sea_orm::Condition::all()
+                        .add(sys_outbox_event::Column::Status.eq(sys_outbox_event::STATUS_PENDING))
+                        .add(sys_outbox_event::Column::CreatedAt.lt(created_before)),
+                ),
+        )
+        .order_by_asc(sys_outbox_event::Column::Id)
+        .paginate(db, page_size);
+    let total = paginator.num_items().await.map_err(|e| {
+        tracing::error!("query_stuck_events error: {}", e);
+        error_util::system_error()
+    })?;
+    let records = paginator
+        .fetch_page(cur_page.saturating_sub(1))
+        .await
+        .map_err(|e| {
+            tracing::error!("query_stuck_events error: {}", e);
+            error_util::system_error()
+        })?;
+    Ok((total, records))
+}
@@ -0,0 +1,15 @@
+use salvo::Router;
+
+use crate::config;
+use crate::handlers::assets_handler;
+
+/// 静态资源与管理后台路由
+pub fn assets_router() -> Router {
+    let mut router = Router::new()
+        .push(Router::with_path("favicon.ico").get(assets_handler::favicon))
+        .push(Router::with_path("assets/<**path>").get(assets_handler::serve));
+    if config::get().assets.admin_enabled {
+        router = router.push(Router::with_path("admin").get(assets_handler::admin_index));
+    }
+    router
+}
@@ -0,0 +1,12 @@
+//! 健康检查路由，供 Kubernetes 探针使用
+
+use salvo::Router;
+
+use crate::handlers::health_handler;
+
+pub fn health_router() -> Router {
+    Router::with_path("health")
+        .push(Router::with_path("live").get(health_handler::live))
+        .push(Router::with_path("ready").get(health_handler::ready))
+        .push(Router::with_path("startup").get(health_handler::startup))
+}
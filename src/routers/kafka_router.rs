@@ -0,0 +1,18 @@
+//! 消息队列路由模块
+//!
+//! 定义消息发送与消费相关API路由
+
+use salvo::prelude::*;
+
+use crate::handlers::kafka_handler::{consume, send, subscribe};
+use crate::hoops::auth;
+
+/// 消息队列路由，仅管理员可用
+pub fn kafka_router() -> Router {
+    Router::with_path("kafka")
+        .hoop(auth::auth_hoop)
+        .hoop(auth::RequireScope("admin"))
+        .push(Router::with_path("send").post(send))
+        .push(Router::with_path("subscribe").post(subscribe))
+        .push(Router::with_path("consume").get(consume))
+}
@@ -0,0 +1,8 @@
+use salvo::Router;
+
+use crate::handlers::metrics_handler;
+
+/// Prometheus 抓取路由，未配置 `metrics.listen_addr` 时挂在主服务上，需要 `metrics:read` 授权范围
+pub fn metrics_router() -> Router {
+    Router::with_path("metrics").get(metrics_handler::export)
+}
@@ -1,21 +1,44 @@
 use salvo::prelude::*;
 
+use crate::config;
 use crate::hoops;
 
+pub mod assets_router;
+pub mod kafka_router;
+pub mod metrics_router;
 pub mod permission;
 pub mod redis_router;
+pub mod system;
 pub mod health;
 
 pub fn root() -> Router {
-    let router = Router::new()
-        .hoop(RequestId::new())// 添加链路ID，由 salvo 自动记录
+    let mut router = Router::new()
         .hoop(Logger::new())// 添加日志
+        .hoop(hoops::access_log::access_log_hoop)// 按配置记录请求/响应正文
+        .hoop(hoops::audit::audit_hoop)// 记录修改类请求的审计日志
+        .hoop(hoops::rate_limit::rate_limit_hoop)// 按配置规则限流
         .push(
             Router::with_path("rust")
-                .push(permission::user_router::user_router()),
+                .push(permission::user_router::user_router())
+                .push(permission::api_key_router::api_key_router())
+                .push(permission::employee_router::employee_router())
+                .push(system::outbox_router::outbox_router())
+                .push(system::file_router::file_router())
+                .push(system::audit_router::audit_router())
+                .push(system::log_router::log_router()),
         )
         .push(redis_router::redis_router())
-        .push(health::health_router());
+        .push(kafka_router::kafka_router())
+        .push(health::health_router())
+        .push(assets_router::assets_router());
+    if config::get().metrics.on_main_server() {
+        // 主服务对外暴露，抓取需携带 metrics:read 授权范围的 API 密钥
+        router = router.push(
+            metrics_router::metrics_router()
+                .hoop(hoops::auth::auth_hoop)
+                .hoop(hoops::auth::RequireScope("metrics:read")),
+        );
+    }
     let doc = OpenApi::new("salvo web api", "0.1.1").merge_router(&router);
     router
         .unshift(doc.into_router("/api-doc/openapi.json"))
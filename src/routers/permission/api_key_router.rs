@@ -0,0 +1,17 @@
+use salvo::Router;
+
+use crate::handlers::permission::api_key_handler;
+use crate::hoops::auth;
+
+pub fn api_key_router() -> Router {
+    Router::with_path("/api-key")
+        .hoop(auth::auth_hoop)
+        .hoop(auth::user_only)
+        .push(
+            Router::new()
+                .get(api_key_handler::list)
+                .post(api_key_handler::create),
+        )
+        .push(Router::with_path("/<id>/rotate").post(api_key_handler::rotate))
+        .push(Router::with_path("/<id>/revoke").post(api_key_handler::revoke))
+}
@@ -0,0 +1,11 @@
+use salvo::Router;
+
+use crate::handlers::permission::employee_handler;
+use crate::hoops::auth;
+
+pub fn employee_router() -> Router {
+    Router::with_path("/employee")
+        .hoop(auth::auth_hoop)
+        .hoop(auth::RequireScope("admin"))
+        .push(Router::with_path("/lookup").post(employee_handler::lookup))
+}
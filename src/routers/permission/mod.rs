@@ -1 +1,3 @@
-pub mod user_router;
\ No newline at end of file
+pub mod api_key_router;
+pub mod user_router;
+pub mod employee_router;
@@ -1,15 +1,59 @@
 use salvo::Router;
 
-use crate::handlers::permission::user_handler;
+use crate::handlers::permission::{challenge_handler, mfa_handler, oidc_handler, user_handler};
+use crate::handlers::system::file_handler;
+use crate::hoops::auth;
 
 pub fn user_router() -> Router {
     Router::new()
         .push(
-            Router::with_path("/auth").push(Router::with_path("/login").post(user_handler::login)),
+            Router::with_path("/auth")
+                .push(Router::with_path("/login").post(user_handler::login))
+                .push(Router::with_path("/challenge").get(challenge_handler::issue))
+                .push(Router::with_path("/mfa/verify").post(mfa_handler::verify))
+                .push(
+                    Router::with_path("/oidc")
+                        .push(Router::with_path("/authorize").get(oidc_handler::authorize))
+                        .push(Router::with_path("/callback").get(oidc_handler::callback)),
+                ),
         )
         .push(
             Router::with_path("/user")
                 .push(Router::with_path("/create").post(user_handler::create))
-                .push(Router::with_path("/page").post(user_handler::list_page)),
+                .push(
+                    Router::new()
+                        .hoop(auth::auth_hoop)
+                        .hoop(auth::RequireScope("admin"))
+                        .push(Router::with_path("/page").post(user_handler::list_page))
+                        .push(Router::with_path("/<user_id>/lock").post(user_handler::lock))
+                        .push(Router::with_path("/<user_id>/unlock").post(user_handler::unlock)),
+                )
+                .push(
+                    Router::with_path("/avatar")
+                        .hoop(auth::auth_hoop)
+                        .hoop(auth::user_only)
+                        .post(file_handler::upload_avatar),
+                )
+                // 头像地址用于 img 标签，无需登录
+                .push(Router::with_path("/<user_id>/avatar").get(file_handler::avatar))
+                .push(
+                    Router::with_path("/mail/test")
+                        .hoop(auth::auth_hoop)
+                        .hoop(auth::user_only)
+                        .post(user_handler::send_test_mail),
+                )
+                .push(
+                    Router::with_path("/mfa")
+                        .hoop(auth::auth_hoop)
+                        .hoop(auth::user_only)
+                        .push(Router::with_path("/enroll").post(mfa_handler::enroll))
+                        .push(Router::with_path("/activate").post(mfa_handler::activate)),
+                )
+                .push(
+                    Router::with_path("/<user_id>/mfa/reset")
+                        .hoop(auth::auth_hoop)
+                        .hoop(auth::RequireScope("admin"))
+                        .post(mfa_handler::reset),
+                ),
         )
 }
@@ -4,11 +4,14 @@
 
 use salvo::prelude::*;
 
-use crate::handlers::redis_handler::{set, get, delete};
+use crate::handlers::redis_handler::{set, get, delete, scan};
+use crate::hoops::auth;
 
-/// Redis路由
+/// Redis路由，可读写任意键，仅限管理员
 pub fn redis_router() -> Router {
     Router::with_path("redis")
+        .hoop(auth::auth_hoop)
+        .hoop(auth::RequireScope("admin"))
         .push(
             Router::with_path("set")
                 .post(set)
@@ -21,4 +24,8 @@ pub fn redis_router() -> Router {
             Router::with_path("delete/<key>")
                 .delete(delete)
         )
-}
\ No newline at end of file
+        .push(
+            Router::with_path("keys")
+                .get(scan)
+        )
+}
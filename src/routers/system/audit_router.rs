@@ -0,0 +1,11 @@
+use salvo::Router;
+
+use crate::handlers::system::audit_handler;
+use crate::hoops::auth;
+
+pub fn audit_router() -> Router {
+    Router::with_path("/audit")
+        .hoop(auth::auth_hoop)
+        .hoop(auth::RequireScope("audit:read"))
+        .push(Router::with_path("/page").post(audit_handler::page))
+}
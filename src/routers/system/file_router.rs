@@ -0,0 +1,16 @@
+use salvo::Router;
+
+use crate::handlers::system::file_handler;
+use crate::hoops::auth;
+
+pub fn file_router() -> Router {
+    Router::with_path("/file")
+        // 下载地址自带签名，无需登录
+        .push(Router::with_path("/download/<**key>").get(file_handler::download))
+        .push(
+            Router::new()
+                .hoop(auth::auth_hoop)
+                .push(Router::with_path("/upload").post(file_handler::upload))
+                .push(Router::with_path("/<id>").get(file_handler::get)),
+        )
+}
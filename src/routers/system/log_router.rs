@@ -0,0 +1,13 @@
+use salvo::Router;
+
+use crate::handlers::system::log_handler;
+use crate::hoops::auth;
+
+pub fn log_router() -> Router {
+    Router::with_path("/log/level")
+        .hoop(auth::auth_hoop)
+        .hoop(auth::RequireScope("admin"))
+        .get(log_handler::get_level)
+        .put(log_handler::set_level)
+        .delete(log_handler::reset_level)
+}
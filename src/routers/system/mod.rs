@@ -0,0 +1,4 @@
+pub mod audit_router;
+pub mod outbox_router;
+pub mod file_router;
+pub mod log_router;
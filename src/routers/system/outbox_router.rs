@@ -0,0 +1,12 @@
+use salvo::Router;
+
+use crate::handlers::system::outbox_handler;
+use crate::hoops::auth;
+
+pub fn outbox_router() -> Router {
+    Router::with_path("/admin/outbox")
+        .hoop(auth::auth_hoop)
+        .hoop(auth::RequireScope("admin"))
+        .push(Router::with_path("/stuck").post(outbox_handler::stuck_page))
+        .push(Router::with_path("/<id>/retry").post(outbox_handler::retry))
+}
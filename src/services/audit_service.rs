@@ -0,0 +1,139 @@
+//! 审计日志服务
+//!
+//! 审计中间件通过有界通道提交日志，后台任务批量写入 `sys_audit_log`，避免阻塞请求。
+//! 攒满一批或每隔 `FLUSH_INTERVAL` 写入一次；停止时（见 [`AuditService::shutdown`]）写完通道中剩余的日志。
+
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::Local;
+use sea_orm::{ActiveValue::Set, DatabaseConnection};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+
+use crate::common::api_response::AppResult;
+use crate::db;
+use crate::entities::system::sys_audit_log;
+use crate::models::system::audit_dto::AuditPageReq;
+use crate::repository::system::audit_repository;
+
+/// 通道容量，写满后新日志会被丢弃并告警
+const CHANNEL_CAPACITY: usize = 4096;
+/// 单次批量写入的最大条数
+const BATCH_SIZE: usize = 100;
+/// 定时写入的间隔，持续有请求时日志最多等待这么久
+const FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+static AUDIT_SENDER: OnceLock<mpsc::Sender<sys_audit_log::ActiveModel>> = OnceLock::new();
+static SHUTDOWN: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::channel(false).0);
+static WRITER: LazyLock<Mutex<Option<JoinHandle<()>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// 待写入的审计日志
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub client_ip: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub changes: Option<serde_json::Value>,
+    pub outcome: &'static str,
+    pub status_code: i32,
+    pub latency_ms: i64,
+}
+
+pub struct AuditService;
+
+impl AuditService {
+    /// 启动后台写入任务
+    pub fn init_writer(db: &'static DatabaseConnection) {
+        let (tx, mut rx) = mpsc::channel::<sys_audit_log::ActiveModel>(CHANNEL_CAPACITY);
+        if AUDIT_SENDER.set(tx).is_err() {
+            return;
+        }
+        let handle = tokio::spawn(async move {
+            let mut shutdown = SHUTDOWN.subscribe();
+            let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+            let mut buffer = Vec::with_capacity(BATCH_SIZE);
+            loop {
+                tokio::select! {
+                    _ = shutdown.changed() => break,
+                    _ = ticker.tick() => flush(&mut buffer, db).await,
+                    log = rx.recv() => match log {
+                        Some(log) => {
+                            buffer.push(log);
+                            if buffer.len() >= BATCH_SIZE {
+                                flush(&mut buffer, db).await;
+                            }
+                        }
+                        None => break,
+                    },
+                }
+            }
+            // 写完已提交的日志
+            while let Ok(log) = rx.try_recv() {
+                buffer.push(log);
+                if buffer.len() >= BATCH_SIZE {
+                    flush(&mut buffer, db).await;
+                }
+            }
+            flush(&mut buffer, db).await;
+        });
+        *WRITER.lock().expect("audit writer lock poisoned") = Some(handle);
+    }
+
+    /// 停止后台写入任务，在超时时间内写完通道中剩余的日志；应在服务停止接收请求后调用
+    pub async fn shutdown(timeout: Duration) {
+        let _ = SHUTDOWN.send(true);
+        let handle = WRITER.lock().expect("audit writer lock poisoned").take();
+        if let Some(handle) = handle
+            && tokio::time::timeout(timeout, handle).await.is_err()
+        {
+            tracing::warn!("audit writer did not stop within {:?}", timeout);
+        }
+    }
+
+    /// 提交审计日志，不等待入库
+    pub fn submit(entry: AuditEntry) {
+        let Some(sender) = AUDIT_SENDER.get() else {
+            tracing::warn!(route = %entry.route, "audit writer not initialized, log dropped");
+            return;
+        };
+        let log = sys_audit_log::ActiveModel {
+            actor: Set(entry.actor),
+            request_id: Set(entry.request_id),
+            method: Set(entry.method),
+            route: Set(entry.route),
+            client_ip: Set(entry.client_ip),
+            target_type: Set(entry.target_type),
+            target_id: Set(entry.target_id),
+            changes: Set(entry.changes),
+            outcome: Set(entry.outcome.to_string()),
+            status_code: Set(entry.status_code),
+            latency_ms: Set(entry.latency_ms),
+            created_at: Set(Local::now().naive_local()),
+            ..Default::default()
+        };
+        if let Err(e) = sender.try_send(log) {
+            tracing::warn!("audit log dropped: {}", e);
+        }
+    }
+
+    /// 分页查询审计日志，日志异步批量写入，本身就有延迟，使用只读副本
+    pub async fn query_page(req: &AuditPageReq) -> AppResult<(u64, Vec<sys_audit_log::Model>)> {
+        audit_repository::query_log_page(req, db::postgres::read()).await
+    }
+}
+
+async fn flush(buffer: &mut Vec<sys_audit_log::ActiveModel>, db: &DatabaseConnection) {
+    if buffer.is_empty() {
+        return;
+    }
+    let logs = std::mem::take(buffer);
+    let count = logs.len();
+    if let Err(e) = audit_repository::insert_logs(logs, db).await {
+        tracing::error!("write {} audit log(s) failed: {}", count, e);
+    }
+}
@@ -0,0 +1,276 @@
+//! 文件上传
+//!
+//! 上传的文件按内容识别类型并校验大小，以 SHA-256 摘要去重：相同内容只保存一份对象，
+//! 同一用户重复上传直接返回已有记录，其他用户上传相同内容时新建自己的记录。图片会按 `storage.thumbnail_sizes` 生成 PNG 缩略图。
+//! 对象 key 为 `files/<摘要前两位>/<摘要>.<扩展名>` 与 `thumbs/<摘要前两位>/<摘要>_<边长>.png`。
+
+use std::io::Cursor;
+
+use anyhow::anyhow;
+use chrono::Local;
+use image::{ImageFormat, ImageReader, Limits};
+use salvo::http::StatusError;
+use sea_orm::{ActiveValue::Set, DatabaseConnection};
+
+use crate::common::api_response::AppResult;
+use crate::{config, db};
+use crate::entities::system::sys_file;
+use crate::hoops::auth::{self, Principal};
+use crate::models::system::file_dto::{FileRes, ThumbnailRes};
+use crate::repository::permission::user_repository;
+use crate::repository::system::file_repository;
+use crate::storage;
+use crate::utils;
+
+/// 解码图片允许的最大边长，防止解压炸弹
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+
+/// 待保存的上传文件
+pub struct UploadFile {
+    pub file_name: Option<String>,
+    pub content: Vec<u8>,
+}
+
+/// 解码后的图片信息与缩略图
+struct ImageInfo {
+    width: u32,
+    height: u32,
+    thumbnails: Vec<(u32, Vec<u8>)>,
+}
+
+pub struct FileService;
+
+impl FileService {
+    /// 保存上传文件，该用户上传过相同内容时直接返回已有记录
+    pub async fn upload(file: UploadFile, user_id: &str, db: &DatabaseConnection) -> AppResult<sys_file::Model> {
+        Self::store(file, user_id, false, db).await
+    }
+
+    /// 保存上传的图片，不是图片时在写入存储前拒绝
+    pub async fn upload_image(file: UploadFile, user_id: &str, db: &DatabaseConnection) -> AppResult<sys_file::Model> {
+        Self::store(file, user_id, true, db).await
+    }
+
+    async fn store(
+        file: UploadFile,
+        user_id: &str,
+        image_only: bool,
+        db: &DatabaseConnection,
+    ) -> AppResult<sys_file::Model> {
+        let config = &config::get().storage;
+        if file.content.is_empty() {
+            return Err(StatusError::bad_request().brief("文件不能为空").into());
+        }
+        if file.content.len() > config.max_size {
+            return Err(StatusError::payload_too_large()
+                .brief(format!("文件不能超过{}KB", config.max_size / 1024))
+                .into());
+        }
+        let Some(kind) = infer::get(&file.content).filter(|kind| {
+            config.allowed_types.iter().any(|allowed| allowed == kind.mime_type())
+        }) else {
+            return Err(StatusError::unsupported_media_type().brief("不支持的文件类型").into());
+        };
+        if image_only && !kind.mime_type().starts_with("image/") {
+            return Err(StatusError::unsupported_media_type().brief("请上传图片").into());
+        }
+
+        let content_hash = utils::sha256_hex(&file.content);
+        if let Some(existing) = file_repository::query_file_by_hash(&content_hash, user_id, db).await? {
+            tracing::info!(file_id = existing.id, "duplicate upload, reuse stored file");
+            return Ok(existing);
+        }
+
+        let image = if kind.mime_type().starts_with("image/") {
+            let content = file.content.clone();
+            let sizes = config.thumbnail_sizes.clone();
+            let image = tokio::task::spawn_blocking(move || decode_image(&content, &sizes))
+                .await
+                .map_err(anyhow::Error::from)?
+                .map_err(|e| {
+                    tracing::warn!("decode uploaded image failed: {}", e);
+                    StatusError::bad_request().brief("图片无法识别")
+                })?;
+            Some(image)
+        } else {
+            None
+        };
+
+        let storage = storage::get()?;
+        let storage_key = file_key(&content_hash, kind.extension());
+        let size = file.content.len() as i64;
+        // 上次上传写入了对象但记录未保存时无需重复写入
+        if !storage.exists(&storage_key).await? {
+            storage.put(&storage_key, file.content, kind.mime_type()).await?;
+        }
+        let mut thumbnail_sizes = Vec::new();
+        for (thumb_size, png) in image.iter().flat_map(|image| image.thumbnails.iter()) {
+            storage
+                .put(&thumbnail_key(&content_hash, *thumb_size), png.clone(), "image/png")
+                .await?;
+            thumbnail_sizes.push(thumb_size.to_string());
+        }
+
+        let model = sys_file::ActiveModel {
+            content_hash: Set(content_hash),
+            storage_key: Set(storage_key),
+            file_name: Set(file.file_name),
+            content_type: Set(kind.mime_type().to_string()),
+            size: Set(size),
+            width: Set(image.as_ref().map(|image| image.width as i32)),
+            height: Set(image.as_ref().map(|image| image.height as i32)),
+            thumbnail_sizes: Set(Some(thumbnail_sizes.join(",")).filter(|s| !s.is_empty())),
+            created_by: Set(Some(user_id.to_string())),
+            created_at: Set(Local::now().naive_local()),
+            ..Default::default()
+        };
+        let saved = file_repository::insert_file(model, db).await?;
+        tracing::info!(file_id = saved.id, size, content_type = %saved.content_type, "file uploaded");
+        Ok(saved)
+    }
+
+    /// 查询文件，只有上传用户与管理员可以查看，其他用户按不存在处理
+    pub async fn get_file(id: i64, principal: &Principal, db: &DatabaseConnection) -> AppResult<sys_file::Model> {
+        match file_repository::query_file_by_id(id, db).await? {
+            Some(file)
+                if file.created_by.as_deref() == Some(principal.user_id.as_str())
+                    || principal.has_scope(auth::SCOPE_ADMIN) =>
+            {
+                Ok(file)
+            }
+            _ => Err(StatusError::not_found().brief("文件不存在").into()),
+        }
+    }
+
+    /// 用户当前头像的签名地址，头像地址见 [`avatar_url`]；头像更新后短暂读到旧头像可以接受，使用只读副本
+    pub async fn avatar_signed_url(user_id: &str) -> AppResult<String> {
+        let db = db::postgres::read();
+        let user = user_repository::query_user_by_user_id(user_id, db)
+            .await?
+            .ok_or_else(|| StatusError::not_found().brief("用户不存在"))?;
+        let file = match user.image_url.as_deref().and_then(parse_avatar_file_id) {
+            Some(id) => file_repository::query_file_by_id(id, db).await?,
+            None => None,
+        };
+        let file = file.ok_or_else(|| StatusError::not_found().brief("未设置头像"))?;
+        let storage = storage::get()?;
+        Ok(storage.signed_url(&file.storage_key, config::get().storage.url_ttl).await?)
+    }
+
+    /// 生成带签名下载地址的文件信息
+    pub async fn to_res(file: sys_file::Model) -> AppResult<FileRes> {
+        let ttl = config::get().storage.url_ttl;
+        let storage = storage::get()?;
+        let url = storage.signed_url(&file.storage_key, ttl).await?;
+        let mut thumbnails = Vec::new();
+        for size in file.thumbnails() {
+            let url = storage.signed_url(&thumbnail_key(&file.content_hash, size), ttl).await?;
+            thumbnails.push(ThumbnailRes { size, url });
+        }
+        Ok(FileRes {
+            id: file.id,
+            file_name: file.file_name,
+            content_type: file.content_type,
+            size: file.size,
+            width: file.width,
+            height: file.height,
+            url,
+            thumbnails,
+            expires_in: ttl,
+            created_at: file.created_at,
+        })
+    }
+
+    /// 读取签名下载地址对应的对象，返回内容与类型，`expires` 之后地址失效
+    pub async fn download(key: &str, expires: u64, signature: &str) -> AppResult<(Vec<u8>, String)> {
+        let storage = storage::get()?;
+        if !storage.verify_signature(key, expires, signature) {
+            return Err(StatusError::forbidden().brief("下载地址无效或已过期").into());
+        }
+        let content = storage
+            .get(key)
+            .await?
+            .ok_or_else(|| StatusError::not_found().brief("文件不存在"))?;
+        let content_type = infer::get(&content)
+            .map(|kind| kind.mime_type())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        Ok((content, content_type))
+    }
+}
+
+/// 解码图片并按给定边长生成缩略图，不放大小于目标尺寸的图片
+fn decode_image(content: &[u8], sizes: &[u32]) -> anyhow::Result<ImageInfo> {
+    let mut reader = ImageReader::new(Cursor::new(content)).with_guessed_format()?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    reader.limits(limits);
+    let image = reader.decode()?;
+
+    let (width, height) = (image.width(), image.height());
+    let mut thumbnails = Vec::new();
+    for &size in sizes {
+        if size >= width.max(height) {
+            continue;
+        }
+        let mut png = Vec::new();
+        image
+            .thumbnail(size, size)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .map_err(|e| anyhow!("encode thumbnail failed: {}", e))?;
+        thumbnails.push((size, png));
+    }
+    Ok(ImageInfo { width, height, thumbnails })
+}
+
+/// 头像地址，无需登录即可在 `<img>` 中使用，`v` 为头像文件 id，更换头像后地址随之变化
+pub fn avatar_url(user_id: &str, file_id: i64) -> String {
+    format!("/rust/user/{}/avatar?v={}", user_id, file_id)
+}
+
+fn parse_avatar_file_id(image_url: &str) -> Option<i64> {
+    let (_, query) = image_url.split_once('?')?;
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("v="))
+        .and_then(|id| id.parse().ok())
+}
+
+fn file_key(content_hash: &str, extension: &str) -> String {
+    format!("files/{}/{}.{}", &content_hash[..2], content_hash, extension)
+}
+
+fn thumbnail_key(content_hash: &str, size: u32) -> String {
+    format!("thumbs/{}/{}_{}.png", &content_hash[..2], content_hash, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, RgbImage};
+
+    use super::*;
+
+    #[test]
+    fn test_avatar_url_roundtrip() {
+        let url = avatar_url("10001", 42);
+        assert_eq!(url, "/rust/user/10001/avatar?v=42");
+        assert_eq!(parse_avatar_file_id(&url), Some(42));
+        assert_eq!(parse_avatar_file_id("/rust/file/42"), None);
+    }
+
+    #[test]
+    fn test_decode_image_skips_upscaling() {
+        let mut png = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(200, 100))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        let info = decode_image(&png, &[64, 256]).unwrap();
+        assert_eq!((info.width, info.height), (200, 100));
+        assert_eq!(info.thumbnails.len(), 1);
+
+        let thumb = image::load_from_memory(&info.thumbnails[0].1).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (64, 32));
+    }
+}
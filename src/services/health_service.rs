@@ -0,0 +1,160 @@
+//! 健康检查
+//!
+//! - 存活检查只表示进程可以处理请求，不检查外部依赖；
+//! - 启动检查在初始化完成（见 [`HealthService::mark_started`]）后才通过；
+//! - 就绪检查并发检查 Postgres、Redis 与消息代理，每项有独立超时，关键依赖失败时不就绪。
+//!   收到停止信号后立即返回不就绪，负载均衡摘除实例后再停止服务。
+
+use std::future::Future;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use sea_orm::{ConnectionTrait, Statement};
+
+use crate::config::{self, CHECK_BROKER, CHECK_POSTGRES, CHECK_REDIS};
+use crate::db;
+use crate::kafka;
+use crate::models::system::health_dto::{
+    HealthCheckRes, HealthReport, STATUS_DEGRADED, STATUS_DOWN, STATUS_DRAINING, STATUS_UP,
+};
+use crate::services::redis_service::RedisService;
+
+const ERROR_TIMEOUT: &str = "timeout";
+const ERROR_UNAVAILABLE: &str = "unavailable";
+
+static STARTED_AT: LazyLock<Instant> = LazyLock::new(Instant::now);
+static STARTED: AtomicBool = AtomicBool::new(false);
+static DRAINING: AtomicBool = AtomicBool::new(false);
+
+pub struct HealthService;
+
+impl HealthService {
+    /// 初始化完成且主服务已开始监听，启动检查开始通过
+    pub fn mark_started() {
+        LazyLock::force(&STARTED_AT);
+        STARTED.store(true, Ordering::Release);
+    }
+
+    /// 进入摘流状态，就绪检查开始失败
+    pub fn start_draining() {
+        DRAINING.store(true, Ordering::Release);
+    }
+
+    pub fn is_started() -> bool {
+        STARTED.load(Ordering::Acquire)
+    }
+
+    pub fn is_draining() -> bool {
+        DRAINING.load(Ordering::Acquire)
+    }
+
+    /// 不检查依赖的简单报告，用于存活与启动检查
+    pub fn status_report(status: &str) -> HealthReport {
+        HealthReport {
+            status: status.to_string(),
+            uptime_secs: STARTED_AT.elapsed().as_secs(),
+            checks: Vec::new(),
+        }
+    }
+
+    /// 检查全部依赖，返回报告与是否就绪
+    pub async fn readiness() -> (HealthReport, bool) {
+        if Self::is_draining() {
+            return (Self::status_report(STATUS_DRAINING), false);
+        }
+        if !Self::is_started() {
+            return (Self::status_report(STATUS_DOWN), false);
+        }
+
+        let (postgres, redis, broker) = tokio::join!(
+            run_check(CHECK_POSTGRES, check_postgres()),
+            run_check(CHECK_REDIS, check_redis()),
+            run_check(CHECK_BROKER, check_broker()),
+        );
+        let (checks, ready) = summarize(vec![postgres, redis, broker]);
+        let status = if !ready {
+            STATUS_DOWN
+        } else if checks.iter().any(|c| c.status != STATUS_UP) {
+            STATUS_DEGRADED
+        } else {
+            STATUS_UP
+        };
+        let report = HealthReport {
+            status: status.to_string(),
+            uptime_secs: STARTED_AT.elapsed().as_secs(),
+            checks,
+        };
+        (report, ready)
+    }
+}
+
+/// 关键依赖全部正常时就绪
+fn summarize(checks: Vec<HealthCheckRes>) -> (Vec<HealthCheckRes>, bool) {
+    let ready = checks.iter().all(|c| !c.critical || c.status == STATUS_UP);
+    (checks, ready)
+}
+
+async fn run_check<F>(name: &str, check: F) -> HealthCheckRes
+where
+    F: Future<Output = Result<()>>,
+{
+    let config = &config::get().health;
+    let timeout = Duration::from_millis(config.timeout_of(name));
+    let start = Instant::now();
+    // 就绪检查无需认证，详细错误可能包含地址等内部信息，只写日志，响应中只返回错误类别
+    let error = match tokio::time::timeout(timeout, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some((ERROR_UNAVAILABLE, e)),
+        Err(_) => Some((ERROR_TIMEOUT, anyhow!("timed out after {:?}", timeout))),
+    };
+    let latency_ms = start.elapsed().as_millis() as u64;
+    if let Some((_, e)) = &error {
+        tracing::warn!(check = name, latency_ms, "health check failed: {}", e);
+    }
+    HealthCheckRes {
+        name: name.to_string(),
+        status: if error.is_none() { STATUS_UP } else { STATUS_DOWN }.to_string(),
+        critical: config.is_critical(name),
+        latency_ms,
+        error: error.map(|(kind, _)| kind.to_string()),
+    }
+}
+
+async fn check_postgres() -> Result<()> {
+    let db = db::postgres::pool();
+    db.execute(Statement::from_string(db.get_database_backend(), "SELECT 1"))
+        .await?;
+    Ok(())
+}
+
+async fn check_redis() -> Result<()> {
+    RedisService::ping().await
+}
+
+async fn check_broker() -> Result<()> {
+    let timeout = Duration::from_millis(config::get().health.timeout_of(CHECK_BROKER));
+    kafka::ping(timeout).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(status: &str, critical: bool) -> HealthCheckRes {
+        HealthCheckRes {
+            name: "test".to_string(),
+            status: status.to_string(),
+            critical,
+            latency_ms: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_non_critical_failure_keeps_ready() {
+        assert!(summarize(vec![check(STATUS_UP, true), check(STATUS_DOWN, false)]).1);
+        assert!(!summarize(vec![check(STATUS_DOWN, true), check(STATUS_UP, false)]).1);
+    }
+}
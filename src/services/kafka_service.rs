@@ -0,0 +1,50 @@
+//! 消息服务层
+//!
+//! 提供对消息代理的发送与拉取式消费封装，供接口层调用。
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use tokio::sync::Mutex;
+
+use crate::kafka::{self, BrokerMessage, Subscription};
+
+/// 接口层使用的拉取式订阅
+static PULL_SUBSCRIPTION: Mutex<Option<Box<dyn Subscription>>> = Mutex::const_new(None);
+
+/// 拉取消息的等待时间
+const CONSUME_WAIT: Duration = Duration::from_secs(1);
+
+pub struct KafkaService;
+
+impl KafkaService {
+    /// 发送消息到指定主题
+    pub async fn send_message(topic: &str, key: Option<&str>, payload: &str) -> Result<()> {
+        let message = BrokerMessage::new(topic, key.map(str::to_string), payload);
+        kafka::broker().publish(message).await
+    }
+
+    /// 订阅主题，重复调用会替换之前的订阅
+    pub async fn subscribe_topics(topics: &[String]) -> Result<()> {
+        let group_id = kafka::broker_config().group_id;
+        let subscription = kafka::broker().subscribe(&group_id, topics).await?;
+        *PULL_SUBSCRIPTION.lock().await = Some(subscription);
+        Ok(())
+    }
+
+    /// 从已订阅的主题中拉取一条消息，没有消息时返回 `None`
+    pub async fn consume_message() -> Result<Option<BrokerMessage>> {
+        let mut guard = PULL_SUBSCRIPTION.lock().await;
+        let subscription = guard
+            .as_mut()
+            .ok_or_else(|| anyhow!("尚未订阅任何主题"))?;
+        match tokio::time::timeout(CONSUME_WAIT, subscription.recv()).await {
+            Ok(Ok(Some(message))) => {
+                subscription.commit().await?;
+                Ok(Some(message))
+            }
+            Ok(Ok(None)) | Err(_) => Ok(None),
+            Ok(Err(e)) => Err(e),
+        }
+    }
+}
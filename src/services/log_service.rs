@@ -0,0 +1,173 @@
+//! 运行时日志级别
+//!
+//! 日志初始化时用 [`LogService::reload_layer`] 包装 `EnvFilter`，之后可通过管理接口临时调整
+//! 过滤规则（如 `sea_orm=debug,base_web::services=trace`），并可设置到期自动恢复。
+//! 配置重新加载时调用 [`LogService::apply_config`]，没有临时规则时立即生效。
+
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime};
+use salvo::http::StatusError;
+use tracing_subscriber::reload::{self, Handle};
+use tracing_subscriber::{EnvFilter, Registry};
+
+use crate::common::api_response::AppResult;
+use crate::config::LogConfig;
+use crate::models::system::log_dto::LogLevelRes;
+
+static HANDLE: OnceLock<Handle<EnvFilter, Registry>> = OnceLock::new();
+static STATE: LazyLock<Mutex<FilterState>> = LazyLock::new(|| Mutex::new(FilterState::default()));
+
+#[derive(Default)]
+struct FilterState {
+    /// 配置文件（或 RUST_LOG）给出的规则
+    configured: String,
+    /// 配置文件中的 `log.filter_level`，设置了 RUST_LOG 时可能与生效的规则不同
+    file_level: String,
+    /// 临时规则，为空表示使用配置规则
+    overridden: Option<String>,
+    revert_at: Option<NaiveDateTime>,
+    /// 每次调整递增，自动恢复任务据此判断规则是否已被再次修改
+    generation: u64,
+}
+
+impl FilterState {
+    fn active(&self) -> &str {
+        self.overridden.as_deref().unwrap_or(&self.configured)
+    }
+}
+
+pub struct LogService;
+
+impl LogService {
+    /// 创建可重新加载的过滤层，由日志初始化调用一次
+    pub fn reload_layer(config: &LogConfig) -> reload::Layer<EnvFilter, Registry> {
+        let configured = configured_directives(config);
+        let filter = EnvFilter::try_new(&configured).unwrap_or_else(|e| {
+            eprintln!("invalid log filter {:?}, fallback to info: {}", configured, e);
+            EnvFilter::new("info")
+        });
+        let (layer, handle) = reload::Layer::new(filter);
+        let _ = HANDLE.set(handle);
+        let mut state = STATE.lock().unwrap();
+        state.configured = configured;
+        state.file_level = config.filter_level.clone();
+        layer
+    }
+
+    pub fn current() -> LogLevelRes {
+        let state = STATE.lock().unwrap();
+        LogLevelRes {
+            directives: state.active().to_string(),
+            configured: state.configured.clone(),
+            revert_at: state.revert_at,
+        }
+    }
+
+    /// 设置临时规则，`revert_after` 秒后自动恢复为配置规则
+    pub fn set(directives: &str, revert_after: Option<u64>) -> AppResult<LogLevelRes> {
+        let filter = EnvFilter::try_new(directives)
+            .map_err(|e| StatusError::bad_request().brief(format!("日志过滤规则无效: {}", e)))?;
+        let generation = {
+            let mut state = STATE.lock().unwrap();
+            reload(filter)?;
+            state.overridden = Some(directives.to_string());
+            state.revert_at = revert_after
+                .map(|secs| Local::now().naive_local() + chrono::Duration::seconds(secs as i64));
+            state.generation += 1;
+            state.generation
+        };
+        tracing::warn!(directives, revert_after, "log filter changed");
+
+        if let Some(secs) = revert_after {
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_secs(secs)).await;
+                if let Err(e) = revert(Some(generation)) {
+                    tracing::error!("log filter revert error: {}", e);
+                }
+            });
+        }
+        Ok(Self::current())
+    }
+
+    /// 恢复为配置规则
+    pub fn reset() -> AppResult<LogLevelRes> {
+        revert(None)?;
+        Ok(Self::current())
+    }
+
+    /// 配置重新加载后更新配置规则，存在临时规则时等其恢复后再生效
+    pub fn apply_config(config: &LogConfig) -> AppResult<()> {
+        let configured = configured_directives(config);
+        let filter = EnvFilter::try_new(&configured)
+            .map_err(|e| StatusError::bad_request().brief(format!("日志过滤规则无效: {}", e)))?;
+        let mut state = STATE.lock().unwrap();
+        if state.file_level != config.filter_level {
+            if env_directives().is_some() {
+                tracing::warn!(
+                    filter_level = %config.filter_level,
+                    directives = %configured,
+                    "log.filter_level changed but RUST_LOG is set and takes precedence, ignored"
+                );
+            }
+            state.file_level = config.filter_level.clone();
+        }
+        if state.configured == configured {
+            return Ok(());
+        }
+        if state.overridden.is_none() {
+            reload(filter)?;
+        }
+        tracing::info!(directives = %configured, "log filter reloaded from config");
+        state.configured = configured;
+        Ok(())
+    }
+}
+
+/// 恢复配置规则；`generation` 不为空时仅在规则未被再次修改时恢复
+fn revert(generation: Option<u64>) -> AppResult<()> {
+    let mut state = STATE.lock().unwrap();
+    if generation.is_some_and(|generation| generation != state.generation) || state.overridden.is_none() {
+        return Ok(());
+    }
+    reload(EnvFilter::new(&state.configured))?;
+    state.overridden = None;
+    state.revert_at = None;
+    state.generation += 1;
+    tracing::warn!(directives = %state.configured, "log filter reverted");
+    Ok(())
+}
+
+fn reload(filter: EnvFilter) -> AppResult<()> {
+    let handle = HANDLE
+        .get()
+        .ok_or_else(|| StatusError::service_unavailable().brief("日志尚未初始化"))?;
+    handle.reload(filter).map_err(|e| {
+        tracing::error!("log filter reload error: {}", e);
+        StatusError::internal_server_error().brief("日志级别调整失败")
+    })?;
+    Ok(())
+}
+
+/// RUST_LOG 优先于配置文件
+fn configured_directives(config: &LogConfig) -> String {
+    env_directives().unwrap_or_else(|| config.filter_level.clone())
+}
+
+fn env_directives() -> Option<String> {
+    std::env::var(EnvFilter::DEFAULT_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_rejects_invalid_directives() {
+        assert!(LogService::set("sea_orm=verbose", None).is_err());
+        assert!(LogService::current().revert_at.is_none());
+    }
+}
@@ -1,3 +1,10 @@
+pub mod audit_service;
+pub mod encryption_service;
+pub mod file_service;
+pub mod health_service;
+pub mod kafka_service;
+pub mod log_service;
+pub mod outbox_service;
 pub mod permission;
 pub mod redis_service;
 pub mod redis_service_test;
\ No newline at end of file
@@ -0,0 +1,251 @@
+//! 事务发件箱服务
+//!
+//! 业务数据与事件在同一事务中写入 `sys_outbox_event`，由后台中继任务轮询待发布事件，
+//! 发布到消息代理或 Redis stream。同一聚合的事件严格按写入顺序发布：
+//! 前序事件未发布成功（退避中或已成为死信）时，后续事件不会被发布。
+
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{Local, NaiveDateTime};
+use sea_orm::{ActiveValue::Set, ConnectionTrait, DatabaseConnection};
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::cache::redis_manager;
+use crate::common::api_response::AppResult;
+use crate::config::{OutboxConfig, TARGET_REDIS};
+use crate::db;
+use crate::entities::system::sys_outbox_event;
+use crate::kafka::{self, BrokerMessage};
+use crate::repository::system::outbox_repository;
+
+pub const HEADER_EVENT_ID: &str = "x-event-id";
+pub const HEADER_EVENT_TYPE: &str = "x-event-type";
+pub const HEADER_AGGREGATE_TYPE: &str = "x-aggregate-type";
+
+/// 失败重试的最大间隔（秒）
+const MAX_BACKOFF_SECS: i64 = 300;
+
+static SHUTDOWN: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::channel(false).0);
+static RELAY: LazyLock<Mutex<Option<JoinHandle<()>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// 待写入发件箱的领域事件
+#[derive(Debug, Clone)]
+pub struct DomainEvent {
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub topic: String,
+    pub payload: serde_json::Value,
+}
+
+impl DomainEvent {
+    pub fn new<T: Serialize>(
+        aggregate_type: &str,
+        aggregate_id: impl Into<String>,
+        event_type: &str,
+        topic: &str,
+        payload: &T,
+    ) -> Result<Self> {
+        Ok(Self {
+            aggregate_type: aggregate_type.to_string(),
+            aggregate_id: aggregate_id.into(),
+            event_type: event_type.to_string(),
+            topic: topic.to_string(),
+            payload: serde_json::to_value(payload)?,
+        })
+    }
+}
+
+pub struct OutboxService;
+
+impl OutboxService {
+    /// 在调用方事务中记录事件
+    pub async fn record<C: ConnectionTrait>(event: DomainEvent, db: &C) -> AppResult<sys_outbox_event::Model> {
+        let now = now();
+        let model = sys_outbox_event::ActiveModel {
+            aggregate_type: Set(event.aggregate_type),
+            aggregate_id: Set(event.aggregate_id),
+            event_type: Set(event.event_type),
+            topic: Set(event.topic),
+            payload: Set(event.payload),
+            status: Set(sys_outbox_event::STATUS_PENDING),
+            attempts: Set(0),
+            created_at: Set(now),
+            next_attempt_at: Set(now),
+            ..Default::default()
+        };
+        outbox_repository::insert_event(model, db).await
+    }
+
+    /// 启动后台中继任务
+    pub fn spawn_relay(config: OutboxConfig, db: &'static DatabaseConnection) {
+        if !config.enabled {
+            tracing::info!("outbox relay disabled");
+            return;
+        }
+        let handle = tokio::spawn(async move {
+            let mut shutdown = SHUTDOWN.subscribe();
+            let interval = Duration::from_millis(config.poll_interval);
+            tracing::info!(target = %config.target, "outbox relay started");
+            loop {
+                match Self::relay_once(&config, db).await {
+                    // 本批次已满时立即继续处理
+                    Ok(count) if count as u64 >= config.batch_size => continue,
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("outbox relay failed: {}", e),
+                }
+                tokio::select! {
+                    _ = shutdown.changed() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+            }
+            tracing::info!("outbox relay stopped");
+        });
+        *RELAY.lock().expect("outbox relay lock poisoned") = Some(handle);
+    }
+
+    /// 停止中继任务，等待当前批次处理完成
+    pub async fn shutdown(timeout: Duration) {
+        let _ = SHUTDOWN.send(true);
+        let handle = RELAY.lock().expect("outbox relay lock poisoned").take();
+        if let Some(handle) = handle
+            && tokio::time::timeout(timeout, handle).await.is_err()
+        {
+            tracing::warn!("outbox relay did not stop within {:?}", timeout);
+        }
+    }
+
+    /// 处理一批待发布事件，返回成功发布的数量
+    ///
+    /// 每批只包含各聚合最早的到期事件，后续事件在前序事件发布后的下一批处理
+    pub async fn relay_once(config: &OutboxConfig, db: &DatabaseConnection) -> AppResult<usize> {
+        let events = outbox_repository::query_pending_events(config.batch_size, now(), db).await?;
+        let mut published = 0;
+        for event in events {
+            let now = now();
+            match publish(config, &event).await {
+                Ok(_) => {
+                    outbox_repository::mark_published(event.id, now, db).await?;
+                    published += 1;
+                }
+                Err(e) => {
+                    let attempts = event.attempts + 1;
+                    let status = if attempts >= config.max_attempts {
+                        tracing::error!(event_id = event.id, attempts, "outbox event dead: {}", e);
+                        sys_outbox_event::STATUS_DEAD
+                    } else {
+                        tracing::warn!(event_id = event.id, attempts, "outbox publish failed: {}", e);
+                        sys_outbox_event::STATUS_PENDING
+                    };
+                    let next_attempt_at = now + retry_backoff(attempts);
+                    outbox_repository::mark_failed(event.id, attempts, status, next_attempt_at, e.to_string(), db)
+                        .await?;
+                }
+            }
+        }
+        Ok(published)
+    }
+
+    /// 分页查询卡住的事件，只用于排查，使用只读副本
+    pub async fn stuck_events(
+        config: &OutboxConfig,
+        cur_page: u64,
+        page_size: u64,
+    ) -> AppResult<(u64, Vec<sys_outbox_event::Model>)> {
+        let created_before = now() - chrono::Duration::seconds(config.stuck_after as i64);
+        outbox_repository::query_stuck_events(created_before, cur_page, page_size, db::postgres::read()).await
+    }
+
+    /// 将事件重置为待发布，立即参与下一轮中继
+    pub async fn retry_event(id: i64, db: &DatabaseConnection) -> AppResult<bool> {
+        outbox_repository::reset_event(id, now(), db).await
+    }
+}
+
+async fn publish(config: &OutboxConfig, event: &sys_outbox_event::Model) -> Result<()> {
+    let payload = serde_json::to_vec(&event.payload)?;
+    if config.target == TARGET_REDIS {
+        let stream = format!("{}{}", config.redis_stream_prefix, event.topic);
+        let mut conn = redis_manager::get_redis_connection().await?;
+        let _: String = deadpool_redis::redis::cmd("XADD")
+            .arg(&stream)
+            .arg("*")
+            .arg(HEADER_EVENT_ID)
+            .arg(event.id)
+            .arg(HEADER_EVENT_TYPE)
+            .arg(&event.event_type)
+            .arg(HEADER_AGGREGATE_TYPE)
+            .arg(&event.aggregate_type)
+            .arg("key")
+            .arg(&event.aggregate_id)
+            .arg("payload")
+            .arg(payload)
+            .query_async(&mut conn)
+            .await?;
+        return Ok(());
+    }
+    let message = BrokerMessage::new(&event.topic, Some(event.aggregate_id.clone()), payload)
+        .with_header(HEADER_EVENT_ID, event.id.to_string())
+        .with_header(HEADER_EVENT_TYPE, &event.event_type)
+        .with_header(HEADER_AGGREGATE_TYPE, &event.aggregate_type);
+    kafka::broker().publish(message).await
+}
+
+fn retry_backoff(attempts: i32) -> chrono::Duration {
+    let secs = 2i64.saturating_pow(attempts.clamp(0, 16) as u32).min(MAX_BACKOFF_SECS);
+    chrono::Duration::seconds(secs)
+}
+
+fn now() -> NaiveDateTime {
+    Local::now().naive_local()
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    use super::*;
+
+    fn event(id: i64, aggregate_id: &str, status: i16) -> sys_outbox_event::Model {
+        let now = now();
+        sys_outbox_event::Model {
+            id,
+            aggregate_type: "sys_user".into(),
+            aggregate_id: aggregate_id.into(),
+            event_type: "user.created".into(),
+            topic: "user-events".into(),
+            payload: serde_json::json!({"id": id}),
+            status,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            next_attempt_at: now,
+            published_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relay_publishes_due_aggregate_heads() {
+        let _ = kafka::init(None);
+        let published = sys_outbox_event::STATUS_PUBLISHED;
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![event(1, "u1", 0), event(3, "u2", 0)]])
+            .append_query_results([vec![event(1, "u1", published)], vec![event(3, "u2", published)]])
+            .into_connection();
+
+        let count = OutboxService::relay_once(&OutboxConfig::default(), &db).await.unwrap();
+        assert_eq!(count, 2);
+
+        let log = db.into_transaction_log();
+        let select = &log[0].statements()[0].sql;
+        // 退避中的事件不占用批次，存在未发布前序事件（含死信）的聚合不参与发布
+        assert!(select.contains(r#""next_attempt_at" <= $"#), "{}", select);
+        assert!(select.contains("NOT EXISTS"), "{}", select);
+        assert!(select.contains(r#""earlier"."status" <> $"#), "{}", select);
+        assert_eq!(log.len(), 3);
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::{Local, NaiveDateTime};
+use salvo::http::StatusError;
+use sea_orm::{ActiveValue::Set, DatabaseConnection, TransactionTrait};
+use subtle::ConstantTimeEq;
+
+use crate::common::api_response::AppResult;
+use crate::entities::permission::sys_api_key;
+use crate::hoops::auth::{self, Principal, PrincipalKind};
+use crate::models::permission::api_key_dto::CreateApiKeyReq;
+use crate::repository::permission::{api_key_repository, role_repository};
+use crate::utils;
+
+/// 密钥前缀标识
+const KEY_PREFIX_MARK: &str = "bwk_";
+const PREFIX_RANDOM_LEN: usize = 8;
+const SECRET_LEN: usize = 40;
+
+pub struct ApiKeyService;
+
+impl ApiKeyService {
+    /// 创建密钥，返回密钥信息与完整密钥（只在此时可见）；只能授予创建者自己拥有的授权范围
+    pub async fn create_api_key(
+        principal: &Principal,
+        req: CreateApiKeyReq,
+        db: &DatabaseConnection,
+    ) -> AppResult<(sys_api_key::Model, String)> {
+        let owner = principal.user_id.as_str();
+        let scopes = normalize_scopes(&req.scopes)?;
+        if let Some(scope) = scopes.iter().find(|scope| !principal.has_scope(scope)) {
+            return Err(StatusError::forbidden().brief(format!("无权授予授权范围: {}", scope)).into());
+        }
+        let now = now();
+        let expires_at = req
+            .expires_in_days
+            .map(|days| now + chrono::Duration::days(days));
+        let (key_prefix, api_key) = generate_key();
+        let model = sys_api_key::ActiveModel {
+            key_prefix: Set(key_prefix),
+            key_hash: Set(hash_key(&api_key)),
+            name: Set(req.name),
+            owner: Set(owner.to_string()),
+            scopes: Set(scopes.join(",")),
+            expires_at: Set(expires_at),
+            revoked: Set(0),
+            created_at: Set(now),
+            ..Default::default()
+        };
+        let created = api_key_repository::insert_api_key(model, db).await?;
+        tracing::info!(owner, key_prefix = %created.key_prefix, "api key created");
+        Ok((created, api_key))
+    }
+
+    /// 轮换密钥：新建同名同权限的密钥并吊销旧密钥
+    pub async fn rotate_api_key(
+        owner: &str,
+        id: i64,
+        db: &DatabaseConnection,
+    ) -> AppResult<(sys_api_key::Model, String)> {
+        let old = Self::get_owned_key(owner, id, db).await?;
+        if old.is_revoked() {
+            return Err(StatusError::bad_request().brief("密钥已吊销").into());
+        }
+        let now = now();
+        // 保持原有的有效期长度
+        let expires_at = old.expires_at.map(|exp| now + (exp - old.created_at));
+        let (key_prefix, api_key) = generate_key();
+
+        let txn = db.begin().await?;
+        let model = sys_api_key::ActiveModel {
+            key_prefix: Set(key_prefix),
+            key_hash: Set(hash_key(&api_key)),
+            name: Set(old.name.clone()),
+            owner: Set(old.owner.clone()),
+            scopes: Set(old.scopes.clone()),
+            expires_at: Set(expires_at),
+            revoked: Set(0),
+            created_at: Set(now),
+            rotated_from: Set(Some(old.id)),
+            ..Default::default()
+        };
+        let created = api_key_repository::insert_api_key(model, &txn).await?;
+        api_key_repository::revoke_api_key(old.id, &txn).await?;
+        txn.commit().await?;
+
+        tracing::info!(owner, old = %old.key_prefix, new = %created.key_prefix, "api key rotated");
+        Ok((created, api_key))
+    }
+
+    /// 吊销密钥
+    pub async fn revoke_api_key(owner: &str, id: i64, db: &DatabaseConnection) -> AppResult<()> {
+        let key = Self::get_owned_key(owner, id, db).await?;
+        api_key_repository::revoke_api_key(key.id, db).await?;
+        tracing::info!(owner, key_prefix = %key.key_prefix, "api key revoked");
+        Ok(())
+    }
+
+    pub async fn list_api_keys(owner: &str, db: &DatabaseConnection) -> AppResult<Vec<sys_api_key::Model>> {
+        api_key_repository::query_api_keys_by_owner(owner, db).await
+    }
+
+    /// 校验 `X-API-Key`，成功时返回调用方
+    pub async fn authenticate(api_key: &str, db: &'static DatabaseConnection) -> AppResult<Principal> {
+        let invalid = || StatusError::unauthorized().brief("API Key 无效");
+        let (key_prefix, _) = api_key.split_once('.').ok_or_else(invalid)?;
+        let key = api_key_repository::query_api_key_by_prefix(key_prefix, db)
+            .await?
+            .ok_or_else(invalid)?;
+        if !bool::from(key.key_hash.as_bytes().ct_eq(hash_key(api_key).as_bytes())) {
+            tracing::warn!(key_prefix, "api key hash mismatch");
+            return Err(invalid().into());
+        }
+        let now = now();
+        if key.is_revoked() {
+            return Err(StatusError::unauthorized().brief("API Key 已吊销").into());
+        }
+        if key.is_expired(now) {
+            return Err(StatusError::unauthorized().brief("API Key 已过期").into());
+        }
+
+        // 最近使用时间不影响认证结果，异步更新
+        let id = key.id;
+        tokio::spawn(async move {
+            let _ = api_key_repository::touch_last_used(id, now, db).await;
+        });
+
+        // 所属用户锁定或失效后密钥同时失效
+        auth::ensure_active(&key.owner, db).await?;
+        // 密钥的权限不超过所属用户当前的角色，用户失去角色后密钥同时失去对应授权范围
+        let roles = role_repository::query_user_role_codes(&key.owner, db).await?;
+        let owner = Principal::user(key.owner.clone(), roles);
+        let scopes = key.scope_list().into_iter().filter(|scope| owner.has_scope(scope)).collect();
+        Ok(Principal {
+            kind: PrincipalKind::ApiKey,
+            scopes,
+            user_id: key.owner,
+            roles: owner.roles,
+            api_key_id: Some(key.id),
+        })
+    }
+
+    async fn get_owned_key(owner: &str, id: i64, db: &DatabaseConnection) -> AppResult<sys_api_key::Model> {
+        match api_key_repository::query_api_key_by_id(id, db).await? {
+            Some(key) if key.owner == owner => Ok(key),
+            _ => Err(StatusError::not_found().brief("密钥不存在").into()),
+        }
+    }
+}
+
+/// 生成 `(前缀, 完整密钥)`，完整密钥格式为 `bwk_xxxxxxxx.<secret>`
+fn generate_key() -> (String, String) {
+    let key_prefix = format!("{}{}", KEY_PREFIX_MARK, utils::random_string(PREFIX_RANDOM_LEN));
+    let api_key = format!("{}.{}", key_prefix, utils::random_string(SECRET_LEN));
+    (key_prefix, api_key)
+}
+
+/// 密钥为高熵随机串，使用 SHA-256 摘要即可，避免每次请求执行 Argon2
+fn hash_key(api_key: &str) -> String {
+    utils::sha256_hex(api_key)
+}
+
+/// 去重排序，只允许 [`auth::SCOPES`] 中的授权范围
+fn normalize_scopes(scopes: &[String]) -> AppResult<Vec<String>> {
+    let mut scopes: Vec<&str> = scopes.iter().map(|s| s.trim()).filter(|s| !s.is_empty()).collect();
+    if let Some(scope) = scopes.iter().find(|scope| !auth::SCOPES.contains(scope)) {
+        return Err(StatusError::bad_request().brief(format!("不支持的授权范围: {}", scope)).into());
+    }
+    scopes.sort_unstable();
+    scopes.dedup();
+    Ok(scopes.into_iter().map(str::to_string).collect())
+}
+
+fn now() -> NaiveDateTime {
+    Local::now().naive_local()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_scopes_rejects_unknown() {
+        let scopes = vec![" audit:read".to_string(), "admin".to_string(), "audit:read".to_string()];
+        assert_eq!(normalize_scopes(&scopes).unwrap(), vec!["admin", "audit:read"]);
+        assert!(normalize_scopes(&["*".to_string()]).is_err());
+        assert!(normalize_scopes(&["kafka:write".to_string()]).is_err());
+    }
+}
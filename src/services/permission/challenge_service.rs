@@ -0,0 +1,256 @@
+//! 人机验证
+//!
+//! 支持进程内生成的图形验证码与轻量工作量证明两种方式，验证类型由配置决定。图形验证码的答案
+//! 以 HMAC 摘要保存在 Redis 中，读取 Redis 也无法得到答案；每个验证只能提交一次。
+//! 注册按配置总是要求验证，登录在连续失败达到阈值后要求验证。
+
+use anyhow::anyhow;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use captcha::Captcha;
+use captcha::filters::{Noise, Wave};
+use salvo::http::StatusError;
+use serde::{Deserialize, Serialize};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::common::api_response::AppResult;
+use crate::config::{self, CHALLENGE_CAPTCHA, CHALLENGE_POW};
+use crate::models::permission::challenge_dto::ChallengeRes;
+use crate::services::redis_service::RedisService;
+use crate::utils;
+
+const CAPTCHA_CHARS: u32 = 5;
+const CAPTCHA_WIDTH: u32 = 160;
+const CAPTCHA_HEIGHT: u32 = 60;
+const POW_PREFIX_LEN: usize = 16;
+/// 从 JWT 密钥派生验证码摘要密钥时使用的标签
+const CAPTCHA_KEY_INFO: &[u8] = b"captcha";
+
+/// Redis 中保存的验证答案
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredChallenge {
+    kind: String,
+    /// 图形验证码文字（小写）的 HMAC 摘要，见 [`answer_digest`]
+    answer: Option<String>,
+    prefix: Option<String>,
+    difficulty: Option<u32>,
+}
+
+pub struct ChallengeService;
+
+impl ChallengeService {
+    /// 下发配置类型的验证，不允许客户端选择更弱的类型
+    pub async fn issue() -> AppResult<ChallengeRes> {
+        let config = &config::get().challenge;
+        let kind = config.kind.as_str();
+        let challenge_id = utils::random_string(32);
+
+        let (stored, res) = match kind {
+            CHALLENGE_CAPTCHA => {
+                let (text, png) = tokio::task::spawn_blocking(generate_captcha)
+                    .await
+                    .map_err(anyhow::Error::from)?
+                    .ok_or_else(|| anyhow!("captcha generate failed"))?;
+                let stored = StoredChallenge {
+                    kind: kind.to_string(),
+                    answer: Some(answer_digest(&challenge_id, &text)),
+                    prefix: None,
+                    difficulty: None,
+                };
+                let res = ChallengeRes {
+                    challenge_id: challenge_id.clone(),
+                    kind: kind.to_string(),
+                    image: Some(format!("data:image/png;base64,{}", BASE64_STANDARD.encode(png))),
+                    prefix: None,
+                    difficulty: None,
+                    expires_in: config.ttl,
+                };
+                (stored, res)
+            }
+            CHALLENGE_POW => {
+                let prefix = utils::random_string(POW_PREFIX_LEN);
+                let stored = StoredChallenge {
+                    kind: kind.to_string(),
+                    answer: None,
+                    prefix: Some(prefix.clone()),
+                    difficulty: Some(config.pow_difficulty),
+                };
+                let res = ChallengeRes {
+                    challenge_id: challenge_id.clone(),
+                    kind: kind.to_string(),
+                    image: None,
+                    prefix: Some(prefix),
+                    difficulty: Some(config.pow_difficulty),
+                    expires_in: config.ttl,
+                };
+                (stored, res)
+            }
+            other => {
+                return Err(StatusError::bad_request().brief(format!("不支持的验证类型: {}", other)).into());
+            }
+        };
+
+        let value = serde_json::to_string(&stored).map_err(anyhow::Error::from)?;
+        RedisService::set(&challenge_key(&challenge_id), &value, Some(config.ttl)).await?;
+        Ok(res)
+    }
+
+    /// 校验验证答案，无论是否通过验证都会作废，失败后需重新获取
+    pub async fn verify(challenge_id: Option<&str>, answer: Option<&str>) -> AppResult<()> {
+        let (Some(challenge_id), Some(answer)) = (challenge_id, answer) else {
+            return Err(challenge_required());
+        };
+        // 取出即删除，并发提交同一验证时只有一个请求能读到
+        let Some(stored) = RedisService::get_del(&challenge_key(challenge_id)).await? else {
+            return Err(StatusError::bad_request().brief("人机验证已过期，请重新获取").into());
+        };
+        let stored: StoredChallenge = serde_json::from_str(&stored).map_err(anyhow::Error::from)?;
+
+        let passed = match (stored.kind.as_str(), stored.answer, stored.prefix, stored.difficulty) {
+            (CHALLENGE_CAPTCHA, Some(expected), _, _) => answer_digest(challenge_id, answer) == expected,
+            (CHALLENGE_POW, _, Some(prefix), Some(difficulty)) => verify_pow(&prefix, answer, difficulty),
+            _ => false,
+        };
+        if !passed {
+            return Err(StatusError::bad_request().brief("人机验证未通过").into());
+        }
+        Ok(())
+    }
+
+    /// 注册是否要求人机验证
+    pub fn registration_required() -> bool {
+        let config = &config::get().challenge;
+        config.enabled && config.registration
+    }
+
+    /// 登录是否要求人机验证，按用户与客户端 IP 分别统计失败次数；
+    /// 客户端 IP 应取自 [`crate::hoops::rate_limit::client_ip`]，只信任可信代理转发的地址
+    pub async fn login_required(user_id: &str, client_ip: &str) -> bool {
+        let config = &config::get().challenge;
+        if !config.enabled {
+            return false;
+        }
+        if config.login_after_failures == 0 {
+            return true;
+        }
+        for key in [user_failure_key(user_id), ip_failure_key(client_ip)] {
+            match RedisService::get(&key).await {
+                Ok(count) => {
+                    let count = count.and_then(|c| c.parse::<u64>().ok()).unwrap_or_default();
+                    if count >= config.login_after_failures {
+                        return true;
+                    }
+                }
+                Err(e) => tracing::warn!("read login failure count failed: {}", e),
+            }
+        }
+        false
+    }
+
+    /// 记录登录失败
+    pub async fn record_login_failure(user_id: &str, client_ip: &str) {
+        let config = &config::get().challenge;
+        if !config.enabled {
+            return;
+        }
+        for key in [user_failure_key(user_id), ip_failure_key(client_ip)] {
+            if let Err(e) = RedisService::incr(&key, config.failure_window).await {
+                tracing::warn!("record login failure failed: {}", e);
+            }
+        }
+    }
+
+    /// 登录成功后清除该用户的失败计数，IP 维度的计数按窗口自然过期
+    pub async fn clear_login_failures(user_id: &str) {
+        if config::get().challenge.enabled {
+            let _ = RedisService::del(&user_failure_key(user_id)).await;
+        }
+    }
+}
+
+fn generate_captcha() -> Option<(String, Vec<u8>)> {
+    Captcha::new()
+        .add_chars(CAPTCHA_CHARS)
+        .apply_filter(Noise::new(0.2))
+        .apply_filter(Wave::new(2.0, 10.0))
+        .view(CAPTCHA_WIDTH, CAPTCHA_HEIGHT)
+        .as_tuple()
+}
+
+/// 图形验证码答案的摘要，以派生密钥为 HMAC 密钥并绑定验证 id，忽略大小写与首尾空白
+fn answer_digest(challenge_id: &str, answer: &str) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&captcha_key()).expect("hmac accepts any key length");
+    mac.update(challenge_id.as_bytes());
+    mac.update(b":");
+    mac.update(answer.trim().to_lowercase().as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// 按 HKDF 从 JWT 密钥派生验证码专用密钥，摘要不会与令牌签名共用同一密钥
+fn captcha_key() -> [u8; 32] {
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, config::get().jwt.secret.as_bytes())
+        .expand(CAPTCHA_KEY_INFO, &mut key)
+        .expect("32 bytes is a valid hkdf output length");
+    key
+}
+
+/// 校验工作量证明：SHA-256(prefix + solution) 的前导零位数不少于 `difficulty`
+pub fn verify_pow(prefix: &str, solution: &str, difficulty: u32) -> bool {
+    let digest = Sha256::digest(format!("{}{}", prefix, solution).as_bytes());
+    leading_zero_bits(&digest) >= difficulty
+}
+
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in bytes {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+fn challenge_required() -> crate::AppError {
+    StatusError::precondition_required().brief("请先完成人机验证").into()
+}
+
+fn challenge_key(challenge_id: &str) -> String {
+    format!("challenge:{}", challenge_id)
+}
+
+fn user_failure_key(user_id: &str) -> String {
+    format!("login_failure:user:{}", user_id)
+}
+
+fn ip_failure_key(client_ip: &str) -> String {
+    format!("login_failure:ip:{}", client_ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leading_zero_bits() {
+        assert_eq!(leading_zero_bits(&[0x00, 0x0f, 0xff]), 12);
+        assert_eq!(leading_zero_bits(&[0x80]), 0);
+        assert_eq!(leading_zero_bits(&[0x00, 0x00]), 16);
+    }
+
+    #[test]
+    fn test_verify_pow() {
+        let prefix = "abcdefgh";
+        let solution = (0u64..)
+            .map(|n| n.to_string())
+            .find(|s| verify_pow(prefix, s, 8))
+            .unwrap();
+        let digest = Sha256::digest(format!("{}{}", prefix, solution).as_bytes());
+
+        assert_eq!(digest[0], 0);
+        assert!(!verify_pow(prefix, "not-a-solution", 32));
+    }
+}
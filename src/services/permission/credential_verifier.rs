@@ -0,0 +1,121 @@
+//! 登录凭据校验
+//!
+//! 定义统一的凭据校验抽象 [`CredentialVerifier`]，本地密码（Argon2）与
+//! LDAP / Active Directory 绑定（需启用 `ldap` 特性）各为一种实现。
+//! 用户的认证来源取 `sys_user.auth_source`，为空时使用 `auth.default_source`。
+
+use std::collections::{BTreeSet, HashMap};
+
+use async_trait::async_trait;
+use salvo::http::StatusError;
+
+use crate::common::api_response::AppResult;
+use crate::config::{self, AUTH_SOURCE_LDAP, AUTH_SOURCE_LOCAL};
+use crate::entities::permission::sys_user;
+use crate::utils;
+
+/// 凭据校验结果
+#[derive(Debug, Clone, Default)]
+pub struct VerifiedCredential {
+    /// 目录服务返回的用户组，`None` 表示该认证来源不提供组信息
+    pub groups: Option<Vec<String>>,
+}
+
+#[async_trait]
+pub trait CredentialVerifier: Send + Sync {
+    /// 校验用户密码，失败时返回 401
+    async fn verify(&self, user: &sys_user::Model, password: &str) -> AppResult<VerifiedCredential>;
+}
+
+/// 本地密码校验，比对 `sys_user.password` 中的 Argon2 摘要
+pub struct Argon2Verifier;
+
+#[async_trait]
+impl CredentialVerifier for Argon2Verifier {
+    async fn verify(&self, user: &sys_user::Model, password: &str) -> AppResult<VerifiedCredential> {
+        if utils::verify_password(password, &user.password).is_err() {
+            return Err(invalid_credentials());
+        }
+        Ok(VerifiedCredential::default())
+    }
+}
+
+/// 用户实际使用的认证来源
+pub fn auth_source(user: &sys_user::Model) -> &str {
+    user.auth_source
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .unwrap_or(&config::get().auth.default_source)
+}
+
+/// 根据认证来源选择校验器
+pub fn verifier_for(source: &str) -> AppResult<Box<dyn CredentialVerifier>> {
+    match source {
+        AUTH_SOURCE_LOCAL => Ok(Box::new(Argon2Verifier)),
+        AUTH_SOURCE_LDAP => ldap_verifier(),
+        other => {
+            tracing::error!("unsupported auth source: {}", other);
+            Err(StatusError::unauthorized().brief("不支持的认证方式").into())
+        }
+    }
+}
+
+#[cfg(feature = "ldap")]
+fn ldap_verifier() -> AppResult<Box<dyn CredentialVerifier>> {
+    let config = config::get()
+        .auth
+        .ldap
+        .clone()
+        .ok_or_else(|| StatusError::service_unavailable().brief("未配置 LDAP 认证"))?;
+    Ok(Box::new(super::ldap_verifier::LdapVerifier::new(config)))
+}
+
+#[cfg(not(feature = "ldap"))]
+fn ldap_verifier() -> AppResult<Box<dyn CredentialVerifier>> {
+    tracing::error!("ldap auth source requested but the ldap feature is not enabled");
+    Err(StatusError::service_unavailable().brief("未启用 LDAP 认证").into())
+}
+
+/// 按配置将用户组映射为角色编码，组 DN 不区分大小写，结果去重排序
+pub fn map_groups_to_roles(groups: &[String], group_roles: &HashMap<String, String>) -> Vec<String> {
+    let group_roles: HashMap<String, &String> = group_roles
+        .iter()
+        .map(|(group, role)| (group.to_lowercase(), role))
+        .collect();
+    groups
+        .iter()
+        .filter_map(|group| group_roles.get(&group.to_lowercase()))
+        .map(|role| role.to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+pub fn invalid_credentials() -> crate::AppError {
+    StatusError::unauthorized().brief("用户账号或者密码不正确").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::map_groups_to_roles;
+
+    #[test]
+    fn test_map_groups_to_roles() {
+        let group_roles = HashMap::from([
+            ("CN=Admins,OU=Groups,DC=example,DC=com".to_string(), "admin".to_string()),
+            ("CN=Ops,OU=Groups,DC=example,DC=com".to_string(), "ops".to_string()),
+            ("CN=Oncall,OU=Groups,DC=example,DC=com".to_string(), "ops".to_string()),
+        ]);
+        let groups = vec![
+            "cn=admins,ou=groups,dc=example,dc=com".to_string(),
+            "CN=Ops,OU=Groups,DC=example,DC=com".to_string(),
+            "CN=Oncall,OU=Groups,DC=example,DC=com".to_string(),
+            "CN=Others,OU=Groups,DC=example,DC=com".to_string(),
+        ];
+
+        assert_eq!(map_groups_to_roles(&groups, &group_roles), vec!["admin", "ops"]);
+        assert!(map_groups_to_roles(&[], &group_roles).is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+use salvo::http::StatusError;
+use crate::common::api_response::AppResult;
+use crate::db;
+use crate::models::permission::employee_dto::{EmployeeLookupReq, EmployeeRes};
+use crate::repository::permission::employee_repository;
+use crate::utils::error_util;
+
+pub struct EmployeeService;
+
+impl EmployeeService {
+    /// 按工号或手机号查询员工，工号优先；员工档案很少变更，使用只读副本
+    pub async fn lookup(req: &EmployeeLookupReq) -> AppResult<EmployeeRes> {
+        let db = db::postgres::read();
+        let emp_no = req.emp_no.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        let mobile = req.mobile.as_deref().map(str::trim).filter(|s| !s.is_empty());
+        let employee = match (emp_no, mobile) {
+            (Some(emp_no), _) => employee_repository::query_employee_by_emp_no(emp_no, db).await?,
+            (None, Some(mobile)) => employee_repository::query_employee_by_mobile(mobile, db).await?,
+            (None, None) => return Err(StatusError::bad_request().brief("请输入工号或手机号").into()),
+        };
+        let employee = employee.ok_or_else(|| StatusError::not_found().brief("员工不存在"))?;
+        let mobile = employee.mobileno_plain().map_err(|e| {
+            tracing::error!(empid = employee.empid, "decrypt employee mobile failed: {}", e);
+            error_util::system_error()
+        })?;
+        Ok(EmployeeRes::new(employee, mobile))
+    }
+}
@@ -0,0 +1,109 @@
+//! LDAP / Active Directory 凭据校验
+//!
+//! 先用服务账号（或匿名）按工号查找用户 DN，再以用户 DN 和输入的密码绑定，
+//! 绑定成功即认证通过，同时读取用户所属组用于角色映射。
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ldap3::{ldap_escape, Ldap, LdapConnAsync, LdapConnSettings, LdapError, Scope, SearchEntry};
+use salvo::http::StatusError;
+
+use crate::common::api_response::AppResult;
+use crate::config::LdapConfig;
+use crate::entities::permission::sys_user;
+
+use super::credential_verifier::{invalid_credentials, CredentialVerifier, VerifiedCredential};
+
+/// LDAP 绑定失败时的结果码: invalidCredentials
+const RC_INVALID_CREDENTIALS: u32 = 49;
+
+pub struct LdapVerifier {
+    config: LdapConfig,
+}
+
+impl LdapVerifier {
+    pub fn new(config: LdapConfig) -> Self {
+        Self { config }
+    }
+
+    async fn connect(&self) -> Result<Ldap, LdapError> {
+        let settings = LdapConnSettings::new()
+            .set_conn_timeout(Duration::from_secs(self.config.timeout))
+            .set_starttls(self.config.starttls);
+        let (conn, ldap) = LdapConnAsync::with_settings(settings, &self.config.url).await?;
+        ldap3::drive!(conn);
+        Ok(ldap)
+    }
+
+    /// 校验成功返回用户所属组，用户不存在或密码错误返回 `None`
+    async fn bind_user(
+        &self,
+        ldap: &mut Ldap,
+        username: &str,
+        password: &str,
+    ) -> Result<Option<Vec<String>>, LdapError> {
+        if let (Some(bind_dn), Some(bind_password)) =
+            (&self.config.bind_dn, &self.config.bind_password)
+        {
+            ldap.simple_bind(bind_dn, bind_password).await?.success()?;
+        }
+
+        let filter = self.config.user_filter.replace("{username}", &ldap_escape(username));
+        let (entries, _) = ldap
+            .search(
+                &self.config.base_dn,
+                Scope::Subtree,
+                &filter,
+                vec![self.config.group_attribute.as_str()],
+            )
+            .await?
+            .success()?;
+        // 查不到或匹配到多个用户都视为认证失败
+        let mut entries = entries.into_iter();
+        let (Some(entry), None) = (entries.next(), entries.next()) else {
+            tracing::warn!(user_id = %username, "ldap user not found or not unique");
+            return Ok(None);
+        };
+        let entry = SearchEntry::construct(entry);
+
+        let result = ldap.simple_bind(&entry.dn, password).await?;
+        if result.rc == RC_INVALID_CREDENTIALS {
+            return Ok(None);
+        }
+        result.success()?;
+
+        let groups = entry
+            .attrs
+            .get(&self.config.group_attribute)
+            .cloned()
+            .unwrap_or_default();
+        Ok(Some(groups))
+    }
+}
+
+#[async_trait]
+impl CredentialVerifier for LdapVerifier {
+    async fn verify(&self, user: &sys_user::Model, password: &str) -> AppResult<VerifiedCredential> {
+        // 空密码会被目录服务当作匿名绑定而返回成功，必须先拒绝
+        if password.is_empty() {
+            return Err(invalid_credentials());
+        }
+
+        let mut ldap = self.connect().await.map_err(|e| {
+            tracing::error!("ldap connect failed: {}", e);
+            StatusError::service_unavailable().brief("目录服务不可用")
+        })?;
+        let result = self.bind_user(&mut ldap, &user.user_id, password).await;
+        let _ = ldap.unbind().await;
+
+        match result {
+            Ok(Some(groups)) => Ok(VerifiedCredential { groups: Some(groups) }),
+            Ok(None) => Err(invalid_credentials()),
+            Err(e) => {
+                tracing::error!(user_id = %user.user_id, "ldap bind failed: {}", e);
+                Err(StatusError::service_unavailable().brief("目录服务不可用").into())
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! TOTP 两步验证
+//!
+//! 开启流程：生成密钥暂存 Redis -> 用户扫码后提交验证码 -> 写入 `sys_user` 并返回恢复码。
+//! 登录流程：密码校验通过后只返回一次性票据，提交验证码或恢复码后才签发令牌。
+//! 验证码错误次数按用户累计而不是按票据，重新输入密码获取新票据不会重置次数。
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use salvo::http::StatusError;
+use sea_orm::DatabaseConnection;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::common::api_response::AppResult;
+use crate::config;
+use crate::entities::permission::sys_user;
+use crate::models::permission::mfa_dto::MfaEnrollRes;
+use crate::repository::permission::user_repository;
+use crate::services::permission::user_service::UserService;
+use crate::services::redis_service::RedisService;
+use crate::utils::{self, crypto_util, error_util};
+use crate::AppError;
+
+const DIGITS: usize = 6;
+const STEP: u64 = 30;
+/// 允许前后各一个时间步的时钟偏差
+const SKEW: u64 = 1;
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LEN: usize = 10;
+/// 待确认密钥的保存时间（秒）
+const ENROLL_TTL: usize = 600;
+
+pub struct MfaService;
+
+impl MfaService {
+    /// 生成新的 TOTP 密钥，确认前暂存在 Redis 中
+    pub async fn enroll(user: &sys_user::Model) -> AppResult<MfaEnrollRes> {
+        if user.is_mfa_enabled() {
+            return Err(StatusError::bad_request().brief("已开启两步验证").into());
+        }
+        // 密钥需加密保存，未配置密钥时直接拒绝，避免确认时才失败
+        if !crypto_util::is_configured() {
+            return Err(StatusError::bad_request().brief("系统未配置加密密钥，暂不支持两步验证").into());
+        }
+        let Secret::Encoded(secret) = Secret::generate_secret().to_encoded() else {
+            return Err(anyhow!("totp secret encode failed").into());
+        };
+        let totp = build_totp(&secret, &user.user_id)?;
+        RedisService::set(&enroll_key(&user.user_id), &secret, Some(ENROLL_TTL)).await?;
+
+        Ok(MfaEnrollRes {
+            secret,
+            otpauth_uri: totp.get_url(),
+        })
+    }
+
+    /// 校验验证码并开启两步验证，返回明文恢复码
+    pub async fn activate(user: &sys_user::Model, code: &str, db: &DatabaseConnection) -> AppResult<Vec<String>> {
+        if user.is_mfa_enabled() {
+            return Err(StatusError::bad_request().brief("已开启两步验证").into());
+        }
+        let key = enroll_key(&user.user_id);
+        let secret = RedisService::get(&key)
+            .await?
+            .ok_or_else(|| StatusError::bad_request().brief("绑定已过期，请重新绑定"))?;
+        let totp = build_totp(&secret, &user.user_id)?;
+        if matching_step(&totp, code, unix_now()).is_none() {
+            return Err(StatusError::bad_request().brief("验证码不正确").into());
+        }
+
+        let recovery_codes = generate_recovery_codes();
+        let hashed = recovery_codes
+            .iter()
+            .map(|code| hash_recovery_code(code))
+            .collect::<Vec<_>>()
+            .join(",");
+        user_repository::update_user_mfa(user.auto_id, 1, Some(secret), Some(hashed), db).await?;
+        let _ = RedisService::del(&key).await;
+        tracing::info!(user_id = %user.user_id, "mfa enabled");
+        Ok(recovery_codes)
+    }
+
+    /// 管理员重置用户的两步验证，用户需重新绑定
+    pub async fn reset(user_id: &str, db: &DatabaseConnection) -> AppResult<sys_user::Model> {
+        let user = user_repository::query_user_by_user_id(user_id, db)
+            .await?
+            .ok_or_else(|| StatusError::not_found().brief("用户不存在"))?;
+        user_repository::update_user_mfa(user.auto_id, 0, None, None, db).await?;
+        let _ = RedisService::del(&enroll_key(user_id)).await;
+        tracing::info!(user_id = %user_id, "mfa reset");
+        Ok(user)
+    }
+
+    /// 密码校验通过后签发一次性票据，两步验证锁定期间拒绝签发
+    pub async fn create_ticket(user_id: &str) -> AppResult<String> {
+        Self::ensure_not_locked(user_id).await?;
+        let ticket = utils::random_string(48);
+        RedisService::set(&ticket_key(&ticket), user_id, Some(config::get().auth.mfa_ticket_ttl)).await?;
+        Ok(ticket)
+    }
+
+    /// 校验票据与验证码（或恢复码），通过后返回用户
+    pub async fn verify_ticket(ticket: &str, code: &str, db: &DatabaseConnection) -> AppResult<sys_user::Model> {
+        let key = ticket_key(ticket);
+        let user_id = RedisService::get(&key)
+            .await?
+            .ok_or_else(|| StatusError::unauthorized().brief("登录已过期，请重新登录"))?;
+        let user = UserService::get_user_by_id(&user_id, db).await?;
+        UserService::verify_user_status(&user).await?;
+        if let Err(e) = Self::ensure_not_locked(&user.user_id).await {
+            let _ = RedisService::del(&key).await;
+            return Err(e);
+        }
+
+        if Self::verify_code(&user, code, db).await? {
+            let _ = RedisService::del(&key).await;
+            let _ = RedisService::del(&failures_key(&user.user_id)).await;
+            return Ok(user);
+        }
+
+        // 错误次数达到上限后作废票据并锁定，锁定期间重新登录也无法获取票据
+        let auth = &config::get().auth;
+        let failures = RedisService::incr(&failures_key(&user.user_id), auth.mfa_lockout_ttl).await?;
+        tracing::warn!(user_id = %user.user_id, failures, "mfa code rejected");
+        if failures >= auth.mfa_max_attempts {
+            let _ = RedisService::del(&key).await;
+            return Err(locked_error());
+        }
+        Err(StatusError::unauthorized().brief("验证码不正确").into())
+    }
+
+    /// 连续错误次数达到上限时返回错误
+    async fn ensure_not_locked(user_id: &str) -> AppResult<()> {
+        let failures = RedisService::get(&failures_key(user_id))
+            .await?
+            .and_then(|count| count.parse::<u64>().ok())
+            .unwrap_or_default();
+        if failures >= config::get().auth.mfa_max_attempts {
+            tracing::warn!(user_id = %user_id, failures, "mfa locked");
+            return Err(locked_error());
+        }
+        Ok(())
+    }
+
+    async fn verify_code(user: &sys_user::Model, code: &str, db: &DatabaseConnection) -> AppResult<bool> {
+        let Some(secret) = user.mfa_secret.as_deref().filter(|_| user.is_mfa_enabled()) else {
+            tracing::error!(user_id = %user.user_id, "mfa ticket issued for user without mfa");
+            return Err(error_util::system_error());
+        };
+
+        if code.len() == DIGITS && code.chars().all(|c| c.is_ascii_digit()) {
+            let secret = crypto_util::decrypt(secret)?;
+            let totp = build_totp(&secret, &user.user_id)?;
+            let Some(step) = matching_step(&totp, code, unix_now()) else {
+                return Ok(false);
+            };
+            // 同一时间步的验证码只能使用一次
+            return user_repository::update_mfa_last_step(user.auto_id, step as i64, db).await;
+        }
+
+        let Some(stored) = user.mfa_recovery_codes.as_deref() else {
+            return Ok(false);
+        };
+        let hashed = hash_recovery_code(code);
+        let remaining: Vec<&str> = stored.split(',').filter(|h| !h.is_empty()).collect();
+        if !remaining.contains(&hashed.as_str()) {
+            return Ok(false);
+        }
+        let left = remaining
+            .iter()
+            .filter(|h| **h != hashed)
+            .copied()
+            .collect::<Vec<_>>();
+        tracing::info!(user_id = %user.user_id, left = left.len(), "mfa recovery code used");
+        user_repository::update_mfa_recovery_codes(user.auto_id, stored, left.join(","), db).await
+    }
+}
+
+fn build_totp(secret: &str, account: &str) -> AppResult<TOTP> {
+    let secret = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(|e| anyhow!("invalid totp secret: {:?}", e))?;
+    let totp = TOTP::new(
+        Algorithm::SHA1,
+        DIGITS,
+        SKEW as u8,
+        STEP,
+        secret,
+        Some(config::get().auth.mfa_issuer.clone()),
+        account.to_string(),
+    )
+    .map_err(|e| anyhow!("build totp failed: {:?}", e))?;
+    Ok(totp)
+}
+
+/// 返回与验证码匹配的时间步，允许前后各 [`SKEW`] 个时间步的偏差
+fn matching_step(totp: &TOTP, code: &str, now: u64) -> Option<u64> {
+    let current = now / STEP;
+    (current.saturating_sub(SKEW)..=current + SKEW).find(|step| totp.generate(step * STEP) == code)
+}
+
+/// 生成形如 `abcde-12345` 的恢复码
+fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let code = utils::random_string(RECOVERY_CODE_LEN).to_lowercase();
+            format!("{}-{}", &code[..RECOVERY_CODE_LEN / 2], &code[RECOVERY_CODE_LEN / 2..])
+        })
+        .collect()
+}
+
+/// 恢复码为高熵随机串，忽略大小写与分隔符后取 SHA-256 摘要
+fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    utils::sha256_hex(&normalized)
+}
+
+fn enroll_key(user_id: &str) -> String {
+    format!("mfa:enroll:{}", user_id)
+}
+
+fn ticket_key(ticket: &str) -> String {
+    format!("mfa:ticket:{}", ticket)
+}
+
+fn failures_key(user_id: &str) -> String {
+    format!("mfa:failures:{}", user_id)
+}
+
+fn locked_error() -> AppError {
+    StatusError::too_many_requests()
+        .brief("验证码错误次数过多，请稍后再试")
+        .into()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use totp_rs::{Algorithm, TOTP};
+
+    use super::*;
+
+    #[test]
+    fn test_matching_step_allows_clock_skew() {
+        // RFC 6238 附录 B 的 SHA1 测试密钥
+        let totp = TOTP::new_unchecked(Algorithm::SHA1, 6, 1, 30, b"12345678901234567890".to_vec(), None, String::new());
+        let now = 59;
+        let code = totp.generate(now);
+
+        assert_eq!(code, "287082");
+        assert_eq!(matching_step(&totp, &code, now), Some(1));
+        assert_eq!(matching_step(&totp, &code, now + 30), Some(1));
+        assert_eq!(matching_step(&totp, &code, now + 60), None);
+    }
+
+    #[test]
+    fn test_recovery_code_hash_ignores_format() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert_eq!(codes[0].len(), RECOVERY_CODE_LEN + 1);
+        assert_eq!(hash_recovery_code("ABCDE-12345"), hash_recovery_code("abcde12345"));
+    }
+}
@@ -1 +1,9 @@
-pub mod user_service;
\ No newline at end of file
+pub mod api_key_service;
+pub mod user_service;
+pub mod oidc_service;
+pub mod credential_verifier;
+#[cfg(feature = "ldap")]
+mod ldap_verifier;
+pub mod mfa_service;
+pub mod challenge_service;
+pub mod employee_service;
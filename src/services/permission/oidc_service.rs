@@ -0,0 +1,474 @@
+//! OpenID Connect 单点登录
+//!
+//! 采用授权码 + PKCE 流程：登录时生成 state、nonce 与 code_verifier 保存到 Redis，
+//! 回调时用授权码换取 ID Token，按身份提供方的 JWKS 校验签名、签发方、受众与 nonce，
+//! 再按 (签发方, subject) 将外部身份映射为 `sys_user`。
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use salvo::http::StatusError;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::OnceCell;
+
+use crate::common::api_response::AppResult;
+use crate::config::OidcConfig;
+use crate::entities::permission::sys_user;
+use crate::models::permission::user_dto::CreateReq;
+use crate::repository::permission::{identity_repository, user_repository};
+use crate::services::permission::user_service::UserService;
+use crate::services::redis_service::RedisService;
+use crate::telemetry;
+use crate::utils;
+
+const STATE_KEY_PREFIX: &str = "oidc:state:";
+
+static PROVIDER: OnceLock<Arc<dyn IdentityProvider>> = OnceLock::new();
+
+/// 身份提供方发现文档中使用到的字段
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenResponse {
+    pub id_token: String,
+}
+
+/// ID Token 声明
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub email: Option<String>,
+    /// 身份提供方是否验证过邮箱，未验证的邮箱不能用于绑定已有用户
+    #[serde(default)]
+    pub email_verified: Option<bool>,
+    pub nonce: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// 保存在 Redis 中的登录请求上下文
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingLogin {
+    nonce: String,
+    code_verifier: String,
+}
+
+/// 身份提供方访问抽象，测试中可替换为本地模拟实现
+#[async_trait]
+pub trait IdentityProvider: Send + Sync {
+    async fn metadata(&self) -> Result<ProviderMetadata>;
+
+    async fn exchange_code(&self, token_endpoint: &str, form: &[(&str, &str)]) -> Result<TokenResponse>;
+
+    async fn jwks(&self, jwks_uri: &str) -> Result<JwkSet>;
+}
+
+/// 基于 HTTP 的身份提供方，发现文档只加载一次
+pub struct HttpIdentityProvider {
+    client: reqwest::Client,
+    issuer: String,
+    metadata: OnceCell<ProviderMetadata>,
+}
+
+impl HttpIdentityProvider {
+    pub fn new(issuer: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            issuer: issuer.trim_end_matches('/').to_string(),
+            metadata: OnceCell::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl IdentityProvider for HttpIdentityProvider {
+    async fn metadata(&self) -> Result<ProviderMetadata> {
+        let metadata = self
+            .metadata
+            .get_or_try_init(|| async {
+                let url = format!("{}/.well-known/openid-configuration", self.issuer);
+                let metadata: ProviderMetadata = telemetry::inject_http(self.client.get(&url))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                Ok::<_, anyhow::Error>(metadata)
+            })
+            .await?;
+        Ok(metadata.clone())
+    }
+
+    async fn exchange_code(&self, token_endpoint: &str, form: &[(&str, &str)]) -> Result<TokenResponse> {
+        let response = telemetry::inject_http(self.client.post(token_endpoint).form(form))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("token endpoint returned {}: {}", status, body));
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn jwks(&self, jwks_uri: &str) -> Result<JwkSet> {
+        Ok(telemetry::inject_http(self.client.get(jwks_uri))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+pub struct OidcService;
+
+impl OidcService {
+    /// 设置身份提供方，未设置时按配置使用 HTTP 实现
+    #[allow(dead_code)]
+    pub fn set_provider(provider: Arc<dyn IdentityProvider>) {
+        let _ = PROVIDER.set(provider);
+    }
+
+    fn provider(config: &OidcConfig) -> Arc<dyn IdentityProvider> {
+        PROVIDER
+            .get_or_init(|| Arc::new(HttpIdentityProvider::new(&config.issuer)))
+            .clone()
+    }
+
+    /// 生成授权地址，并将 state 对应的 nonce 与 code_verifier 保存到 Redis
+    pub async fn authorize_url(config: &OidcConfig) -> AppResult<String> {
+        let metadata = Self::provider(config).metadata().await.map_err(|e| {
+            tracing::error!("oidc discovery failed: {}", e);
+            StatusError::service_unavailable().brief("身份提供方不可用")
+        })?;
+        let state = utils::random_string(32);
+        let pending = PendingLogin {
+            nonce: utils::random_string(32),
+            code_verifier: utils::random_string(64),
+        };
+        let value = serde_json::to_string(&pending).map_err(anyhow::Error::from)?;
+        RedisService::set(&state_key(&state), &value, Some(config.state_ttl)).await?;
+
+        let mut url = reqwest::Url::parse(&metadata.authorization_endpoint).map_err(anyhow::Error::from)?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &config.client_id)
+            .append_pair("redirect_uri", &config.redirect_uri)
+            .append_pair("scope", &config.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("nonce", &pending.nonce)
+            .append_pair("code_challenge", &code_challenge(&pending.code_verifier))
+            .append_pair("code_challenge_method", "S256");
+        Ok(url.into())
+    }
+
+    /// 处理身份提供方回调，返回映射后的本地用户
+    pub async fn handle_callback(
+        config: &OidcConfig,
+        code: &str,
+        state: &str,
+        db: &DatabaseConnection,
+    ) -> AppResult<sys_user::Model> {
+        // state 只能使用一次
+        let key = state_key(state);
+        let pending = RedisService::get(&key)
+            .await?
+            .ok_or_else(|| StatusError::bad_request().brief("登录请求已过期，请重新登录"))?;
+        let _ = RedisService::del(&key).await;
+        let pending: PendingLogin = serde_json::from_str(&pending).map_err(anyhow::Error::from)?;
+
+        let provider = Self::provider(config);
+        let claims = exchange_and_verify(provider.as_ref(), config, code, &pending).await.map_err(|e| {
+            tracing::error!("oidc callback failed: {}", e);
+            StatusError::unauthorized().brief("单点登录校验失败")
+        })?;
+        Self::map_user(config, &claims, db).await
+    }
+
+    /// 将外部身份映射为本地用户
+    ///
+    /// 已绑定的身份按 (签发方, subject) 查找；首次登录时按配置自动开通，不按邮箱、工号等声明绑定已有用户，
+    /// 本地邮箱未经验证且不唯一，按邮箱匹配会被用于接管已有账号。
+    async fn map_user(config: &OidcConfig, claims: &IdTokenClaims, db: &DatabaseConnection) -> AppResult<sys_user::Model> {
+        if let Some(identity) = identity_repository::query_identity(&claims.iss, &claims.sub, db).await? {
+            return user_repository::query_user_by_user_id(&identity.user_id, db)
+                .await?
+                .ok_or_else(|| StatusError::unauthorized().brief("该账号尚未开通").into());
+        }
+
+        let verified_email = claims.email.as_deref().filter(|_| claims.email_verified == Some(true));
+        let user = Self::provision_user(config, claims, verified_email, db).await?;
+        identity_repository::insert_identity(&claims.iss, &claims.sub, &user.user_id, db).await?;
+        tracing::info!(sub = %claims.sub, user_id = %user.user_id, "oidc identity linked");
+        Ok(user)
+    }
+
+    /// 按工号声明自动开通用户，工号已被占用时拒绝，避免接管已有账号
+    async fn provision_user(
+        config: &OidcConfig,
+        claims: &IdTokenClaims,
+        verified_email: Option<&str>,
+        db: &DatabaseConnection,
+    ) -> AppResult<sys_user::Model> {
+        let employee_no = claims
+            .extra
+            .get(&config.employee_claim)
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        let Some(employee_no) = employee_no.filter(|_| config.auto_provision) else {
+            tracing::warn!(sub = %claims.sub, "oidc subject has no local user");
+            return Err(StatusError::unauthorized().brief("该账号尚未开通").into());
+        };
+        if user_repository::query_user_by_user_id(&employee_no, db).await?.is_some() {
+            tracing::warn!(sub = %claims.sub, user_id = %employee_no, "oidc provision conflicts with existing user");
+            return Err(StatusError::conflict().brief("工号已被其他账号使用，请联系管理员绑定").into());
+        }
+        tracing::info!(sub = %claims.sub, user_id = %employee_no, "auto provision user from oidc");
+        // 单点登录用户不使用本地密码，设置随机密码
+        let req = CreateReq {
+            user_id: employee_no,
+            user_name: String::new(),
+            password: utils::random_string(32),
+            phone: None,
+            email: verified_email.map(str::to_string),
+            email_password: None,
+            remark: Some("OIDC自动开通".to_string()),
+            image_url: None,
+            locked: None,
+            is_valid: None,
+            challenge_id: None,
+            challenge_answer: None,
+        };
+        UserService::create_user(req, db).await
+    }
+}
+
+
+async fn exchange_and_verify(
+    provider: &dyn IdentityProvider,
+    config: &OidcConfig,
+    code: &str,
+    pending: &PendingLogin,
+) -> Result<IdTokenClaims> {
+    let metadata = provider.metadata().await?;
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", config.redirect_uri.as_str()),
+        ("client_id", config.client_id.as_str()),
+        ("code_verifier", pending.code_verifier.as_str()),
+    ];
+    if let Some(secret) = &config.client_secret {
+        form.push(("client_secret", secret.as_str()));
+    }
+    let token = provider.exchange_code(&metadata.token_endpoint, &form).await?;
+    verify_id_token(provider, &metadata, config, &token.id_token, &pending.nonce).await
+}
+
+/// 校验 ID Token 的签名、签发方、受众、有效期与 nonce
+pub async fn verify_id_token(
+    provider: &dyn IdentityProvider,
+    metadata: &ProviderMetadata,
+    config: &OidcConfig,
+    id_token: &str,
+    nonce: &str,
+) -> Result<IdTokenClaims> {
+    let header = jsonwebtoken::decode_header(id_token)?;
+    let kid = header.kid.ok_or_else(|| anyhow!("id token missing kid"))?;
+    let jwks = provider.jwks(&metadata.jwks_uri).await?;
+    let jwk = jwks.find(&kid).ok_or_else(|| anyhow!("unknown signing key: {}", kid))?;
+    let key = DecodingKey::from_jwk(jwk)?;
+
+    // 算法以密钥声明或配置为准，不信任令牌头中的 alg
+    let algorithms = match jwk.common.key_algorithm {
+        Some(algorithm) => vec![algorithm.to_string().parse::<Algorithm>()?],
+        None => config
+            .algorithms
+            .iter()
+            .map(|algorithm| algorithm.parse::<Algorithm>())
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    if !algorithms.contains(&header.alg) {
+        return Err(anyhow!("id token algorithm {:?} is not allowed", header.alg));
+    }
+    let mut validation = Validation::new(header.alg);
+    validation.algorithms = algorithms;
+    validation.set_audience(&[&config.client_id]);
+    validation.set_issuer(&[&metadata.issuer]);
+    let claims = jsonwebtoken::decode::<IdTokenClaims>(id_token, &key, &validation)?.claims;
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(anyhow!("id token nonce mismatch"));
+    }
+    Ok(claims)
+}
+
+fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+fn state_key(state: &str) -> String {
+    format!("{}{}", STATE_KEY_PREFIX, state)
+}
+
+#[cfg(test)]
+pub mod mock {
+    //! 本地模拟身份提供方，使用 HS256 对称密钥签发 ID Token
+
+    use super::*;
+    use jsonwebtoken::jwk::{AlgorithmParameters, CommonParameters, Jwk, OctetKeyParameters, OctetKeyType};
+    use jsonwebtoken::{EncodingKey, Header};
+
+    pub const ISSUER: &str = "http://mock-idp.local";
+    const KID: &str = "mock-key";
+    const SECRET: &[u8] = b"mock-identity-provider-secret";
+
+    pub struct MockIdentityProvider {
+        pub claims: serde_json::Value,
+    }
+
+    impl MockIdentityProvider {
+        pub fn sign(claims: &serde_json::Value) -> String {
+            let mut header = Header::new(jsonwebtoken::Algorithm::HS256);
+            header.kid = Some(KID.to_string());
+            jsonwebtoken::encode(&header, claims, &EncodingKey::from_secret(SECRET)).unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl IdentityProvider for MockIdentityProvider {
+        async fn metadata(&self) -> Result<ProviderMetadata> {
+            Ok(ProviderMetadata {
+                issuer: ISSUER.to_string(),
+                authorization_endpoint: format!("{}/authorize", ISSUER),
+                token_endpoint: format!("{}/token", ISSUER),
+                jwks_uri: format!("{}/jwks", ISSUER),
+            })
+        }
+
+        async fn exchange_code(&self, _token_endpoint: &str, form: &[(&str, &str)]) -> Result<TokenResponse> {
+            if !form.iter().any(|(k, v)| *k == "code" && *v == "valid-code") {
+                return Err(anyhow!("invalid_grant"));
+            }
+            Ok(TokenResponse {
+                id_token: Self::sign(&self.claims),
+            })
+        }
+
+        async fn jwks(&self, _jwks_uri: &str) -> Result<JwkSet> {
+            Ok(JwkSet {
+                keys: vec![Jwk {
+                    common: CommonParameters {
+                        key_id: Some(KID.to_string()),
+                        ..Default::default()
+                    },
+                    algorithm: AlgorithmParameters::OctetKey(OctetKeyParameters {
+                        key_type: OctetKeyType::Octet,
+                        value: URL_SAFE_NO_PAD.encode(SECRET),
+                    }),
+                }],
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::mock::{ISSUER, MockIdentityProvider};
+    use super::*;
+
+    fn config() -> OidcConfig {
+        OidcConfig {
+            issuer: ISSUER.to_string(),
+            client_id: "base_web".to_string(),
+            client_secret: None,
+            redirect_uri: "http://127.0.0.1:8008/rust/auth/oidc/callback".to_string(),
+            scopes: vec!["openid".to_string()],
+            employee_claim: "employee_no".to_string(),
+            auto_provision: false,
+            algorithms: vec!["HS256".to_string()],
+            state_ttl: 300,
+        }
+    }
+
+    fn provider(nonce: &str, aud: &str) -> MockIdentityProvider {
+        let exp = chrono::Utc::now().timestamp() + 300;
+        MockIdentityProvider {
+            claims: json!({
+                "iss": ISSUER,
+                "aud": aud,
+                "sub": "external-1",
+                "exp": exp,
+                "nonce": nonce,
+                "email": "zhangsan@example.com",
+                "employee_no": "S0001"
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exchange_and_verify_id_token() {
+        let provider = provider("n-1", "base_web");
+        let pending = PendingLogin {
+            nonce: "n-1".to_string(),
+            code_verifier: "verifier".to_string(),
+        };
+        let claims = exchange_and_verify(&provider, &config(), "valid-code", &pending)
+            .await
+            .unwrap();
+        assert_eq!(claims.sub, "external-1");
+        assert_eq!(claims.extra["employee_no"], "S0001");
+    }
+
+    #[tokio::test]
+    async fn test_reject_wrong_nonce_and_audience() {
+        let pending = PendingLogin {
+            nonce: "expected".to_string(),
+            code_verifier: "verifier".to_string(),
+        };
+        let wrong_nonce = provider("other", "base_web");
+        assert!(exchange_and_verify(&wrong_nonce, &config(), "valid-code", &pending).await.is_err());
+
+        let wrong_aud = provider("expected", "another_client");
+        assert!(exchange_and_verify(&wrong_aud, &config(), "valid-code", &pending).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reject_unpinned_algorithm() {
+        let pending = PendingLogin {
+            nonce: "n-1".to_string(),
+            code_verifier: "verifier".to_string(),
+        };
+        let config = OidcConfig {
+            algorithms: vec!["RS256".to_string()],
+            ..config()
+        };
+        assert!(exchange_and_verify(&provider("n-1", "base_web"), &config, "valid-code", &pending).await.is_err());
+    }
+
+    #[test]
+    fn test_code_challenge_s256() {
+        // RFC 7636 附录 B 示例
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
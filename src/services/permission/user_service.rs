@@ -1,11 +1,30 @@
+use std::collections::HashMap;
+
 use salvo::http::StatusError;
-use sea_orm::DatabaseConnection;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use sha2::{Digest, Sha256};
+use crate::{config, db};
+use crate::mail::{self, MailMessage, MailSender};
 use crate::utils::error_util;
 use crate::{
-    common::api_response::AppResult, entities::permission::sys_user,
-    models::permission::user_dto::CreateReq, repository::permission::user_repository, utils,
+    common::api_response::AppResult, entities::permission::{sys_user, sys_user_role},
+    hoops::jwt,
+    models::permission::user_dto::{CreateReq, UserCreatedEvent, UserPageReq},
+    repository::permission::user_repository, utils,
 };
-use crate::repository::permission::employee_repository;
+use crate::repository::permission::{employee_repository, role_repository};
+use crate::services::permission::credential_verifier;
+use crate::services::outbox_service::{DomainEvent, OutboxService};
+use crate::services::redis_service::RedisService;
+
+/// 用户领域事件发布的主题
+pub const USER_EVENTS_TOPIC: &str = "user_events";
+/// 同一收件人在限流窗口内最多收到的欢迎邮件数
+const WELCOME_MAIL_PER_RECIPIENT: u64 = 1;
+/// 同一来源 IP 在限流窗口内最多触发的欢迎邮件数
+const WELCOME_MAIL_PER_IP: u64 = 5;
+/// 欢迎邮件限流窗口（秒）
+const WELCOME_MAIL_WINDOW: usize = 24 * 3600;
 
 pub struct UserService;
 
@@ -49,21 +68,114 @@ impl UserService {
         Ok(())
     }
 
-    /// 校验用户账号密码
-    pub async fn verify_user_credentials(password: &str, input_password: &str) -> AppResult<()> {
-        // 密码校验业务逻辑
-        if utils::verify_password(input_password, password)
-            .err()
-            .is_some()
+    /// 校验用户账号密码，按用户的认证来源选择本地密码或 LDAP 校验
+    pub async fn verify_user_credentials(
+        user: &sys_user::Model,
+        input_password: &str,
+        db: &DatabaseConnection,
+    ) -> AppResult<()> {
+        let source = credential_verifier::auth_source(user);
+        let verified = credential_verifier::verifier_for(source)?
+            .verify(user, input_password)
+            .await?;
+
+        // 目录服务返回了用户组时同步组映射的角色，同步失败不影响登录
+        if let Some(groups) = verified.groups
+            && let Err(e) = Self::sync_group_roles(&user.user_id, &groups, db).await
         {
-            return Err(StatusError::unauthorized()
-                .brief("用户账号或者密码不正确")
-                .into());
+            tracing::error!(user_id = %user.user_id, "sync ldap group roles failed: {:?}", e);
+        }
+        Ok(())
+    }
+
+    /// 以用户组映射的角色替换该用户此前由 LDAP 同步的角色，手动分配的角色不受影响；
+    /// 认证时按 `sys_user_role` 加载角色，同步结果在下一次请求生效
+    async fn sync_group_roles(user_id: &str, groups: &[String], db: &DatabaseConnection) -> AppResult<()> {
+        let group_roles = config::get()
+            .auth
+            .ldap
+            .as_ref()
+            .map(|ldap| credential_verifier::map_groups_to_roles(groups, &ldap.group_roles))
+            .unwrap_or_default();
+
+        let txn = db.begin().await?;
+        let role_codes = role_repository::query_existing_role_codes(&group_roles, &txn).await?;
+        if role_codes.len() < group_roles.len() {
+            tracing::warn!(user_id = %user_id, "some mapped roles do not exist: {:?}", group_roles);
+        }
+        role_repository::delete_user_roles_by_source(user_id, sys_user_role::SOURCE_LDAP, &txn).await?;
+        role_repository::insert_user_roles(user_id, &role_codes, sys_user_role::SOURCE_LDAP, &txn).await?;
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// 账号密码登录认证：查询用户、校验状态与凭据
+    pub async fn authenticate(
+        user_id: &str,
+        password: &str,
+        db: &DatabaseConnection,
+    ) -> AppResult<sys_user::Model> {
+        let user = Self::get_user_by_id(user_id, db).await?;
+        Self::verify_user_status(&user).await?;
+        Self::verify_user_credentials(&user, password, db).await?;
+        Ok(user)
+    }
+
+    /// 分页查询用户，允许读到略有延迟的数据，使用只读副本
+    pub async fn query_page(req: &UserPageReq) -> AppResult<(u64, Vec<sys_user::Model>)> {
+        user_repository::query_user_page(req, db::postgres::read()).await
+    }
+
+    /// 锁定或解锁用户，返回修改前的用户；锁定后立即无法访问，此前签发的令牌在解锁后也不再有效
+    pub async fn set_locked(user_id: &str, locked: bool, db: &DatabaseConnection) -> AppResult<sys_user::Model> {
+        let user = user_repository::query_user_by_user_id(user_id, db)
+            .await?
+            .ok_or_else(|| StatusError::not_found().brief("用户不存在"))?;
+        user_repository::update_user_locked(user.auto_id, i32::from(locked), db).await?;
+        if locked {
+            Self::revoke_tokens(user_id).await?;
         }
+        tracing::info!(user_id = %user_id, locked, "user lock state changed");
+        Ok(user)
+    }
+
+    /// 签发登录令牌并记录登录态，返回带 `Bearer ` 前缀的令牌
+    /// 作废用户此前签发的全部令牌，记录保留到最后一个令牌过期
+    pub async fn revoke_tokens(user_id: &str) -> AppResult<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let ttl = config::get().jwt.expiry.max(1) as usize;
+        RedisService::set(&revoked_key(user_id), &now.to_string(), Some(ttl)).await?;
         Ok(())
     }
 
+    /// 令牌是否已作废：签发时间不晚于作废时间，均精确到毫秒，作废后同一秒内重新登录签发的令牌仍然有效。
+    /// 读取失败时按未作废处理，锁定状态仍由数据库校验
+    pub async fn is_token_revoked(user_id: &str, issued_at: i64) -> bool {
+        match RedisService::get(&revoked_key(user_id)).await {
+            Ok(revoked_at) => revoked_at
+                .and_then(|at| at.parse::<i64>().ok())
+                .is_some_and(|revoked_at| issued_at <= revoked_at),
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, "read token revocation failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// 签发登录令牌并记录登录态，返回带 `Bearer ` 前缀的令牌
+    pub async fn issue_token(user_id: &str) -> AppResult<String> {
+        let (token, exp) = jwt::get_token(user_id)?;
+        let token = format!("Bearer {}", token);
+
+        let _ = RedisService::set_login(&token, user_id, Some(exp as usize)).await;
+        Ok(token)
+    }
+
     pub async fn create_user(mut data: CreateReq, db: &DatabaseConnection) -> AppResult<sys_user::Model> {
+        // 邮箱密码需加密保存，未配置密钥时直接拒绝，避免写入时才失败
+        if data.email_password.as_deref().is_some_and(|p| !p.is_empty()) && !utils::crypto_util::is_configured() {
+            return Err(StatusError::bad_request().brief("系统未配置加密密钥，暂不支持保存邮箱密码").into());
+        }
         // 查询输入的工号是否存在
         let employee = employee_repository::query_employee_by_emp_no(&data.user_id, db).await?
             .ok_or_else(|| StatusError::internal_server_error().brief("请使用正确的工号进行注册"))?;
@@ -74,17 +186,100 @@ impl UserService {
         }
 
         // 设置用户名和手机号
-        data.user_name = employee.empname.unwrap_or_default();
+        data.user_name = employee.empname.clone().unwrap_or_default();
         if data.phone.is_none() {
-            data.phone = employee.mobileno;
+            data.phone = employee.mobileno_plain().map_err(|e| {
+                tracing::error!("decrypt employee mobileno error: {}", e);
+                error_util::system_error()
+            })?;
         }
         // 密码哈希处理
         data.password = utils::hash_password(&data.password)?;
 
+        // 创建用户与用户创建事件在同一事务中写入，事件由发件箱中继异步发布
+        let txn = db.begin().await?;
+
         // 创建用户，依赖数据库层面的唯一性约束来处理重复用户的情况
-        user_repository::create_user(data, db).await.map_err(|e| {
+        let created = user_repository::create_user(data, &txn).await.map_err(|e| {
             tracing::error!("create_user error: {}", e);
             e
-        })
+        })?;
+
+        let event = DomainEvent::new(
+            "sys_user",
+            &created.user_id,
+            "user.created",
+            USER_EVENTS_TOPIC,
+            &UserCreatedEvent::from(&created),
+        )?;
+        OutboxService::record(event, &txn).await?;
+
+        txn.commit().await?;
+        Ok(created)
     }
-}
\ No newline at end of file
+
+    /// 为用户手动分配角色，已拥有该角色时不做修改
+    pub async fn grant_role(user_id: &str, role_code: &str, db: &DatabaseConnection) -> AppResult<()> {
+        user_repository::query_user_by_user_id(user_id, db)
+            .await?
+            .ok_or_else(|| StatusError::not_found().brief("用户不存在"))?;
+        let role_codes = role_repository::query_existing_role_codes(&[role_code.to_string()], db).await?;
+        if role_codes.is_empty() {
+            return Err(StatusError::not_found().brief("角色不存在").into());
+        }
+        role_repository::insert_user_roles(user_id, &role_codes, sys_user_role::SOURCE_MANUAL, db).await?;
+        tracing::info!(user_id = %user_id, role_code = %role_code, "role granted");
+        Ok(())
+    }
+
+    /// 发送欢迎邮件，失败不影响注册结果
+    ///
+    /// 注册时填写的邮箱未经验证，按收件人与来源 IP 限流，避免注册接口被用来向任意邮箱发信；
+    /// 读取计数失败时不发送
+    pub async fn send_welcome_mail(user: &sys_user::Model, client_ip: &str) {
+        let Some(email) = user.email.clone().filter(|e| !e.is_empty()) else {
+            return;
+        };
+        let limits = [
+            (welcome_recipient_key(&email), WELCOME_MAIL_PER_RECIPIENT),
+            (welcome_ip_key(client_ip), WELCOME_MAIL_PER_IP),
+        ];
+        for (key, limit) in limits {
+            match RedisService::incr(&key, WELCOME_MAIL_WINDOW).await {
+                Ok(count) if count <= limit => {}
+                Ok(_) => {
+                    tracing::warn!(user_id = %user.user_id, client_ip = %client_ip, "welcome mail rate limited");
+                    return;
+                }
+                Err(e) => {
+                    tracing::warn!(user_id = %user.user_id, "check welcome mail limit failed: {}", e);
+                    return;
+                }
+            }
+        }
+
+        let vars = HashMap::from([
+            ("user_id", user.user_id.clone()),
+            ("user_name", user.user_name.clone()),
+        ]);
+        if let Err(e) = MailMessage::from_template("welcome", &vars, vec![email], MailSender::System)
+            .and_then(mail::enqueue)
+        {
+            tracing::warn!(user_id = %user.user_id, "enqueue welcome mail failed: {}", e);
+        }
+    }
+}
+
+fn revoked_key(user_id: &str) -> String {
+    format!("token_revoked:{}", user_id)
+}
+
+/// 收件人按摘要计数，Redis 中不保存邮箱明文
+fn welcome_recipient_key(email: &str) -> String {
+    let digest = Sha256::digest(email.trim().to_lowercase().as_bytes());
+    format!("welcome_mail:to:{:x}", digest)
+}
+
+fn welcome_ip_key(client_ip: &str) -> String {
+    format!("welcome_mail:ip:{}", client_ip)
+}
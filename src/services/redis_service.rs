@@ -6,6 +6,7 @@ use anyhow::Result;
 use deadpool_redis::{redis::AsyncCommands, Pool};
 
 use crate::cache::redis_manager;
+use crate::metrics;
 
 /// Redis服务结构体
 pub struct RedisService;
@@ -30,15 +31,15 @@ impl RedisService {
     /// 设置登录信息，默认过期时间为8小时
     pub async fn set_login(key: &str, value: &str, mut ttl: Option<usize>) -> Result<()> {
         let mut conn = redis_manager::get_redis_connection().await?;
-        if ttl == None {
-            ttl = Some(60 * 60 * 8 as usize);
+        if ttl.is_none() {
+            ttl = Some(60 * 60 * 8_usize);
         }
         set_inner(&mut conn, key, value, ttl).await
     }
 
     /// 依赖指定连接池设置键值对，便于注入 AppState。
     pub async fn set_with_pool(pool: &Pool, key: &str, value: &str, ttl: Option<usize>) -> Result<()> {
-        let mut conn = pool.get().await?;
+        let mut conn = redis_manager::get_connection_from(pool).await?;
         set_inner(&mut conn, key, value, ttl).await
     }
 
@@ -58,10 +59,16 @@ impl RedisService {
 
     /// 依赖指定连接池获取值。
     pub async fn get_with_pool(pool: &Pool, key: &str) -> Result<Option<String>> {
-        let mut conn = pool.get().await?;
+        let mut conn = redis_manager::get_connection_from(pool).await?;
         get_inner(&mut conn, key).await
     }
 
+    /// 获取并删除指定键，用于只能使用一次的值
+    pub async fn get_del(key: &str) -> Result<Option<String>> {
+        let mut conn = redis_manager::get_redis_connection().await?;
+        get_del_inner(&mut conn, key).await
+    }
+
     /// 删除指定键
     ///
     /// # Arguments
@@ -78,7 +85,7 @@ impl RedisService {
 
     /// 依赖指定连接池删除键。
     pub async fn del_with_pool(pool: &Pool, key: &str) -> Result<u32> {
-        let mut conn = pool.get().await?;
+        let mut conn = redis_manager::get_connection_from(pool).await?;
         del_inner(&mut conn, key).await
     }
 
@@ -91,13 +98,15 @@ impl RedisService {
     /// # Returns
     ///
     /// 如果键存在返回true，否则返回false
+    #[allow(dead_code)]
     pub async fn exists(key: &str) -> Result<bool> {
         let mut conn = redis_manager::get_redis_connection().await?;
         exists_inner(&mut conn, key).await
     }
 
+    #[allow(dead_code)]
     pub async fn exists_with_pool(pool: &Pool, key: &str) -> Result<bool> {
-        let mut conn = pool.get().await?;
+        let mut conn = redis_manager::get_connection_from(pool).await?;
         exists_inner(&mut conn, key).await
     }
 
@@ -111,19 +120,58 @@ impl RedisService {
     /// # Returns
     ///
     /// 如果设置成功返回true，否则返回false
+    #[allow(dead_code)]
     pub async fn expire(key: &str, ttl: usize) -> Result<bool> {
         let mut conn = redis_manager::get_redis_connection().await?;
         expire_inner(&mut conn, key, ttl).await
     }
 
+    #[allow(dead_code)]
     pub async fn expire_with_pool(pool: &Pool, key: &str, ttl: usize) -> Result<bool> {
-        let mut conn = pool.get().await?;
+        let mut conn = redis_manager::get_connection_from(pool).await?;
         expire_inner(&mut conn, key, ttl).await
     }
 
+    /// 按模式增量遍历键，返回下一次遍历的游标（为0表示遍历结束）与本批键
+    #[tracing::instrument(name = "redis SCAN", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
+    pub async fn scan_with_pool(pool: &Pool, pattern: &str, cursor: u64, count: usize) -> Result<(u64, Vec<String>)> {
+        let mut conn = redis_manager::get_connection_from(pool).await?;
+        let _timer = metrics::redis_timer("SCAN");
+        let result: (u64, Vec<String>) = deadpool_redis::redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut conn)
+            .await?;
+        Ok(result)
+    }
+
+    /// 检查 Redis 连接
+    #[tracing::instrument(name = "redis PING", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
+    pub async fn ping() -> Result<()> {
+        let mut conn = redis_manager::get_redis_connection().await?;
+        let _timer = metrics::redis_timer("PING");
+        let _: String = deadpool_redis::redis::cmd("PING").query_async(&mut conn).await?;
+        Ok(())
+    }
+
+    /// 计数加一并返回新值，首次计数时设置过期时间（秒）
+    pub async fn incr(key: &str, ttl: usize) -> Result<u64> {
+        let mut conn = redis_manager::get_redis_connection().await?;
+        let result = incr_inner(&mut conn, key).await?;
+        if result == 1 {
+            expire_inner(&mut conn, key, ttl).await?;
+        }
+        Ok(result)
+    }
+
 }
 
+#[tracing::instrument(name = "redis SET", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
 async fn set_inner(conn: &mut deadpool_redis::Connection, key: &str, value: &str, ttl: Option<usize>) -> Result<()> {
+    let _timer = metrics::redis_timer("SET");
     if let Some(expire) = ttl {
         let _: () = conn.set_ex(key, value, expire as u64).await?;
     } else {
@@ -132,22 +180,44 @@ async fn set_inner(conn: &mut deadpool_redis::Connection, key: &str, value: &str
     Ok(())
 }
 
+#[tracing::instrument(name = "redis GET", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
 async fn get_inner(conn: &mut deadpool_redis::Connection, key: &str) -> Result<Option<String>> {
+    let _timer = metrics::redis_timer("GET");
     let result: Option<String> = conn.get(key).await?;
     Ok(result)
 }
 
+#[tracing::instrument(name = "redis GETDEL", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
+async fn get_del_inner(conn: &mut deadpool_redis::Connection, key: &str) -> Result<Option<String>> {
+    let _timer = metrics::redis_timer("GETDEL");
+    let result: Option<String> = conn.get_del(key).await?;
+    Ok(result)
+}
+
+#[tracing::instrument(name = "redis DEL", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
 async fn del_inner(conn: &mut deadpool_redis::Connection, key: &str) -> Result<u32> {
+    let _timer = metrics::redis_timer("DEL");
     let result: u32 = conn.del(key).await?;
     Ok(result)
 }
 
+#[tracing::instrument(name = "redis EXISTS", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
 async fn exists_inner(conn: &mut deadpool_redis::Connection, key: &str) -> Result<bool> {
+    let _timer = metrics::redis_timer("EXISTS");
     let result: bool = conn.exists(key).await?;
     Ok(result)
 }
 
+#[tracing::instrument(name = "redis INCR", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
+async fn incr_inner(conn: &mut deadpool_redis::Connection, key: &str) -> Result<u64> {
+    let _timer = metrics::redis_timer("INCR");
+    let result: u64 = conn.incr(key, 1).await?;
+    Ok(result)
+}
+
+#[tracing::instrument(name = "redis EXPIRE", skip_all, fields(otel.kind = "client", db.system.name = "redis"))]
 async fn expire_inner(conn: &mut deadpool_redis::Connection, key: &str, ttl: usize) -> Result<bool> {
+    let _timer = metrics::redis_timer("EXPIRE");
     let result: bool = conn.expire(key, ttl as i64).await?;
     Ok(result)
 }
@@ -10,7 +10,7 @@ mod tests {
     #[tokio::test]
     async fn test_redis_connection() {
         // 初始化配置
-        config::init();
+        config::init_test();
         
         // 初始化Redis连接池
         let init_result = crate::cache::redis_manager::init_redis_pool();
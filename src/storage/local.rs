@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use super::{validate_key, Storage};
+use crate::config::StorageConfig;
+use crate::utils;
+
+/// 本地下载地址的路由前缀，需与 `file_router` 保持一致
+pub const DOWNLOAD_PATH: &str = "/rust/file/download";
+
+/// 本地文件系统存储，下载地址使用 HMAC-SHA256 签名
+pub struct LocalStorage {
+    root: PathBuf,
+    public_base_url: String,
+    signing_key: Vec<u8>,
+}
+
+impl LocalStorage {
+    pub fn new(config: &StorageConfig) -> Self {
+        let signing_key = if config.signing_key.is_empty() {
+            tracing::warn!("storage.signing_key not configured, download urls become invalid after restart");
+            utils::random_string(32).into_bytes()
+        } else {
+            config.signing_key.clone().into_bytes()
+        };
+        Self {
+            root: PathBuf::from(&config.local_dir),
+            public_base_url: config.public_base_url.trim_end_matches('/').to_string(),
+            signing_key,
+        }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    fn mac(&self, key: &str, expires: u64) -> Hmac<Sha256> {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.signing_key).expect("hmac accepts any key length");
+        mac.update(format!("{}\n{}", key, expires).as_bytes());
+        mac
+    }
+
+    fn sign(&self, key: &str, expires: u64) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(self.mac(key, expires).finalize().into_bytes())
+    }
+
+    fn verify_at(&self, key: &str, expires: u64, signature: &str, now: u64) -> bool {
+        if expires < now {
+            return false;
+        }
+        let Ok(signature) = BASE64_URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        self.mac(key, expires).verify_slice(&signature).is_ok()
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, content: Vec<u8>, _content_type: &str) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // 先写临时文件再改名，避免读到写了一半的文件
+        let tmp = path.with_extension(format!("tmp{}", utils::random_string(8)));
+        tokio::fs::write(&tmp, content).await?;
+        if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(tokio::fs::try_exists(self.path(key)?).await?)
+    }
+
+    async fn signed_url(&self, key: &str, ttl: u64) -> Result<String> {
+        validate_key(key)?;
+        let expires = unix_now()
+            .checked_add(ttl)
+            .ok_or_else(|| anyhow!("invalid url ttl"))?;
+        Ok(format!(
+            "{}{}/{}?expires={}&signature={}",
+            self.public_base_url,
+            DOWNLOAD_PATH,
+            key,
+            expires,
+            self.sign(key, expires)
+        ))
+    }
+
+    fn verify_signature(&self, key: &str, expires: u64, signature: &str) -> bool {
+        self.verify_at(key, expires, signature, unix_now())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage() -> LocalStorage {
+        LocalStorage {
+            root: PathBuf::from("data/files"),
+            public_base_url: String::new(),
+            signing_key: b"test-signing-key".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_signature_expires_and_binds_key() {
+        let storage = storage();
+        let signature = storage.sign("files/ab/ab.png", 1000);
+
+        assert!(storage.verify_at("files/ab/ab.png", 1000, &signature, 999));
+        assert!(!storage.verify_at("files/ab/ab.png", 1000, &signature, 1001));
+        assert!(!storage.verify_at("files/ab/other.png", 1000, &signature, 999));
+        assert!(!storage.verify_at("files/ab/ab.png", 2000, &signature, 999));
+        assert!(!storage.verify_at("files/ab/ab.png", 1000, "not-base64!", 999));
+    }
+}
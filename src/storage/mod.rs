@@ -0,0 +1,100 @@
+//! 文件存储
+//!
+//! 定义统一的存储抽象 [`Storage`]，本地文件系统与 S3 兼容对象存储（需启用 `s3` 特性）
+//! 各为一种实现，按 `storage.driver` 在启动时选择。对象以相对路径形式的 key 标识，
+//! 下载统一通过带有效期的签名地址完成。
+
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use crate::config::{StorageConfig, STORAGE_S3};
+
+mod local;
+#[cfg(feature = "s3")]
+mod s3;
+
+pub use local::LocalStorage;
+
+static STORAGE: OnceLock<Box<dyn Storage>> = OnceLock::new();
+
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// 写入对象，已存在时覆盖
+    async fn put(&self, key: &str, content: Vec<u8>, content_type: &str) -> Result<()>;
+
+    /// 读取对象，不存在时返回 `None`
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    async fn exists(&self, key: &str) -> Result<bool>;
+
+    /// 生成有效期为 `ttl` 秒的下载地址
+    async fn signed_url(&self, key: &str, ttl: u64) -> Result<String>;
+
+    /// 校验由本服务签发的下载地址，对象存储自行校验签名时始终返回 `false`
+    fn verify_signature(&self, _key: &str, _expires: u64, _signature: &str) -> bool {
+        false
+    }
+}
+
+/// 按配置初始化存储
+pub fn init(config: &StorageConfig) -> Result<()> {
+    let storage: Box<dyn Storage> = match config.driver.as_str() {
+        STORAGE_S3 => s3_storage(config)?,
+        _ => Box::new(LocalStorage::new(config)),
+    };
+    tracing::info!(driver = %config.driver, "storage initialized");
+    let _ = STORAGE.set(storage);
+    Ok(())
+}
+
+pub fn get() -> Result<&'static dyn Storage> {
+    STORAGE
+        .get()
+        .map(|storage| storage.as_ref())
+        .ok_or_else(|| anyhow!("storage not initialized"))
+}
+
+#[cfg(feature = "s3")]
+fn s3_storage(config: &StorageConfig) -> Result<Box<dyn Storage>> {
+    let s3_config = config
+        .s3
+        .as_ref()
+        .ok_or_else(|| anyhow!("storage.s3 not configured"))?;
+    Ok(Box::new(s3::S3Storage::new(s3_config)?))
+}
+
+#[cfg(not(feature = "s3"))]
+fn s3_storage(_config: &StorageConfig) -> Result<Box<dyn Storage>> {
+    Err(anyhow!("storage.driver is s3 but the s3 feature is not enabled"))
+}
+
+/// 校验对象 key，只允许相对路径且不能包含 `..` 等特殊路径段
+pub fn validate_key(key: &str) -> Result<()> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && !key.contains('\\')
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("invalid storage key: {}", key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("files/ab/abcdef.png").is_ok());
+        assert!(validate_key("../etc/passwd").is_err());
+        assert!(validate_key("files/../../etc/passwd").is_err());
+        assert!(validate_key("/etc/passwd").is_err());
+        assert!(validate_key("files//a.png").is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use s3::creds::Credentials;
+use s3::{Bucket, Region};
+
+use super::{validate_key, Storage};
+use crate::config::S3Config;
+
+/// S3 兼容对象存储，下载地址使用预签名 URL
+pub struct S3Storage {
+    bucket: Box<Bucket>,
+}
+
+impl S3Storage {
+    pub fn new(config: &S3Config) -> Result<Self> {
+        let region = Region::Custom {
+            region: config.region.clone(),
+            endpoint: config.endpoint.clone(),
+        };
+        let credentials = Credentials::new(
+            Some(&config.access_key),
+            Some(&config.secret_key),
+            None,
+            None,
+            None,
+        )?;
+        let mut bucket = Bucket::new(&config.bucket, region, credentials)?;
+        if config.path_style {
+            bucket = bucket.with_path_style();
+        }
+        Ok(Self { bucket })
+    }
+}
+
+#[async_trait]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, content: Vec<u8>, content_type: &str) -> Result<()> {
+        validate_key(key)?;
+        let response = self
+            .bucket
+            .put_object_with_content_type(key, &content, content_type)
+            .await?;
+        match response.status_code() {
+            200..=299 => Ok(()),
+            code => Err(anyhow!("s3 put object failed, status: {}", code)),
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        validate_key(key)?;
+        let response = self.bucket.get_object(key).await?;
+        match response.status_code() {
+            200..=299 => Ok(Some(response.to_vec())),
+            404 => Ok(None),
+            code => Err(anyhow!("s3 get object failed, status: {}", code)),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        validate_key(key)?;
+        match self.bucket.head_object(key).await {
+            Ok((_, code)) => Ok((200..300).contains(&code)),
+            Err(s3::error::S3Error::HttpFailWithBody(404, _)) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn signed_url(&self, key: &str, ttl: u64) -> Result<String> {
+        validate_key(key)?;
+        // S3 预签名最长 7 天
+        let ttl = ttl.min(7 * 24 * 3600) as u32;
+        Ok(self.bucket.presign_get(key, ttl, None).await?)
+    }
+}
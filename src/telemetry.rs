@@ -0,0 +1,229 @@
+//! OpenTelemetry 链路追踪
+//!
+//! 开启 `trace.enabled` 后，tracing 的 span 经 OTLP/HTTP 导出到采集端。入站请求与 Kafka
+//! 消息从 W3C `traceparent` 头恢复上游上下文，出站 HTTP 请求与 Kafka 消息写入当前上下文。
+//! SQL 的耗时由 SeaORM 回调给出，直接用 OpenTelemetry 接口按实际起止时间补录 span。
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
+use opentelemetry::trace::{Span as _, SpanKind, Status, TraceContextExt, Tracer as _, TracerProvider as _};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider, Tracer};
+use opentelemetry_sdk::Resource;
+use salvo::http::HeaderMap;
+use sea_orm::metric::Info;
+use tracing::Subscriber;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::TraceConfig;
+use crate::metrics;
+
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// 创建导出到 OTLP 采集端的 tracing 层，未开启时返回 None
+pub fn layer<S>(config: &TraceConfig) -> Result<Option<OpenTelemetryLayer<S, Tracer>>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    if !config.enabled {
+        return Ok(None);
+    }
+    let provider = build_provider(config)?;
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+    let _ = PROVIDER.set(provider);
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+fn build_provider(config: &TraceConfig) -> Result<SdkTracerProvider> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&config.endpoint)
+        .with_timeout(Duration::from_millis(config.timeout))
+        .build()?;
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sampling_ratio,
+        ))))
+        .with_resource(Resource::builder().with_service_name(config.service_name.clone()).build())
+        .build())
+}
+
+/// 导出剩余的 span，进程退出前调用；导出使用阻塞 HTTP 客户端，需在阻塞线程中执行
+pub fn shutdown() {
+    if let Some(provider) = PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        eprintln!("trace provider shutdown error: {}", e);
+    }
+}
+
+/// 从 HTTP 请求头恢复上游链路上下文
+pub fn extract_http(headers: &HeaderMap) -> Context {
+    TraceContextPropagator::new().extract(&HttpHeaders(headers))
+}
+
+/// 从消息头恢复生产者的链路上下文
+pub fn extract_message(headers: &[(String, String)]) -> Context {
+    TraceContextPropagator::new().extract(&MessageHeaders(headers))
+}
+
+/// 当前 span 的链路上下文，以 `traceparent` 等头的形式返回；不在链路中时为空
+pub fn current_headers() -> HashMap<String, String> {
+    inject(&tracing::Span::current().context())
+}
+
+/// 为出站 HTTP 请求写入当前链路上下文
+pub fn inject_http(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    current_headers()
+        .into_iter()
+        .fold(builder, |builder, (name, value)| builder.header(name, value))
+}
+
+fn inject(cx: &Context) -> HashMap<String, String> {
+    let mut headers = HashMap::new();
+    TraceContextPropagator::new().inject_context(cx, &mut headers);
+    headers
+}
+
+/// span 所属链路的 trace id，未开启导出时为 None
+pub fn trace_id(span: &tracing::Span) -> Option<String> {
+    let cx = span.context();
+    let span_context = cx.span().span_context().clone();
+    span_context.is_valid().then(|| span_context.trace_id().to_string())
+}
+
+/// 按 SeaORM 回调给出的耗时补录一条 SQL span，父 span 为当前请求
+pub fn record_query(info: &Info<'_>) {
+    let Some(provider) = PROVIDER.get() else {
+        return;
+    };
+    let tracer = provider.tracer("sea-orm");
+    let parent = tracing::Span::current().context();
+    let operation = metrics::sql_operation(&info.statement.sql);
+    let end = SystemTime::now();
+    let mut span = tracer
+        .span_builder(format!("db {}", operation))
+        .with_kind(SpanKind::Client)
+        .with_start_time(end - info.elapsed)
+        .with_attributes(vec![
+            KeyValue::new("db.system.name", "postgresql"),
+            KeyValue::new("db.operation.name", operation),
+            // 只记录带占位符的语句，不记录参数值
+            KeyValue::new("db.query.text", info.statement.sql.clone()),
+        ])
+        .start_with_context(&tracer, &parent);
+    if info.failed {
+        span.set_status(Status::error("query failed"));
+    }
+    span.end_with_timestamp(end);
+}
+
+struct HttpHeaders<'a>(&'a HeaderMap);
+
+impl Extractor for HttpHeaders<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+struct MessageHeaders<'a>(&'a [(String, String)]);
+
+impl Extractor for MessageHeaders<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.iter().map(|(name, _)| name.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let mut headers = HeaderMap::new();
+        headers.insert("traceparent", TRACEPARENT.parse().unwrap());
+        let cx = extract_http(&headers);
+        assert_eq!(
+            cx.span().span_context().trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(inject(&cx).get("traceparent").map(String::as_str), Some(TRACEPARENT));
+
+        let cx = extract_message(&[("Traceparent".to_string(), TRACEPARENT.to_string())]);
+        assert!(cx.span().span_context().is_remote());
+        assert!(inject(&Context::new()).is_empty());
+    }
+
+    /// 用一个只回 200 的 TCP 服务代替采集端，确认 span 以 OTLP/HTTP 发往配置的地址
+    #[test]
+    fn test_export_to_collector() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            tx.send((request_line, body.len())).unwrap();
+        });
+
+        let config = TraceConfig {
+            enabled: true,
+            endpoint: format!("http://{}/v1/traces", addr),
+            ..Default::default()
+        };
+        let provider = build_provider(&config).unwrap();
+        provider.tracer("test").start("test-span").end();
+        provider.force_flush().unwrap();
+
+        let (request_line, body_len) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(request_line.starts_with("POST /v1/traces "));
+        assert!(body_len > 0);
+        let _ = provider.shutdown();
+    }
+}
@@ -0,0 +1,272 @@
+//! 敏感字段加解密
+//!
+//! 采用信封加密：每个值使用随机生成的数据密钥（AES-256-GCM）加密，数据密钥再由
+//! 主密钥加密后与密文一起保存，格式为
+//! `enc:v2:<密钥id>:<Base64(nonce || 加密的数据密钥)>:<Base64(nonce || 密文)>`。
+//! 轮换主密钥时只需重新加密数据密钥，见 [`rotate`]。
+//!
+//! 兼容 `enc:v1:<Base64(nonce || 密文)>`（直接使用 `default` 主密钥加密）；
+//! 不带前缀的值视为历史明文，解密时原样返回，便于存量数据平滑迁移。
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::config::{CryptoConfig, DEFAULT_KEY_ID};
+
+const PREFIX: &str = "enc:";
+const PREFIX_V1: &str = "enc:v1:";
+const PREFIX_V2: &str = "enc:v2:";
+const NONCE_LEN: usize = 12;
+
+static KEYRING: OnceLock<Keyring> = OnceLock::new();
+
+/// 主密钥与盲索引密钥
+struct Keyring {
+    active_key_id: String,
+    keys: HashMap<String, Aes256Gcm>,
+    blind_index_key: Option<Vec<u8>>,
+}
+
+/// 初始化加密密钥，未配置时加密操作会返回错误
+pub fn init(config: Option<&CryptoConfig>) -> Result<()> {
+    let Some(config) = config else {
+        tracing::warn!("crypto key not configured, sensitive fields cannot be encrypted");
+        return Ok(());
+    };
+    let keys = config
+        .load_keys()?
+        .into_iter()
+        .map(|(key_id, key)| {
+            let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| anyhow!("invalid crypto key: {}", key_id))?;
+            Ok((key_id, cipher))
+        })
+        .collect::<Result<_>>()?;
+    let keyring = Keyring {
+        active_key_id: config.active_key_id.clone(),
+        keys,
+        blind_index_key: config.blind_index_key()?,
+    };
+    tracing::info!(active_key_id = %keyring.active_key_id, keys = keyring.keys.len(), "crypto keyring loaded");
+    let _ = KEYRING.set(keyring);
+    Ok(())
+}
+
+/// 是否已配置加密密钥
+pub fn is_configured() -> bool {
+    KEYRING.get().is_some()
+}
+
+/// 是否已配置盲索引密钥
+pub fn has_blind_index_key() -> bool {
+    KEYRING.get().is_some_and(|keyring| keyring.blind_index_key.is_some())
+}
+
+fn keyring() -> Result<&'static Keyring> {
+    KEYRING.get().ok_or_else(|| anyhow!("crypto key not configured"))
+}
+
+/// 加密敏感字段
+pub fn encrypt(plain: &str) -> Result<String> {
+    keyring()?.encrypt(plain)
+}
+
+/// 解密敏感字段，不带密文前缀的值原样返回
+pub fn decrypt(value: &str) -> Result<String> {
+    if !is_encrypted(value) {
+        return Ok(value.to_string());
+    }
+    keyring()?.decrypt(value)
+}
+
+/// 解密可空字段
+pub fn decrypt_opt(value: Option<&str>) -> Result<Option<String>> {
+    value.map(decrypt).transpose()
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+/// 是否为可以用当前密钥解密的 v1/v2 密文，仅带前缀的普通字符串不算
+pub fn is_valid_ciphertext(value: &str) -> bool {
+    is_encrypted(value) && keyring().and_then(|keyring| keyring.decrypt(value)).is_ok()
+}
+
+/// 是否需要加密或轮换：明文、旧格式或非当前主密钥加密的值
+pub fn needs_rotation(value: &str) -> Result<bool> {
+    let keyring = keyring()?;
+    Ok(match value.strip_prefix(PREFIX_V2) {
+        Some(rest) => rest.split(':').next() != Some(keyring.active_key_id.as_str()),
+        None => true,
+    })
+}
+
+/// 使用当前主密钥重新加密：明文与 v1 值整体加密，v2 值只重新加密数据密钥
+pub fn rotate(value: &str) -> Result<String> {
+    let keyring = keyring()?;
+    if value.starts_with(PREFIX_V2) {
+        keyring.rewrap(value)
+    } else {
+        keyring.encrypt(&decrypt(value)?)
+    }
+}
+
+/// 计算盲索引，用于密文字段的等值查询，值会先去除首尾空白
+pub fn blind_index(value: &str) -> Result<String> {
+    let key = keyring()?
+        .blind_index_key
+        .as_deref()
+        .ok_or_else(|| anyhow!("crypto.blind_index_key not configured"))?;
+    blind_index_with(key, value)
+}
+
+fn blind_index_with(key: &[u8], value: &str) -> Result<String> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).map_err(|_| anyhow!("invalid blind index key"))?;
+    mac.update(value.trim().as_bytes());
+    Ok(format!("{:x}", mac.finalize().into_bytes()))
+}
+
+impl Keyring {
+    fn key(&self, key_id: &str) -> Result<&Aes256Gcm> {
+        self.keys
+            .get(key_id)
+            .ok_or_else(|| anyhow!("crypto key not found: {}", key_id))
+    }
+
+    fn encrypt(&self, plain: &str) -> Result<String> {
+        let data_key = Aes256Gcm::generate_key(&mut OsRng);
+        let wrapped = seal(self.key(&self.active_key_id)?, &data_key)?;
+        let ciphertext = seal(&Aes256Gcm::new(&data_key), plain.as_bytes())?;
+        Ok(format!("{}{}:{}:{}", PREFIX_V2, self.active_key_id, wrapped, ciphertext))
+    }
+
+    fn decrypt(&self, value: &str) -> Result<String> {
+        if let Some(ciphertext) = value.strip_prefix(PREFIX_V1) {
+            let plain = open(self.key(DEFAULT_KEY_ID)?, ciphertext)?;
+            return Ok(String::from_utf8(plain)?);
+        }
+        let (key_id, wrapped, ciphertext) = parse_v2(value)?;
+        let data_key = open(self.key(key_id)?, wrapped)?;
+        let cipher = Aes256Gcm::new_from_slice(&data_key).map_err(|_| anyhow!("invalid data key"))?;
+        Ok(String::from_utf8(open(&cipher, ciphertext)?)?)
+    }
+
+    fn rewrap(&self, value: &str) -> Result<String> {
+        let (key_id, wrapped, ciphertext) = parse_v2(value)?;
+        if key_id == self.active_key_id {
+            return Ok(value.to_string());
+        }
+        let data_key = open(self.key(key_id)?, wrapped)?;
+        let wrapped = seal(self.key(&self.active_key_id)?, &data_key)?;
+        Ok(format!("{}{}:{}:{}", PREFIX_V2, self.active_key_id, wrapped, ciphertext))
+    }
+}
+
+/// 拆分 v2 密文为 (密钥id, 加密的数据密钥, 密文)
+fn parse_v2(value: &str) -> Result<(&str, &str, &str)> {
+    let mut parts = value
+        .strip_prefix(PREFIX_V2)
+        .ok_or_else(|| anyhow!("unsupported ciphertext format"))?
+        .splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(key_id), Some(wrapped), Some(ciphertext)) => Ok((key_id, wrapped, ciphertext)),
+        _ => Err(anyhow!("malformed ciphertext")),
+    }
+}
+
+/// 加密并编码为 `Base64(nonce || 密文)`
+fn seal(cipher: &Aes256Gcm, plain: &[u8]) -> Result<String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plain)
+        .map_err(|_| anyhow!("encrypt failed"))?;
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(BASE64_STANDARD.encode(payload))
+}
+
+fn open(cipher: &Aes256Gcm, encoded: &str) -> Result<Vec<u8>> {
+    let payload = BASE64_STANDARD.decode(encoded)?;
+    if payload.len() <= NONCE_LEN {
+        return Err(anyhow!("ciphertext too short"));
+    }
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into()?;
+    cipher
+        .decrypt(&Nonce::from(nonce), ciphertext)
+        .map_err(|_| anyhow!("decrypt failed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyring(active_key_id: &str) -> Keyring {
+        let keys = [("default", 7u8), ("k2", 8u8)]
+            .into_iter()
+            .map(|(key_id, byte)| (key_id.to_string(), Aes256Gcm::new_from_slice(&[byte; 32]).unwrap()))
+            .collect();
+        Keyring {
+            active_key_id: active_key_id.to_string(),
+            keys,
+            blind_index_key: None,
+        }
+    }
+
+    #[test]
+    fn test_envelope_roundtrip_and_rewrap() {
+        let old = keyring("default");
+        let encrypted = old.encrypt("13800000000").unwrap();
+
+        assert!(encrypted.starts_with("enc:v2:default:"));
+        assert_ne!(encrypted, old.encrypt("13800000000").unwrap());
+        assert_eq!(old.decrypt(&encrypted).unwrap(), "13800000000");
+
+        let new = keyring("k2");
+        let rotated = new.rewrap(&encrypted).unwrap();
+        assert!(rotated.starts_with("enc:v2:k2:"));
+        // 只重新加密数据密钥，密文部分不变
+        assert_eq!(rotated.rsplit(':').next(), encrypted.rsplit(':').next());
+        assert_eq!(new.decrypt(&rotated).unwrap(), "13800000000");
+    }
+
+    #[test]
+    fn test_decrypt_v1_with_default_key() {
+        let keyring = keyring("k2");
+        let v1 = format!("{}{}", PREFIX_V1, seal(keyring.key(DEFAULT_KEY_ID).unwrap(), b"smtp-secret").unwrap());
+
+        assert_eq!(keyring.decrypt(&v1).unwrap(), "smtp-secret");
+        assert!(parse_v2(&v1).is_err());
+    }
+
+    #[test]
+    fn test_blind_index_is_deterministic() {
+        let key = [1u8; 32];
+        let index = blind_index_with(&key, "110101199001011234").unwrap();
+
+        assert_eq!(index.len(), 64);
+        assert_eq!(index, blind_index_with(&key, " 110101199001011234 ").unwrap());
+        assert_ne!(index, blind_index_with(&[2u8; 32], "110101199001011234").unwrap());
+    }
+
+    #[test]
+    fn test_forged_prefix_is_not_ciphertext() {
+        let keyring = keyring("default");
+        assert!(keyring.decrypt("enc:v2:default:not:ciphertext").is_err());
+        assert!(keyring.decrypt("enc:v1:bm90LWNpcGhlcnRleHQ=").is_err());
+        assert!(!is_valid_ciphertext("enc:looks-encrypted"));
+    }
+
+    #[test]
+    fn test_decrypt_legacy_plaintext() {
+        assert_eq!(decrypt("legacy-plaintext").unwrap(), "legacy-plaintext");
+    }
+}
@@ -1,14 +1,16 @@
 pub mod param_validation_util;
 pub mod timer_util;
 pub mod error_util;
+pub mod crypto_util;
+pub mod rolling_file;
 use argon2::{
     Argon2, PasswordHash,
     password_hash::{SaltString, rand_core::OsRng},
 };
 use rand::Rng;
+use sha2::{Digest, Sha256};
 use std::iter;
 
-#[allow(dead_code)]
 #[inline]
 pub fn random_string(limit: usize) -> String {
     iter::repeat(())
@@ -19,7 +21,7 @@ pub fn random_string(limit: usize) -> String {
 }
 
 pub fn verify_password(password: &str, password_hash: &str) -> anyhow::Result<()> {
-    let hash = PasswordHash::new(&password_hash)
+    let hash = PasswordHash::new(password_hash)
         .map_err(|e| anyhow::anyhow!("invalid password hash: {}", e))?;
     let result = hash.verify_password(&[&Argon2::default()], password);
     match result {
@@ -34,3 +36,8 @@ pub fn hash_password(password: &str) -> anyhow::Result<String> {
         .map_err(|e| anyhow::anyhow!("failed to generate password hash: {}", e))?
         .to_string())
 }
+
+/// 计算 SHA-256 摘要，返回小写十六进制
+pub fn sha256_hex(value: impl AsRef<[u8]>) -> String {
+    format!("{:x}", Sha256::digest(value.as_ref()))
+}
@@ -0,0 +1,305 @@
+//! 按时间与大小切分的日志文件
+//!
+//! 正在写入的文件固定为 `{file_name}`，切分时重命名为 `{file_name}.{yyyyMMdd-HHmmss}`，
+//! 可选 gzip 压缩，随后按保留个数与保留时长清理旧文件。压缩与清理在独立线程中执行，
+//! 不阻塞日志写入。写入器内部出错只能输出到 stderr，不能再写 tracing 日志。
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, NaiveDateTime, Timelike};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rolling {
+    Minutely,
+    Hourly,
+    Daily,
+    Never,
+}
+
+impl FromStr for Rolling {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "minutely" => Ok(Self::Minutely),
+            "hourly" => Ok(Self::Hourly),
+            "daily" => Ok(Self::Daily),
+            "never" => Ok(Self::Never),
+            other => Err(anyhow!("不支持的 rolling: {}，可选 minutely | hourly | daily | never", other)),
+        }
+    }
+}
+
+impl Rolling {
+    /// `time` 所在周期结束的时间
+    fn next_after(self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = match self {
+            Self::Minutely => time.with_second(0)?,
+            Self::Hourly => time.with_minute(0)?.with_second(0)?,
+            Self::Daily => time.date().and_hms_opt(0, 0, 0)?,
+            Self::Never => return None,
+        }
+        .with_nanosecond(0)?;
+        let period = match self {
+            Self::Minutely => chrono::Duration::minutes(1),
+            Self::Hourly => chrono::Duration::hours(1),
+            _ => chrono::Duration::days(1),
+        };
+        Some(start + period)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RollingOptions {
+    pub directory: PathBuf,
+    pub file_name: String,
+    pub rolling: Rolling,
+    /// 单个文件的最大字节数，超过后切分
+    pub max_size: Option<u64>,
+    /// 保留的历史文件个数
+    pub max_files: Option<usize>,
+    /// 历史文件的保留时长
+    pub max_age: Option<Duration>,
+    /// 切分后 gzip 压缩历史文件
+    pub compress: bool,
+}
+
+pub struct RollingFile {
+    options: RollingOptions,
+    file: File,
+    size: u64,
+    next_rollover: Option<NaiveDateTime>,
+    maintenance: Option<JoinHandle<()>>,
+}
+
+impl RollingFile {
+    pub fn new(options: RollingOptions) -> Result<Self> {
+        fs::create_dir_all(&options.directory)
+            .map_err(|e| anyhow!("创建日志目录 {} 失败: {}", options.directory.display(), e))?;
+        let path = options.directory.join(&options.file_name);
+        let file = open_append(&path)?;
+        let metadata = file.metadata()?;
+        // 按已有文件的修改时间计算切分点，重启后跨周期的文件在首次写入时切分
+        let modified = metadata
+            .modified()
+            .map(|time| DateTime::<Local>::from(time).naive_local())
+            .unwrap_or_else(|_| Local::now().naive_local());
+        Ok(Self {
+            next_rollover: options.rolling.next_after(modified),
+            size: metadata.len(),
+            options,
+            file,
+            maintenance: None,
+        })
+    }
+
+    fn should_roll(&self, incoming: u64) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        self.options.max_size.is_some_and(|max| self.size + incoming > max)
+            || self
+                .next_rollover
+                .is_some_and(|time| Local::now().naive_local() >= time)
+    }
+
+    fn roll(&mut self) -> Result<()> {
+        self.file.flush()?;
+        let path = self.options.directory.join(&self.options.file_name);
+        let archive = self.archive_path();
+        fs::rename(&path, &archive)?;
+        self.file = open_append(&path)?;
+        self.size = 0;
+        self.next_rollover = self.options.rolling.next_after(Local::now().naive_local());
+
+        // 上一次的压缩与清理完成后再开始，避免两个线程处理同一批文件
+        self.finish_maintenance();
+        let options = self.options.clone();
+        self.maintenance = Some(std::thread::spawn(move || {
+            if options.compress
+                && let Err(e) = compress(&archive)
+            {
+                eprintln!("log compress {} error: {}", archive.display(), e);
+            }
+            if let Err(e) = cleanup(&options) {
+                eprintln!("log cleanup error: {}", e);
+            }
+        }));
+        Ok(())
+    }
+
+    fn archive_path(&self) -> PathBuf {
+        let base = format!(
+            "{}.{}",
+            self.options.file_name,
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        // 同一秒内多次切分时追加序号
+        (0..)
+            .map(|i| match i {
+                0 => base.clone(),
+                i => format!("{}.{}", base, i),
+            })
+            .map(|name| self.options.directory.join(name))
+            .find(|path| !path.exists() && !path.with_extension(gz_extension(path)).exists())
+            .expect("archive name")
+    }
+
+    fn finish_maintenance(&mut self) {
+        if let Some(handle) = self.maintenance.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.should_roll(buf.len() as u64)
+            && let Err(e) = self.roll()
+        {
+            eprintln!("log rotate error: {}", e);
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| anyhow!("打开日志文件 {} 失败: {}", path.display(), e))
+}
+
+/// `app.log.20250101-000000` 的压缩文件扩展名为 `20250101-000000.gz`
+fn gz_extension(path: &Path) -> String {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+    format!("{}.gz", extension)
+}
+
+fn compress(path: &Path) -> Result<()> {
+    let target = path.with_extension(gz_extension(path));
+    let mut source = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+    io::copy(&mut source, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+/// 按修改时间从新到旧保留 `max_files` 个历史文件，并删除超过 `max_age` 的文件
+fn cleanup(options: &RollingOptions) -> Result<()> {
+    if options.max_files.is_none() && options.max_age.is_none() {
+        return Ok(());
+    }
+    let mut archives: Vec<(PathBuf, SystemTime)> = fs::read_dir(&options.directory)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| is_archive(&options.file_name, &entry.file_name().to_string_lossy()))
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            Some((entry.path(), modified))
+        })
+        .collect();
+    archives.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    let now = SystemTime::now();
+    for (i, (path, modified)) in archives.iter().enumerate() {
+        let over_count = options.max_files.is_some_and(|max| i >= max);
+        let expired = options.max_age.is_some_and(|max_age| {
+            now.duration_since(*modified).is_ok_and(|age| age > max_age)
+        });
+        if over_count || expired {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// 只匹配切分产生的 `{file_name}.{yyyyMMdd-HHmmss}[.N][.gz]`，不误删同前缀的其他文件
+fn is_archive(file_name: &str, name: &str) -> bool {
+    let Some(suffix) = name.strip_prefix(file_name).and_then(|rest| rest.strip_prefix('.')) else {
+        return false;
+    };
+    let suffix = suffix.strip_suffix(".gz").unwrap_or(suffix);
+    let (timestamp, index) = match suffix.split_once('.') {
+        Some((timestamp, index)) => (timestamp, Some(index)),
+        None => (suffix, None),
+    };
+    NaiveDateTime::parse_from_str(timestamp, "%Y%m%d-%H%M%S").is_ok()
+        && timestamp.len() == "yyyyMMdd-HHmmss".len()
+        && index.is_none_or(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_after() {
+        let time = NaiveDateTime::parse_from_str("2025-01-01 10:20:30", "%Y-%m-%d %H:%M:%S").unwrap();
+        let next = |rolling: Rolling| rolling.next_after(time).map(|t| t.to_string());
+        assert_eq!(next(Rolling::Minutely).as_deref(), Some("2025-01-01 10:21:00"));
+        assert_eq!(next(Rolling::Hourly).as_deref(), Some("2025-01-01 11:00:00"));
+        assert_eq!(next(Rolling::Daily).as_deref(), Some("2025-01-02 00:00:00"));
+        assert_eq!(next(Rolling::Never), None);
+        assert!("weekly".parse::<Rolling>().is_err());
+    }
+
+    #[test]
+    fn test_is_archive() {
+        assert!(is_archive("app.log", "app.log.20250101-000000"));
+        assert!(is_archive("app.log", "app.log.20250101-000000.2"));
+        assert!(is_archive("app.log", "app.log.20250101-000000.2.gz"));
+        assert!(is_archive("app.log", "app.log.20250101-000000.gz"));
+        assert!(!is_archive("app.log", "app.log"));
+        assert!(!is_archive("app.log", "app.log.bak"));
+        assert!(!is_archive("app.log", "app.log.20250101-000000.old"));
+        assert!(!is_archive("app", "app.log.20250101-000000"));
+    }
+
+    #[test]
+    fn test_size_rotation_with_retention() {
+        let directory = std::env::temp_dir().join(format!("rolling-{}", ulid::Ulid::new()));
+        let mut file = RollingFile::new(RollingOptions {
+            directory: directory.clone(),
+            file_name: "app.log".into(),
+            rolling: Rolling::Never,
+            max_size: Some(10),
+            max_files: Some(1),
+            max_age: None,
+            compress: true,
+        })
+        .unwrap();
+        for _ in 0..3 {
+            file.write_all(b"12345678\n").unwrap();
+            // 切分名精确到秒，避免同一秒的文件排序不确定
+            std::thread::sleep(Duration::from_millis(1100));
+        }
+        file.finish_maintenance();
+
+        let mut names: Vec<String> = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[0], "app.log");
+        assert!(names[1].ends_with(".gz"));
+        assert_eq!(fs::read(directory.join("app.log")).unwrap(), b"12345678\n");
+        let _ = fs::remove_dir_all(&directory);
+    }
+}
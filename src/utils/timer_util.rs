@@ -19,146 +19,146 @@ impl FormatTime for LocalTimeWithMillis {
 /// 获取本地当前时间 yyyy-mm-dd hh:mm:ss
 #[allow(dead_code)]
 pub async fn now_local_timer() -> String {
-    return Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取UTC当前时间 yyyy-mm-dd hh:mm:ss
 #[allow(dead_code)]
 pub async fn now_utc_timer() -> String {
-    return Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 
 /// 获取本地当前时间 yyyy-mm-dd hh:mm:ss.sss
 #[allow(dead_code)]
 pub async fn now_local_timer_with_millis() -> String {
-    return Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string()
 }
 
 /// 获取UTC当前时间 yyyy-mm-dd hh:mm:ss.sss
 #[allow(dead_code)]
 pub async fn now_utc_timer_with_millis() -> String {
-    return Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string()
 }
 
 /// 获取本地当前日期 yyyy-mm-dd
 #[allow(dead_code)]
 pub async fn now_local_date() -> String {
-    return Local::now().format("%Y-%m-%d").to_string();
+    Local::now().format("%Y-%m-%d").to_string()
 }
 
 /// 获取UTC当前日期 yyyy-mm-dd
 #[allow(dead_code)]
 pub async fn now_utc_date() -> String {
-    return Utc::now().format("%Y-%m-%d").to_string();
+    Utc::now().format("%Y-%m-%d").to_string()
 }
 
 /// 获取本地当前时间戳（秒）
 #[allow(dead_code)]
 pub async fn now_local_timestamp() -> i64 {
-    return Local::now().timestamp();
+    Local::now().timestamp()
 }
 
 /// 获取UTC当前时间戳（秒）
 #[allow(dead_code)]
 pub async fn now_utc_timestamp() -> i64 {
-    return Utc::now().timestamp();
+    Utc::now().timestamp()
 }
 
 /// 获取本地当前时间戳（毫秒）
 #[allow(dead_code)]
 pub async fn now_local_timestamp_millis() -> i64 {
-    return Local::now().timestamp_millis();
+    Local::now().timestamp_millis()
 }
 
 /// 获取UTC当前时间戳（毫秒）
 #[allow(dead_code)]
 pub async fn now_utc_timestamp_millis() -> i64 {
-    return Utc::now().timestamp_millis();
+    Utc::now().timestamp_millis()
 }
 
 /// 获取本地当前年份
 #[allow(dead_code)]
 pub async fn now_local_year() -> i32 {
-    return Local::now().year();
+    Local::now().year()
 }
 
 /// 获取UTC当前年份
 #[allow(dead_code)]
 pub async fn now_utc_year() -> i32 {
-    return Utc::now().year();
+    Utc::now().year()
 }
 
 /// 获取本地当前月份 (1-12)
 #[allow(dead_code)]
 pub async fn now_local_month() -> u32 {
-    return Local::now().month();
+    Local::now().month()
 }
 
 /// 获取UTC当前月份 (1-12)
 #[allow(dead_code)]
 pub async fn now_utc_month() -> u32 {
-    return Utc::now().month();
+    Utc::now().month()
 }
 
 /// 获取本地当前日 (1-31)
 #[allow(dead_code)]
 pub async fn now_local_day() -> u32 {
-    return Local::now().day();
+    Local::now().day()
 }
 
 /// 获取UTC当前日 (1-31)
 #[allow(dead_code)]
 pub async fn now_utc_day() -> u32 {
-    return Utc::now().day();
+    Utc::now().day()
 }
 
 /// 获取本地当前星期几 (1=周一, 7=周日)
 #[allow(dead_code)]
 pub async fn now_local_weekday() -> u32 {
-    return Local::now().weekday().number_from_monday();
+    Local::now().weekday().number_from_monday()
 }
 
 /// 获取UTC当前星期几 (1=周一, 7=周日)
 #[allow(dead_code)]
 pub async fn now_utc_weekday() -> u32 {
-    return Utc::now().weekday().number_from_monday();
+    Utc::now().weekday().number_from_monday()
 }
 
 /// 获取本地当前小时 (0-23)
 #[allow(dead_code)]
 pub async fn now_local_hour() -> u32 {
-    return Local::now().hour();
+    Local::now().hour()
 }
 
 /// 获取UTC当前小时 (0-23)
 #[allow(dead_code)]
 pub async fn now_utc_hour() -> u32 {
-    return Utc::now().hour();
+    Utc::now().hour()
 }
 
 /// 获取本地当前分钟 (0-59)
 #[allow(dead_code)]
 pub async fn now_local_minute() -> u32 {
-    return Local::now().minute();
+    Local::now().minute()
 }
 
 /// 获取UTC当前分钟 (0-59)
 #[allow(dead_code)]
 pub async fn now_utc_minute() -> u32 {
-    return Utc::now().minute();
+    Utc::now().minute()
 }
 
 /// 获取本地当前秒 (0-59)
 #[allow(dead_code)]
 pub async fn now_local_second() -> u32 {
-    return Local::now().second();
+    Local::now().second()
 }
 
 /// 获取UTC当前秒 (0-59)
 #[allow(dead_code)]
 pub async fn now_utc_second() -> u32 {
-    return Utc::now().second();
+    Utc::now().second()
 }
 
 /// 获取本地N天前的时间 yyyy-mm-dd hh:mm:ss
@@ -166,7 +166,7 @@ pub async fn now_utc_second() -> u32 {
 pub async fn local_days_ago(days: i64) -> String {
     let now = Local::now();
     let past = now - Duration::days(days);
-    return past.format("%Y-%m-%d %H:%M:%S").to_string();
+    past.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取UTC N天前的时间 yyyy-mm-dd hh:mm:ss
@@ -174,7 +174,7 @@ pub async fn local_days_ago(days: i64) -> String {
 pub async fn utc_days_ago(days: i64) -> String {
     let now = Utc::now();
     let past = now - Duration::days(days);
-    return past.format("%Y-%m-%d %H:%M:%S").to_string();
+    past.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取本地N天后的时间 yyyy-mm-dd hh:mm:ss
@@ -182,7 +182,7 @@ pub async fn utc_days_ago(days: i64) -> String {
 pub async fn local_days_after(days: i64) -> String {
     let now = Local::now();
     let future = now + Duration::days(days);
-    return future.format("%Y-%m-%d %H:%M:%S").to_string();
+    future.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取UTC N天后的时间 yyyy-mm-dd hh:mm:ss
@@ -190,7 +190,7 @@ pub async fn local_days_after(days: i64) -> String {
 pub async fn utc_days_after(days: i64) -> String {
     let now = Utc::now();
     let future = now + Duration::days(days);
-    return future.format("%Y-%m-%d %H:%M:%S").to_string();
+    future.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取本地N小时前的时间 yyyy-mm-dd hh:mm:ss
@@ -198,7 +198,7 @@ pub async fn utc_days_after(days: i64) -> String {
 pub async fn local_hours_ago(hours: i64) -> String {
     let now = Local::now();
     let past = now - Duration::hours(hours);
-    return past.format("%Y-%m-%d %H:%M:%S").to_string();
+    past.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取UTC N小时前的时间 yyyy-mm-dd hh:mm:ss
@@ -206,7 +206,7 @@ pub async fn local_hours_ago(hours: i64) -> String {
 pub async fn utc_hours_ago(hours: i64) -> String {
     let now = Utc::now();
     let past = now - Duration::hours(hours);
-    return past.format("%Y-%m-%d %H:%M:%S").to_string();
+    past.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取本地N小时后的时间 yyyy-mm-dd hh:mm:ss
@@ -214,7 +214,7 @@ pub async fn utc_hours_ago(hours: i64) -> String {
 pub async fn local_hours_after(hours: i64) -> String {
     let now = Local::now();
     let future = now + Duration::hours(hours);
-    return future.format("%Y-%m-%d %H:%M:%S").to_string();
+    future.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取UTC N小时后的时间 yyyy-mm-dd hh:mm:ss
@@ -222,7 +222,7 @@ pub async fn local_hours_after(hours: i64) -> String {
 pub async fn utc_hours_after(hours: i64) -> String {
     let now = Utc::now();
     let future = now + Duration::hours(hours);
-    return future.format("%Y-%m-%d %H:%M:%S").to_string();
+    future.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取本地N分钟前的时间 yyyy-mm-dd hh:mm:ss
@@ -230,7 +230,7 @@ pub async fn utc_hours_after(hours: i64) -> String {
 pub async fn local_minutes_ago(minutes: i64) -> String {
     let now = Local::now();
     let past = now - Duration::minutes(minutes);
-    return past.format("%Y-%m-%d %H:%M:%S").to_string();
+    past.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取UTC N分钟前的时间 yyyy-mm-dd hh:mm:ss
@@ -238,7 +238,7 @@ pub async fn local_minutes_ago(minutes: i64) -> String {
 pub async fn utc_minutes_ago(minutes: i64) -> String {
     let now = Utc::now();
     let past = now - Duration::minutes(minutes);
-    return past.format("%Y-%m-%d %H:%M:%S").to_string();
+    past.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取本地N分钟后的时间 yyyy-mm-dd hh:mm:ss
@@ -246,7 +246,7 @@ pub async fn utc_minutes_ago(minutes: i64) -> String {
 pub async fn local_minutes_after(minutes: i64) -> String {
     let now = Local::now();
     let future = now + Duration::minutes(minutes);
-    return future.format("%Y-%m-%d %H:%M:%S").to_string();
+    future.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 获取UTC N分钟后的时间 yyyy-mm-dd hh:mm:ss
@@ -254,7 +254,7 @@ pub async fn local_minutes_after(minutes: i64) -> String {
 pub async fn utc_minutes_after(minutes: i64) -> String {
     let now = Utc::now();
     let future = now + Duration::minutes(minutes);
-    return future.format("%Y-%m-%d %H:%M:%S").to_string();
+    future.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 格式化本地时间戳为字符串 yyyy-mm-dd hh:mm:ss
@@ -262,14 +262,14 @@ pub async fn utc_minutes_after(minutes: i64) -> String {
 pub async fn format_local_timestamp(timestamp: i64) -> String {
     let dt = DateTime::from_timestamp(timestamp, 0).unwrap();
     let local_dt = dt.with_timezone(&Local);
-    return local_dt.format("%Y-%m-%d %H:%M:%S").to_string();
+    local_dt.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 格式化UTC时间戳为字符串 yyyy-mm-dd hh:mm:ss
 #[allow(dead_code)]
 pub async fn format_utc_timestamp(timestamp: i64) -> String {
     let dt = DateTime::from_timestamp(timestamp, 0).unwrap();
-    return dt.format("%Y-%m-%d %H:%M:%S").to_string();
+    dt.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
 /// 解析时间字符串为本地时间戳（支持 yyyy-mm-dd hh:mm:ss）
@@ -286,11 +286,10 @@ pub async fn parse_local_timestamp(time_str: &str) -> Option<i64> {
 #[allow(dead_code)]
 pub async fn parse_utc_timestamp(time_str: &str) -> Option<i64> {
     let format = "%Y-%m-%d %H:%M:%S";
-    if let Ok(dt) = NaiveDate::parse_from_str(time_str, format) {
-        if let Some(dt) = dt.and_hms_opt(0, 0, 0) {
+    if let Ok(dt) = NaiveDate::parse_from_str(time_str, format)
+        && let Some(dt) = dt.and_hms_opt(0, 0, 0) {
             return Some(dt.and_utc().timestamp());
         }
-    }
     None
 }
 
@@ -300,7 +299,7 @@ pub async fn local_monday_date() -> String {
     let now = Local::now();
     let days_since_monday = now.weekday().num_days_from_monday();
     let monday = now - Duration::days(days_since_monday as i64);
-    return monday.format("%Y-%m-%d").to_string();
+    monday.format("%Y-%m-%d").to_string()
 }
 
 /// 获取本周一的UTC日期 yyyy-mm-dd
@@ -309,7 +308,7 @@ pub async fn utc_monday_date() -> String {
     let now = Utc::now();
     let days_since_monday = now.weekday().num_days_from_monday();
     let monday = now - Duration::days(days_since_monday as i64);
-    return monday.format("%Y-%m-%d").to_string();
+    monday.format("%Y-%m-%d").to_string()
 }
 
 /// 获取本月第一天的本地日期 yyyy-mm-dd
@@ -317,7 +316,7 @@ pub async fn utc_monday_date() -> String {
 pub async fn local_first_day_of_month() -> String {
     let now = Local::now();
     let first_day = now.with_day(1).unwrap();
-    return first_day.format("%Y-%m-%d").to_string();
+    first_day.format("%Y-%m-%d").to_string()
 }
 
 /// 获取本月第一天的UTC日期 yyyy-mm-dd
@@ -325,7 +324,7 @@ pub async fn local_first_day_of_month() -> String {
 pub async fn utc_first_day_of_month() -> String {
     let now = Utc::now();
     let first_day = now.with_day(1).unwrap();
-    return first_day.format("%Y-%m-%d").to_string();
+    first_day.format("%Y-%m-%d").to_string()
 }
 
 /// 获取本月最后一天的本地日期 yyyy-mm-dd
@@ -334,7 +333,7 @@ pub async fn local_last_day_of_month() -> String {
     let now = Local::now();
     let next_month = now.with_month(now.month() + 1).unwrap_or(now.with_month(1).unwrap().with_year(now.year() + 1).unwrap());
     let last_day = next_month.with_day(1).unwrap() - Duration::days(1);
-    return last_day.format("%Y-%m-%d").to_string();
+    last_day.format("%Y-%m-%d").to_string()
 }
 
 /// 获取本月最后一天的UTC日期 yyyy-mm-dd
@@ -343,7 +342,7 @@ pub async fn utc_last_day_of_month() -> String {
     let now = Utc::now();
     let next_month = now.with_month(now.month() + 1).unwrap_or(now.with_month(1).unwrap().with_year(now.year() + 1).unwrap());
     let last_day = next_month.with_day(1).unwrap() - Duration::days(1);
-    return last_day.format("%Y-%m-%d").to_string();
+    last_day.format("%Y-%m-%d").to_string()
 }
 
 /// 计算两个时间戳之间的天数差（本地时间）
@@ -352,7 +351,7 @@ pub async fn days_between_local(start_timestamp: i64, end_timestamp: i64) -> i64
     let start_dt = DateTime::from_timestamp(start_timestamp, 0).unwrap();
     let end_dt = DateTime::from_timestamp(end_timestamp, 0).unwrap();
     let duration = end_dt - start_dt;
-    return duration.num_days();
+    duration.num_days()
 }
 
 /// 计算两个时间戳之间的天数差（UTC时间）
@@ -361,7 +360,7 @@ pub async fn days_between_utc(start_timestamp: i64, end_timestamp: i64) -> i64 {
     let start_dt = DateTime::from_timestamp(start_timestamp, 0).unwrap();
     let end_dt = DateTime::from_timestamp(end_timestamp, 0).unwrap();
     let duration = end_dt - start_dt;
-    return duration.num_days();
+    duration.num_days()
 }
 
 /// 检查时间字符串是否是有效的 yyyy-mm-dd hh:mm:ss 格式
@@ -375,7 +374,7 @@ pub async fn is_valid_time_format(time_str: &str) -> bool {
 #[allow(dead_code)]
 pub async fn now_quarter() -> u32 {
     let month = Utc::now().month();
-    return (month - 1) / 3 + 1;
+    (month - 1) / 3 + 1
 }
 
 /// 获取当前是否在营业时间（9:00-18:00）
@@ -383,12 +382,12 @@ pub async fn now_quarter() -> u32 {
 pub async fn is_business_hours() -> bool {
     let now = Local::now();
     let hour = now.hour();
-    return hour >= 9 && hour < 18;
+    (9..18).contains(&hour)
 }
 
 /// 获取当前是否工作日（周一至周五）
 #[allow(dead_code)]
 pub async fn is_weekday() -> bool {
     let weekday = Local::now().weekday();
-    return weekday != chrono::Weekday::Sat && weekday != chrono::Weekday::Sun;
+    weekday != chrono::Weekday::Sat && weekday != chrono::Weekday::Sun
 }
\ No newline at end of file